@@ -0,0 +1,172 @@
+use super::{Delay, Printer, PrinterError, UnderlineMode, Write};
+
+const ESC: u8 = 0x1B;
+
+/// Parameters kept per escape sequence; later ones are ignored.
+const MAX_PARAMS: usize = 8;
+
+#[derive(Copy, Clone)]
+enum State {
+    Text,
+    Escape,
+    Csi,
+}
+
+/// Text writer translating ANSI SGR escape sequences into printer styles.
+///
+/// Returned by [`Printer::ansi`]. Terminal output such as coloured logs or
+/// CLI tables can be written to it unchanged: bold (`1`, `22`), underline
+/// (`4`, `21`, `24`), inverse (`7`, `27`) and reset (`0`) are turned into the
+/// matching ESC/POS commands, and every other escape sequence, including
+/// colours and cursor movement, is dropped.
+///
+/// Sequences may be split across writes.
+///
+/// ```
+/// # use escpos_embedded::{Printer, Recorder};
+/// let mut printer = Printer::new(Recorder::<64>::new());
+/// printer.ansi().write(b"\x1b[1mERROR\x1b[0m disk full\n").unwrap();
+/// let expected: &[u8] = b"\x1bE\x01ERROR\x1bE\x00\x1b-\x00\x1dB\x00 disk full\n";
+/// assert_eq!(printer.transport().as_bytes(), expected);
+/// ```
+pub struct AnsiWriter<'a, T: Write, P: Delay = ()> {
+    printer: &'a mut Printer<T, P>,
+    state: State,
+    params: [u16; MAX_PARAMS],
+    count: usize,
+}
+
+impl<'a, T: Write, P: Delay> AnsiWriter<'a, T, P> {
+    pub(crate) fn new(printer: &'a mut Printer<T, P>) -> Self {
+        Self {
+            printer,
+            state: State::Text,
+            params: [0; MAX_PARAMS],
+            count: 0,
+        }
+    }
+
+    /// Write `data`, translating any escape sequences in it.
+    pub fn write(&mut self, data: &[u8]) -> Result<(), PrinterError<<T as Write>::Error>> {
+        let mut rest = data;
+        while !rest.is_empty() {
+            if let State::Text = self.state {
+                let run = rest.iter().position(|&b| b == ESC).unwrap_or(rest.len());
+                if run > 0 {
+                    self.printer.write_text_bytes(&rest[..run])?;
+                }
+                rest = &rest[run..];
+                if rest.is_empty() {
+                    break;
+                }
+            }
+            let byte = rest[0];
+            rest = &rest[1..];
+            self.state = match (self.state, byte) {
+                (State::Text, _) => State::Escape,
+                (State::Escape, b'[') => {
+                    self.params = [0; MAX_PARAMS];
+                    self.count = 1;
+                    State::Csi
+                }
+                (State::Escape, _) => State::Text,
+                (State::Csi, b'0'..=b'9') => {
+                    if let Some(param) = self.params.get_mut(self.count - 1) {
+                        *param = param
+                            .saturating_mul(10)
+                            .saturating_add(u16::from(byte - b'0'));
+                    }
+                    State::Csi
+                }
+                (State::Csi, b';') => {
+                    self.count += 1;
+                    State::Csi
+                }
+                (State::Csi, b'm') => {
+                    self.apply()?;
+                    State::Text
+                }
+                // Any other final byte ends a sequence that isn't SGR.
+                (State::Csi, 0x40..=0x7E) => State::Text,
+                (State::Csi, _) => State::Csi,
+            };
+        }
+        Ok(())
+    }
+
+    /// Apply the parameters of an SGR sequence.
+    fn apply(&mut self) -> Result<(), PrinterError<<T as Write>::Error>> {
+        let count = self.count.min(MAX_PARAMS);
+        for i in 0..count {
+            match self.params[i] {
+                0 => {
+                    self.printer.set_bold(false)?;
+                    self.printer.set_underline(UnderlineMode::None)?;
+                    self.printer.set_invert(false)?;
+                }
+                1 => self.printer.set_bold(true)?,
+                22 => self.printer.set_bold(false)?,
+                4 => self.printer.set_underline(UnderlineMode::Single)?,
+                21 => self.printer.set_underline(UnderlineMode::Double)?,
+                24 => self.printer.set_underline(UnderlineMode::None)?,
+                7 => self.printer.set_invert(true)?,
+                27 => self.printer.set_invert(false)?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: Write, P: Delay> core::fmt::Write for AnsiWriter<'_, T, P> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.write(s.as_bytes()).map_err(|_| core::fmt::Error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Write, P: Delay> std::io::Write for AnsiWriter<'_, T, P>
+where
+    T::Error: core::fmt::Debug,
+{
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        AnsiWriter::write(self, buf)
+            .map_err(|err| std::io::Error::other(std::format!("{:?}", err)))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<T: Write, P: Delay> Printer<T, P> {
+    /// Write text containing ANSI escape sequences, translating their
+    /// styles; see [`AnsiWriter`].
+    pub fn ansi(&mut self) -> AnsiWriter<'_, T, P> {
+        AnsiWriter::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockTransport;
+
+    #[test]
+    fn test_split_sequences() {
+        let mut printer = Printer::new(MockTransport::new());
+        let mut ansi = printer.ansi();
+        ansi.write(b"a\x1b[4;3").unwrap();
+        ansi.write(b"1mb\x1b").unwrap();
+        ansi.write(b"[2Kc\x1b[24m").unwrap();
+        assert_eq!(printer.transport().written(), b"a\x1b-\x01bc\x1b-\x00");
+    }
+
+    #[test]
+    fn test_inverse_and_drop_others() {
+        let mut printer = Printer::new(MockTransport::new());
+        printer.ansi().write(b"\x1b[7mX\x1b[27m\x1b]Y").unwrap();
+        assert_eq!(printer.transport().written(), b"\x1dB\x01X\x1dB\x00Y");
+    }
+}
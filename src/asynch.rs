@@ -0,0 +1,256 @@
+use super::{batch::Batch, command, Command, CutMode, PrinterError};
+
+/// Async counterpart of [`Write`](crate::Write).
+#[allow(async_fn_in_trait)]
+pub trait AsyncWrite {
+    /// Error type produced when writing fails.
+    type Error;
+
+    /// Write raw bytes to the transport.
+    async fn write(&mut self, data: &[u8]) -> Result<(), Self::Error>;
+}
+
+impl<T: AsyncWrite + ?Sized> AsyncWrite for &mut T {
+    type Error = T::Error;
+
+    async fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        (**self).write(data).await
+    }
+}
+
+/// ESC/POS printer driver for async transports.
+///
+/// Mirrors the output side of [`Printer`](crate::Printer); commands are
+/// encoded the same way and errors carry the same context.
+pub struct AsyncPrinter<T: AsyncWrite> {
+    transport: T,
+}
+
+impl<T: AsyncWrite> AsyncPrinter<T> {
+    /// Create a new printer from the given transport.
+    pub fn new(transport: T) -> Self {
+        Self { transport }
+    }
+
+    /// Borrow the underlying transport.
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// Mutably borrow the underlying transport.
+    pub fn transport_mut(&mut self) -> &mut T {
+        &mut self.transport
+    }
+
+    /// Consume the printer, returning the underlying transport.
+    pub fn into_inner(self) -> T {
+        self.transport
+    }
+
+    /// Encode and send a single command.
+    ///
+    /// Parameters are checked with [`Command::validate`] first.
+    pub async fn send(&mut self, command: &Command<'_>) -> Result<(), PrinterError<T::Error>> {
+        command.validate()?;
+        let name = command.name();
+        let transport = |error| PrinterError::Transport {
+            command: name,
+            error,
+        };
+        match *command {
+            Command::Text(text) => self.transport.write(text.as_bytes()).await,
+            Command::Raw(data) | Command::Vendor { data, .. } | Command::Unknown(data) => {
+                self.transport.write(data).await
+            }
+            Command::WriteUserMemory { address, data } => {
+                let header = command::user_memory_header(b'1', address, data.len() as u16);
+                self.transport.write(&header).await.map_err(transport)?;
+                self.transport.write(data).await
+            }
+            Command::RasterImage {
+                width,
+                height,
+                scale,
+                data,
+            } => {
+                let header = command::raster_header(width, height, scale);
+                self.transport.write(&header).await.map_err(transport)?;
+                self.transport.write(data).await
+            }
+            Command::BitImage { mode, width, data } => {
+                let header = command::bit_image_header(mode, width);
+                self.transport.write(&header).await.map_err(transport)?;
+                self.transport.write(data).await
+            }
+            Command::DefineDownloadedImage {
+                width,
+                height,
+                data,
+            } => {
+                let header = [0x1D, 0x2A, width, height];
+                self.transport.write(&header).await.map_err(transport)?;
+                self.transport.write(data).await
+            }
+            Command::StoreGraphics {
+                color,
+                width,
+                height,
+                data,
+            } => {
+                let (header, len) =
+                    command::store_graphics_header(color, width, height, data.len());
+                self.transport
+                    .write(&header[..len])
+                    .await
+                    .map_err(transport)?;
+                self.transport.write(data).await
+            }
+            _ => {
+                // Every other command is a short fixed sequence.
+                let mut buf = [0u8; 32];
+                let len = command
+                    .encode_to_slice(&mut buf)
+                    .map_err(|_| PrinterError::Encode { command: name })?;
+                self.transport.write(&buf[..len]).await
+            }
+        }
+        .map_err(transport)
+    }
+
+    /// Write raw text to the printer.
+    pub async fn write(&mut self, text: &str) -> Result<(), PrinterError<T::Error>> {
+        self.send(&Command::Text(text)).await
+    }
+
+    /// Write text followed by a newline.
+    pub async fn write_line(&mut self, text: &str) -> Result<(), PrinterError<T::Error>> {
+        self.write(text).await?;
+        self.send(&Command::LineFeed).await
+    }
+
+    /// Print each of `lines` followed by a newline, gathered into 128-byte
+    /// writes.
+    pub async fn print_lines<'a>(
+        &mut self,
+        lines: impl IntoIterator<Item = &'a str>,
+    ) -> Result<(), PrinterError<T::Error>> {
+        let transport = |error| PrinterError::Transport {
+            command: "Text",
+            error,
+        };
+        let mut batch = Batch::new();
+        for line in lines {
+            for mut data in [line.as_bytes(), b"\n"] {
+                while !data.is_empty() {
+                    if let Some(full) = batch.fill(&mut data) {
+                        self.transport.write(full).await.map_err(transport)?;
+                    }
+                }
+            }
+        }
+        match batch.rest() {
+            [] => Ok(()),
+            rest => self.transport.write(rest).await.map_err(transport),
+        }
+    }
+
+    /// Feed the specified number of lines, split into `ESC d` commands of
+    /// at most 255 lines.
+    pub async fn feed(&mut self, lines: u16) -> Result<(), PrinterError<T::Error>> {
+        let mut left = lines;
+        loop {
+            let n = left.min(255) as u8;
+            self.send(&Command::Feed(n)).await?;
+            left -= u16::from(n);
+            if left == 0 {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Cut the paper using the given mode.
+    pub async fn cut(&mut self, mode: CutMode) -> Result<(), PrinterError<T::Error>> {
+        self.send(&Command::Cut(mode)).await
+    }
+
+    /// Send raw bytes directly to the printer.
+    pub async fn raw(&mut self, data: &[u8]) -> Result<(), PrinterError<T::Error>> {
+        self.send(&Command::Raw(data)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{block_on, MockTransport};
+    use crate::{Align, BitImageMode, Color};
+
+    #[test]
+    fn test_send_matches_sync_encoding() {
+        let mut printer = AsyncPrinter::new(MockTransport::new());
+        block_on(async {
+            printer.send(&Command::SetAlign(Align::Center)).await?;
+            printer.write_line("Hi").await?;
+            printer.send(&Command::SetBaudRate(9600)).await?;
+            printer.cut(CutMode::Partial).await
+        })
+        .unwrap();
+        let mut expected = [0x1B, 0x61, 0x01, b'H', b'i', b'\n'].to_vec();
+        expected.extend_from_slice(&[0x1B, 0x23, 0x23, b'S', b'B', b'D', b'R', 0x80, 0x25, 0, 0]);
+        expected.extend_from_slice(&[0x1D, 0x56, 0x01]);
+        assert_eq!(printer.transport().written(), expected);
+    }
+
+    #[test]
+    fn test_print_lines() {
+        let mut printer = AsyncPrinter::new(MockTransport::new());
+        block_on(printer.print_lines(["one", "two"])).unwrap();
+        assert_eq!(printer.transport().written(), b"one\ntwo\n");
+    }
+
+    #[test]
+    fn test_send_bit_image_band() {
+        let data = [0xAA; 3 * 40];
+        let command = Command::BitImage {
+            mode: BitImageMode::DoubleDensity24,
+            width: 40,
+            data: &data,
+        };
+        let mut printer = AsyncPrinter::new(MockTransport::new());
+        block_on(printer.send(&command)).unwrap();
+        let mut expected = [0x1B, 0x2A, 33, 40, 0].to_vec();
+        expected.extend_from_slice(&data);
+        assert_eq!(printer.transport().written(), expected);
+    }
+
+    #[test]
+    fn test_send_images() {
+        // A 384 x 200 dot graphic, as printed on 80 mm paper.
+        let plane = [0x55; 48 * 200];
+        let downloaded = [0xF0; 8 * 8 * 8];
+        let commands = [
+            Command::StoreGraphics {
+                color: Color::Red,
+                width: 384,
+                height: 200,
+                data: &plane,
+            },
+            Command::DefineDownloadedImage {
+                width: 8,
+                height: 8,
+                data: &downloaded,
+            },
+        ];
+        let mut printer = AsyncPrinter::new(MockTransport::new());
+        let mut expected = MockTransport::new();
+        block_on(async {
+            for command in &commands {
+                printer.send(command).await?;
+                command.encode(&mut expected).unwrap();
+            }
+            Ok::<_, PrinterError<_>>(())
+        })
+        .unwrap();
+        assert_eq!(printer.transport().written(), expected.written());
+    }
+}
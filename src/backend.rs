@@ -0,0 +1,106 @@
+use super::{
+    command, Align, BitImageScale, Command, CutMode, Delay, Density, Printer, PrinterError, Write,
+};
+
+/// A printer protocol that [`Command`]s can be sent in.
+///
+/// [`Printer`] speaks ESC/POS; other families (such as the BLE "cat
+/// printers") implement `Backend` themselves, translating what they can
+/// and returning [`PrinterError::Unsupported`] for the rest. Receipt code
+/// written against `Backend` prints on any of them:
+///
+/// ```
+/// use escpos_embedded::{Align, Backend, PrinterError};
+///
+/// fn print_ticket<B: Backend>(printer: &mut B, number: &str) -> Result<(), PrinterError<B::Error>> {
+///     printer.set_align(Align::Center)?;
+///     printer.write_line("Your ticket")?;
+///     printer.write_line(number)?;
+///     printer.feed(3)
+/// }
+/// ```
+pub trait Backend {
+    /// Transport error type.
+    type Error;
+
+    /// Send a single command.
+    fn send(&mut self, command: &Command) -> Result<(), PrinterError<Self::Error>>;
+
+    /// Print text without a line feed.
+    fn write(&mut self, text: &str) -> Result<(), PrinterError<Self::Error>> {
+        self.send(&Command::Text(text))
+    }
+
+    /// Print text followed by a line feed.
+    fn write_line(&mut self, text: &str) -> Result<(), PrinterError<Self::Error>> {
+        self.write(text)?;
+        self.send(&Command::LineFeed)
+    }
+
+    /// Feed the given number of lines.
+    fn feed(&mut self, lines: u16) -> Result<(), PrinterError<Self::Error>> {
+        let mut left = lines;
+        loop {
+            let n = left.min(255) as u8;
+            self.send(&Command::Feed(n))?;
+            left -= u16::from(n);
+            if left == 0 {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Cut the paper.
+    fn cut(&mut self, mode: CutMode) -> Result<(), PrinterError<Self::Error>> {
+        self.send(&Command::Cut(mode))
+    }
+
+    /// Set the alignment of following lines.
+    fn set_align(&mut self, align: Align) -> Result<(), PrinterError<Self::Error>> {
+        self.send(&Command::SetAlign(align))
+    }
+
+    /// Set the print density.
+    fn set_density(&mut self, density: Density) -> Result<(), PrinterError<Self::Error>> {
+        self.send(&Command::SetDensity(density))
+    }
+
+    /// Print a 1-bit raster image, rows packed MSB first.
+    fn print_raster(
+        &mut self,
+        width: u16,
+        height: u16,
+        data: &[u8],
+    ) -> Result<(), PrinterError<Self::Error>> {
+        let expected = command::image_len("RasterImage", width, height, 8)?;
+        if data.len() != expected {
+            return Err(PrinterError::ImageSizeMismatch {
+                command: "RasterImage",
+                expected,
+                actual: data.len(),
+            });
+        }
+        self.send(&Command::RasterImage {
+            width,
+            height,
+            scale: BitImageScale::Normal,
+            data,
+        })
+    }
+}
+
+impl<T: Write, P: Delay> Backend for Printer<T, P> {
+    type Error = T::Error;
+
+    fn send(&mut self, command: &Command) -> Result<(), PrinterError<T::Error>> {
+        Printer::send(self, command)
+    }
+
+    fn feed(&mut self, lines: u16) -> Result<(), PrinterError<T::Error>> {
+        Printer::feed(self, lines)
+    }
+
+    fn cut(&mut self, mode: CutMode) -> Result<(), PrinterError<T::Error>> {
+        Printer::cut(self, mode)
+    }
+}
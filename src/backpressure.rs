@@ -0,0 +1,169 @@
+use super::{Delay, Read, Write};
+
+/// `DLE EOT 1`: transmit printer status.
+const QUERY: [u8; 3] = [0x10, 0x04, 0x01];
+/// Offline bit of the `DLE EOT 1` response.
+const OFFLINE: u8 = 0x08;
+
+/// Errors produced by a [`Backpressure`] transport.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BackpressureError<E> {
+    /// The underlying transport failed.
+    Transport(E),
+    /// The printer stayed busy (or didn't answer) for every poll.
+    Timeout,
+}
+
+impl<E> From<E> for BackpressureError<E> {
+    fn from(err: E) -> Self {
+        BackpressureError::Transport(err)
+    }
+}
+
+/// Transport wrapper that waits for the printer before each chunk of data.
+///
+/// For printers without hardware flow control. Writes are split into chunks
+/// and each chunk is preceded by a `DLE EOT 1` status query; real-time
+/// commands are answered even when the receive buffer is full. While the
+/// printer reports itself offline, the wrapper waits `poll_ms` and asks
+/// again, giving up with [`BackpressureError::Timeout`] after `max_polls`
+/// attempts.
+///
+/// Printers only report a full buffer as offline when their busy condition
+/// is set to "offline or receive buffer full" (memory switch 1-3 on Epson
+/// models).
+///
+/// Status responses are consumed by the wrapper, so avoid mixing it with
+/// Automatic Status Back. The inner transport's `read` must return `Ok(0)`
+/// when no data is available.
+pub struct Backpressure<T, D> {
+    inner: T,
+    delay: D,
+    chunk: usize,
+    poll_ms: u32,
+    max_polls: u32,
+}
+
+impl<T, D> Backpressure<T, D> {
+    /// Wrap `inner`, checking every 256 bytes and polling every 50ms for up
+    /// to 5 seconds.
+    pub fn new(inner: T, delay: D) -> Self {
+        Self {
+            inner,
+            delay,
+            chunk: 256,
+            poll_ms: 50,
+            max_polls: 100,
+        }
+    }
+
+    /// Check the printer's status before every `bytes` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is zero.
+    pub fn chunk_size(mut self, bytes: usize) -> Self {
+        assert!(bytes > 0, "chunk size must be non-zero");
+        self.chunk = bytes;
+        self
+    }
+
+    /// Wait `poll_ms` between polls, giving up after `max_polls`.
+    pub fn polling(mut self, poll_ms: u32, max_polls: u32) -> Self {
+        self.poll_ms = poll_ms;
+        self.max_polls = max_polls;
+        self
+    }
+
+    /// Consume the wrapper, returning the inner transport.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T, D> Backpressure<T, D>
+where
+    T: Write + Read<Error = <T as Write>::Error>,
+    D: Delay,
+{
+    fn wait_ready(&mut self) -> Result<(), BackpressureError<<T as Write>::Error>> {
+        let mut polls = 0;
+        let mut pending = false;
+        loop {
+            if !pending {
+                self.inner.write(&QUERY)?;
+                pending = true;
+            }
+            let mut byte = [0u8; 1];
+            // DLE EOT responses always have bits 1 and 4 set and 0 and 7 clear.
+            if self.inner.read(&mut byte)? == 1 && byte[0] & 0x93 == 0x12 {
+                if byte[0] & OFFLINE == 0 {
+                    return Ok(());
+                }
+                pending = false;
+            }
+            polls += 1;
+            if polls >= self.max_polls {
+                return Err(BackpressureError::Timeout);
+            }
+            self.delay.delay_ms(self.poll_ms);
+        }
+    }
+}
+
+impl<T, D> Write for Backpressure<T, D>
+where
+    T: Write + Read<Error = <T as Write>::Error>,
+    D: Delay,
+{
+    type Error = BackpressureError<<T as Write>::Error>;
+
+    fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        for chunk in data.chunks(self.chunk) {
+            self.wait_ready()?;
+            self.inner.write(chunk)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, D> Read for Backpressure<T, D>
+where
+    T: Read,
+{
+    type Error = BackpressureError<T::Error>;
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        Ok(self.inner.read(buf)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockTransport, RecordDelay};
+    use crate::Printer;
+
+    #[test]
+    fn test_waits_while_offline() {
+        // Offline for the first poll, then online.
+        let mock = MockTransport::with_responses(&[0x1A]);
+        let mut transport = Backpressure::new(mock, RecordDelay::default()).chunk_size(4);
+        transport.inner.push_response(&[0x12, 0x12]);
+        let mut printer = Printer::new(transport);
+        printer.write("abcdef").unwrap();
+        let transport = printer.into_inner();
+        assert_eq!(transport.delay.0, [50]);
+        let expected = [&QUERY[..], &QUERY, b"abcd", &QUERY, b"ef"].concat();
+        assert_eq!(transport.inner.written(), expected);
+    }
+
+    #[test]
+    fn test_timeout() {
+        let mock = MockTransport::with_responses(&[0x1A; 3]);
+        let mut transport = Backpressure::new(mock, ()).polling(10, 3);
+        assert_eq!(transport.write(b"x"), Err(BackpressureError::Timeout));
+        assert_eq!(transport.into_inner().written(), [QUERY; 3].concat());
+    }
+}
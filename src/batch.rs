@@ -0,0 +1,63 @@
+/// Size of the writes assembled by [`Batch`].
+const BATCH: usize = 128;
+
+/// Fixed buffer gathering many small pieces of data into fewer writes.
+///
+/// [`fill`](Batch::fill) hands the buffer out whenever it fills up; what is
+/// left at the end comes from [`rest`](Batch::rest). Used by the
+/// `print_lines` methods so each line doesn't cost its own writes.
+pub(crate) struct Batch {
+    buf: [u8; BATCH],
+    len: usize,
+}
+
+impl Batch {
+    pub(crate) fn new() -> Self {
+        Self {
+            buf: [0; BATCH],
+            len: 0,
+        }
+    }
+
+    /// Copy as much of `data` as fits, advancing it past the copied bytes.
+    ///
+    /// Returns the buffer once it is full; it is emptied on the next call.
+    pub(crate) fn fill(&mut self, data: &mut &[u8]) -> Option<&[u8]> {
+        if self.len == BATCH {
+            self.len = 0;
+        }
+        let n = data.len().min(BATCH - self.len);
+        self.buf[self.len..self.len + n].copy_from_slice(&data[..n]);
+        self.len += n;
+        *data = &data[n..];
+        (self.len == BATCH).then_some(&self.buf[..])
+    }
+
+    /// Data not yet handed out by [`fill`](Batch::fill).
+    pub(crate) fn rest(&self) -> &[u8] {
+        &self.buf[..self.len % BATCH]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec::Vec;
+
+    #[test]
+    fn test_fill() {
+        let mut batch = Batch::new();
+        let mut out = Vec::new();
+        for _ in 0..3 {
+            let mut data = &[7u8; 100][..];
+            while !data.is_empty() {
+                if let Some(full) = batch.fill(&mut data) {
+                    assert_eq!(full.len(), BATCH);
+                    out.extend_from_slice(full);
+                }
+            }
+        }
+        out.extend_from_slice(batch.rest());
+        assert_eq!(out, [7u8; 300]);
+    }
+}
@@ -0,0 +1,210 @@
+use std::time::{Duration, Instant};
+use std::vec;
+
+use super::{Align, BitImageScale, Command, CutMode, Delay, Printer, PrinterError, Write};
+
+/// Logo height in dots for [`Workload::LogoReceipt`].
+const LOGO_HEIGHT: u16 = 160;
+
+/// Representative receipt sent by [`Printer::bench`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Workload {
+    /// A long itemised receipt: mostly text, with a few style changes and a
+    /// cut. About 1.5KB.
+    TextReceipt,
+    /// A full-width raster logo above a short receipt. Mostly image data,
+    /// about 11.5KB on 80mm paper.
+    LogoReceipt,
+}
+
+/// Transport wrapper timing writes for [`Printer::bench`].
+///
+/// Passes everything through unchanged, so it can stay in place between
+/// benchmarks and normal printing.
+pub struct Bench<T> {
+    inner: T,
+    start: Instant,
+    first_write: Option<Duration>,
+    bytes: usize,
+}
+
+impl<T> Bench<T> {
+    /// Wrap `inner`.
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            start: Instant::now(),
+            first_write: None,
+            bytes: 0,
+        }
+    }
+
+    /// The wrapped transport.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Mutable access to the wrapped transport.
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consume the wrapper, returning the wrapped transport.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    fn reset(&mut self) {
+        self.start = Instant::now();
+        self.first_write = None;
+        self.bytes = 0;
+    }
+}
+
+impl<T: Write> Write for Bench<T> {
+    type Error = T::Error;
+
+    fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        self.inner.write(data)?;
+        self.bytes += data.len();
+        if self.first_write.is_none() {
+            self.first_write = Some(self.start.elapsed());
+        }
+        Ok(())
+    }
+}
+
+/// Measurements from [`Printer::bench`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct BenchReport {
+    /// Bytes written to the transport.
+    pub bytes: usize,
+    /// Time until the first write to the transport completed.
+    pub first_byte: Duration,
+    /// Time to send the whole workload.
+    pub elapsed: Duration,
+}
+
+impl BenchReport {
+    /// Effective throughput in bytes per second.
+    pub fn bytes_per_sec(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs > 0.0 {
+            self.bytes as f64 / secs
+        } else {
+            f64::INFINITY
+        }
+    }
+}
+
+impl<T: Write, P: Delay> Printer<Bench<T>, P> {
+    /// Send `workload` and measure how fast the transport took it.
+    ///
+    /// The printer's chunk size and pacing apply as for any other output,
+    /// so compare settings by building printers with each of them. Only the
+    /// sending is timed: with a real printer, printing may carry on after
+    /// this returns.
+    ///
+    /// ```
+    /// # use escpos_embedded::{Bench, Printer, SimulatedPrinter, Workload};
+    /// for chunk_size in [64, 512] {
+    ///     let mut printer = Printer::builder(Bench::new(SimulatedPrinter::new()))
+    ///         .chunk_size(chunk_size)
+    ///         .build()
+    ///         .unwrap();
+    ///     let report = printer.bench(Workload::TextReceipt).unwrap();
+    ///     println!("{chunk_size}: {:.0} B/s", report.bytes_per_sec());
+    /// }
+    /// ```
+    pub fn bench(&mut self, workload: Workload) -> Result<BenchReport, PrinterError<T::Error>> {
+        self.transport.reset();
+        match workload {
+            Workload::TextReceipt => self.bench_text()?,
+            Workload::LogoReceipt => self.bench_logo()?,
+        }
+        let bench = &self.transport;
+        Ok(BenchReport {
+            bytes: bench.bytes,
+            first_byte: bench.first_write.unwrap_or_default(),
+            elapsed: bench.start.elapsed(),
+        })
+    }
+
+    fn bench_text(&mut self) -> Result<(), PrinterError<T::Error>> {
+        self.set_align(Align::Center)?;
+        self.set_bold(true)?;
+        self.write_line("BENCHMARK STORE")?;
+        self.set_bold(false)?;
+        self.write_line("1 Example Street")?;
+        self.set_align(Align::Left)?;
+        for item in 1..=40 {
+            self.write_line(&std::format!(
+                "Item {:02} ...................... {:>2}.99",
+                item,
+                item
+            ))?;
+        }
+        self.set_bold(true)?;
+        self.write_line("TOTAL                          819.60")?;
+        self.set_bold(false)?;
+        self.feed(3)?;
+        self.cut(CutMode::Partial)
+    }
+
+    fn bench_logo(&mut self) -> Result<(), PrinterError<T::Error>> {
+        let width = self.paper_width.dots();
+        let row = usize::from(width.div_ceil(8));
+        let mut data = vec![0u8; row * usize::from(LOGO_HEIGHT)];
+        // Diagonal stripes, so the data isn't trivially compressible.
+        for (i, byte) in data.iter_mut().enumerate() {
+            *byte = 0x0F_u8.rotate_left(((i / row + i % row) % 8) as u32);
+        }
+        self.set_align(Align::Center)?;
+        self.send(&Command::RasterImage {
+            width,
+            height: LOGO_HEIGHT,
+            scale: BitImageScale::Normal,
+            data: &data,
+        })?;
+        self.write_line("BENCHMARK STORE")?;
+        self.set_align(Align::Left)?;
+        self.write_line("Coffee                          3.50")?;
+        self.feed(3)?;
+        self.cut(CutMode::Partial)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockTransport, RecordDelay};
+    use crate::Pacing;
+
+    #[test]
+    fn test_bench_counts_bytes() {
+        let mut printer = Printer::new(Bench::new(MockTransport::new()));
+        let report = printer.bench(Workload::LogoReceipt).unwrap();
+        let written = printer.transport().inner().written().len();
+        assert_eq!(report.bytes, written);
+        assert!(written > 48 * usize::from(LOGO_HEIGHT));
+        assert!(report.first_byte <= report.elapsed);
+
+        let text = printer.bench(Workload::TextReceipt).unwrap();
+        assert_eq!(
+            text.bytes,
+            printer.transport().inner().written().len() - written
+        );
+    }
+
+    #[test]
+    fn test_bench_paced() {
+        let pacing = Pacing::new(RecordDelay::default()).after_cut(300);
+        let mut printer = Printer::builder(Bench::new(MockTransport::new()))
+            .pacing(pacing)
+            .build()
+            .unwrap();
+        let report = printer.bench(Workload::TextReceipt).unwrap();
+        assert_eq!(report.bytes, printer.transport().inner().written().len());
+        assert_eq!(printer.pacing().unwrap().delay().0, [300]);
+    }
+}
@@ -0,0 +1,211 @@
+use super::{CodePage, Command, Delay, Printer, PrinterError, Write};
+
+/// Line style for rules and table borders.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BorderStyle {
+    /// `+`, `-` and `|`, which print on every printer.
+    #[default]
+    Ascii,
+    /// Single box-drawing lines: `┌─┬─┐`.
+    Single,
+    /// Double box-drawing lines: `╔═╦═╗`.
+    Double,
+}
+
+/// Bytes that draw a [`BorderStyle`] in a code page.
+///
+/// Each field is a single byte in the selected code page, so the border
+/// must be sent with [`Printer::raw`] rather than as text.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BoxChars {
+    /// Horizontal line.
+    pub horizontal: u8,
+    /// Vertical line.
+    pub vertical: u8,
+    /// Top left corner.
+    pub top_left: u8,
+    /// Top right corner.
+    pub top_right: u8,
+    /// Bottom left corner.
+    pub bottom_left: u8,
+    /// Bottom right corner.
+    pub bottom_right: u8,
+    /// Joint of a column line with the top border.
+    pub top_joint: u8,
+    /// Joint of a column line with the bottom border.
+    pub bottom_joint: u8,
+    /// Joint of a row line with the left border.
+    pub left_joint: u8,
+    /// Joint of a row line with the right border.
+    pub right_joint: u8,
+    /// Crossing of a row line and a column line.
+    pub cross: u8,
+}
+
+impl BoxChars {
+    /// `+-|`, for code pages without box-drawing characters.
+    pub const ASCII: Self = Self {
+        horizontal: b'-',
+        vertical: b'|',
+        top_left: b'+',
+        top_right: b'+',
+        bottom_left: b'+',
+        bottom_right: b'+',
+        top_joint: b'+',
+        bottom_joint: b'+',
+        left_joint: b'+',
+        right_joint: b'+',
+        cross: b'+',
+    };
+
+    /// Single lines, as encoded in CP437, CP850 and their variants.
+    pub const SINGLE: Self = Self {
+        horizontal: 0xC4,
+        vertical: 0xB3,
+        top_left: 0xDA,
+        top_right: 0xBF,
+        bottom_left: 0xC0,
+        bottom_right: 0xD9,
+        top_joint: 0xC2,
+        bottom_joint: 0xC1,
+        left_joint: 0xC3,
+        right_joint: 0xB4,
+        cross: 0xC5,
+    };
+
+    /// Double lines, as encoded in CP437, CP850 and their variants.
+    pub const DOUBLE: Self = Self {
+        horizontal: 0xCD,
+        vertical: 0xBA,
+        top_left: 0xC9,
+        top_right: 0xBB,
+        bottom_left: 0xC8,
+        bottom_right: 0xBC,
+        top_joint: 0xCB,
+        bottom_joint: 0xCA,
+        left_joint: 0xCC,
+        right_joint: 0xB9,
+        cross: 0xCE,
+    };
+
+    /// Characters for `style` in `code_page`, falling back to
+    /// [`ASCII`](BoxChars::ASCII) if the code page has no box-drawing
+    /// characters or isn't known.
+    ///
+    /// ```
+    /// # use escpos_embedded::{BorderStyle, BoxChars, CodePage};
+    /// let chars = BoxChars::for_code_page(BorderStyle::Double, Some(CodePage::Pc850));
+    /// assert_eq!(chars, BoxChars::DOUBLE);
+    /// let chars = BoxChars::for_code_page(BorderStyle::Double, Some(CodePage::Wpc1252));
+    /// assert_eq!(chars, BoxChars::ASCII);
+    /// ```
+    pub const fn for_code_page(style: BorderStyle, code_page: Option<CodePage>) -> Self {
+        let Some(page) = code_page else {
+            return Self::ASCII;
+        };
+        if !page.has_box_drawing() {
+            return Self::ASCII;
+        }
+        match style {
+            BorderStyle::Ascii => Self::ASCII,
+            BorderStyle::Single => Self::SINGLE,
+            BorderStyle::Double => Self::DOUBLE,
+        }
+    }
+}
+
+impl CodePage {
+    /// Whether the code page has the CP437 box-drawing characters at their
+    /// usual positions.
+    pub const fn has_box_drawing(self) -> bool {
+        matches!(
+            self,
+            CodePage::Pc437
+                | CodePage::Pc850
+                | CodePage::Pc858
+                | CodePage::Pc860
+                | CodePage::Pc863
+                | CodePage::Pc865
+        )
+    }
+}
+
+impl<T: Write, P: Delay> Printer<T, P> {
+    /// Characters for drawing `style` in the code page selected through
+    /// this printer; see [`BoxChars::for_code_page`].
+    ///
+    /// Box-drawing characters need a code page selected with
+    /// [`set_code_page`](Printer::set_code_page) or the builder, since the
+    /// printer's power-on code page isn't known.
+    pub fn box_chars(&self, style: BorderStyle) -> BoxChars {
+        BoxChars::for_code_page(style, self.code_page)
+    }
+
+    /// Print a horizontal line across the paper in `style`.
+    ///
+    /// ```
+    /// # use escpos_embedded::{BorderStyle, CodePage, Printer, PrinterError, Write};
+    /// # fn print<T: Write>(printer: &mut Printer<T>) -> Result<(), PrinterError<T::Error>> {
+    /// printer.set_code_page(CodePage::Pc437)?;
+    /// printer.write_line("Subtotal   12.50")?;
+    /// printer.write_rule(BorderStyle::Double)?;
+    /// printer.write_line("TOTAL      12.50")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_rule(
+        &mut self,
+        style: BorderStyle,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        let line = [self.box_chars(style).horizontal; 32];
+        let mut left = self.columns();
+        while left > 0 {
+            let n = left.min(line.len());
+            self.send(&Command::Raw(&line[..n]))?;
+            left -= n;
+        }
+        self.end_line()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockTransport;
+    use crate::PaperWidth;
+
+    #[test]
+    fn test_box_chars_fall_back() {
+        for page in [CodePage::Pc437, CodePage::Pc858, CodePage::Pc865] {
+            assert_eq!(
+                BoxChars::for_code_page(BorderStyle::Single, Some(page)),
+                BoxChars::SINGLE
+            );
+        }
+        for page in [None, Some(CodePage::Katakana), Some(CodePage::Wpc1252)] {
+            assert_eq!(
+                BoxChars::for_code_page(BorderStyle::Single, page),
+                BoxChars::ASCII
+            );
+        }
+        assert_eq!(
+            BoxChars::for_code_page(BorderStyle::Ascii, Some(CodePage::Pc437)),
+            BoxChars::ASCII
+        );
+    }
+
+    #[test]
+    fn test_write_rule() {
+        let mut printer = Printer::new(MockTransport::new());
+        printer.paper_width = PaperWidth::Dots(48);
+        printer.write_rule(BorderStyle::Double).unwrap();
+        assert_eq!(printer.transport.take_written(), b"----\n");
+        printer.set_code_page(CodePage::Pc850).unwrap();
+        printer.transport.take_written();
+        printer.write_rule(BorderStyle::Double).unwrap();
+        assert_eq!(printer.transport.written(), b"\xCD\xCD\xCD\xCD\n");
+    }
+}
@@ -0,0 +1,358 @@
+#[cfg(feature = "image")]
+use super::ImageFit;
+use super::{
+    Charset, CodePage, Degradation, Delay, Density, Hooks, LineEnding, NearEndPolicy, Pacing,
+    PaperWidth, Printer, PrinterError, Profile, Write,
+};
+
+/// Commands that bring a printer to a known state.
+///
+/// Sent when a printer is created with [`Printer::new_initialized`] or
+/// [`PrinterBuilder::startup`], and again by [`Printer::reinitialize`] and
+/// [`Printer::reconnect`], so a printer that was power-cycled or lost its
+/// link is set up the same way. The commands are sent in the order of the
+/// methods below.
+///
+/// [`Startup::default`] sends nothing; [`Startup::new`] only resets the
+/// printer.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Startup {
+    pub(crate) initialize: bool,
+    pub(crate) code_page: Option<CodePage>,
+    pub(crate) charset: Option<Charset>,
+    pub(crate) density: Option<Density>,
+    pub(crate) line_spacing: Option<u8>,
+}
+
+impl Startup {
+    /// Reset the printer with `ESC @`, without further settings.
+    pub const fn new() -> Self {
+        Self {
+            initialize: true,
+            code_page: None,
+            charset: None,
+            density: None,
+            line_spacing: None,
+        }
+    }
+
+    /// Reset the printer with `ESC @` first.
+    pub const fn initialize(mut self, on: bool) -> Self {
+        self.initialize = on;
+        self
+    }
+
+    /// Select `code_page` with `ESC t`.
+    pub const fn code_page(mut self, code_page: CodePage) -> Self {
+        self.code_page = Some(code_page);
+        self
+    }
+
+    /// Select the international character set with `ESC R`.
+    pub const fn charset(mut self, charset: Charset) -> Self {
+        self.charset = Some(charset);
+        self
+    }
+
+    /// Set the print density with `GS |`.
+    pub const fn density(mut self, level: Density) -> Self {
+        self.density = Some(level);
+        self
+    }
+
+    /// Set the line spacing to `units` motion units with `ESC 3`.
+    pub const fn line_spacing(mut self, units: u8) -> Self {
+        self.line_spacing = Some(units);
+        self
+    }
+}
+
+/// Configures a [`Printer`] and its startup sequence.
+///
+/// Returned by [`Printer::builder`]. Settings left unset fall back to the
+/// profile, and nothing is sent unless asked for:
+///
+/// ```
+/// # use escpos_embedded::{CodePage, Printer, Profile, Write};
+/// # fn setup<T: Write>(uart: T) -> Result<(), escpos_embedded::PrinterError<T::Error>> {
+/// let mut printer = Printer::builder(uart)
+///     .profile(Profile::GENERIC_58MM)
+///     .chunk_size(64)
+///     .initialize(true)
+///     .code_page(CodePage::Pc858)
+///     .build()?;
+/// printer.write_line("Total: 4,20 €")?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct PrinterBuilder<T: Write, P: Delay = ()> {
+    transport: T,
+    profile: Profile,
+    paper_width: Option<PaperWidth>,
+    chunk_size: Option<usize>,
+    startup: Startup,
+    near_end_policy: Option<NearEndPolicy>,
+    cut_fallback: Option<u8>,
+    degradation: Degradation,
+    pacing: Option<Pacing<P>>,
+    hooks: Hooks,
+    line_ending: LineEnding,
+    #[cfg(feature = "image")]
+    image_fit: ImageFit,
+}
+
+impl<T: Write> PrinterBuilder<T> {
+    /// Start configuring a printer on `transport`.
+    pub fn new(transport: T) -> Self {
+        Self {
+            transport,
+            profile: Profile::GENERIC,
+            paper_width: None,
+            chunk_size: None,
+            startup: Startup::default(),
+            near_end_policy: None,
+            cut_fallback: None,
+            degradation: Degradation::new(),
+            pacing: None,
+            hooks: Hooks::new(),
+            line_ending: LineEnding::Lf,
+            #[cfg(feature = "image")]
+            image_fit: ImageFit::Error,
+        }
+    }
+}
+
+impl<T: Write, P: Delay> PrinterBuilder<T, P> {
+    /// Describe the printer model. Defaults to [`Profile::GENERIC`].
+    pub fn profile(mut self, profile: Profile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Override the profile's paper width.
+    pub fn paper_width(mut self, width: PaperWidth) -> Self {
+        self.paper_width = Some(width);
+        self
+    }
+
+    /// Select `code_page` with `ESC t` when building.
+    pub fn code_page(mut self, code_page: CodePage) -> Self {
+        self.startup = self.startup.code_page(code_page);
+        self
+    }
+
+    /// Select the international character set with `ESC R` when building.
+    pub fn charset(mut self, charset: Charset) -> Self {
+        self.startup = self.startup.charset(charset);
+        self
+    }
+
+    /// Set the line spacing to `units` motion units with `ESC 3` when
+    /// building.
+    pub fn line_spacing(mut self, units: u8) -> Self {
+        self.startup = self.startup.line_spacing(units);
+        self
+    }
+
+    /// Replace the whole startup sequence.
+    ///
+    /// The sequence is also what [`Printer::reinitialize`] and
+    /// [`Printer::reconnect`] send.
+    pub fn startup(mut self, startup: Startup) -> Self {
+        self.startup = startup;
+        self
+    }
+
+    /// Split every write into pieces of at most `max` bytes.
+    ///
+    /// Without this, commands are written whole, except images which are
+    /// sent in 512-byte pieces.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max` is zero.
+    pub fn chunk_size(mut self, max: usize) -> Self {
+        assert!(max > 0, "chunk size must be non-zero");
+        self.chunk_size = Some(max);
+        self
+    }
+
+    /// Reset the printer with `ESC @` before anything else is sent.
+    pub fn initialize(mut self, on: bool) -> Self {
+        self.startup = self.startup.initialize(on);
+        self
+    }
+
+    /// Set the print density with `GS |` when building.
+    pub fn density(mut self, level: Density) -> Self {
+        self.startup = self.startup.density(level);
+        self
+    }
+
+    /// Program the paper sensors for `policy` when building; see
+    /// [`Printer::set_near_end_policy`].
+    pub fn near_end_policy(mut self, policy: NearEndPolicy) -> Self {
+        self.near_end_policy = Some(policy);
+        self
+    }
+
+    /// On printers without a cutter, feed `lines` lines instead of cutting.
+    ///
+    /// Without this, cutting on such printers returns
+    /// [`PrinterError::Unsupported`].
+    pub fn cut_fallback(mut self, lines: u8) -> Self {
+        self.cut_fallback = Some(lines);
+        self
+    }
+
+    /// Handle commands the profile lacks according to `degradation`.
+    /// Defaults to [`Degradation::new`], which returns
+    /// [`PrinterError::Unsupported`].
+    pub fn degradation(mut self, degradation: Degradation) -> Self {
+        self.degradation = degradation;
+        self
+    }
+
+    /// Pause after cuts, feeds and image data as configured by `pacing`.
+    pub fn pacing<D: Delay>(self, pacing: Pacing<D>) -> PrinterBuilder<T, D> {
+        PrinterBuilder {
+            transport: self.transport,
+            profile: self.profile,
+            paper_width: self.paper_width,
+            chunk_size: self.chunk_size,
+            startup: self.startup,
+            near_end_policy: self.near_end_policy,
+            cut_fallback: self.cut_fallback,
+            degradation: self.degradation,
+            pacing: Some(pacing),
+            hooks: self.hooks,
+            line_ending: self.line_ending,
+            #[cfg(feature = "image")]
+            image_fit: self.image_fit,
+        }
+    }
+
+    /// Run `hooks` before anything is sent, including the startup commands.
+    pub fn hooks(mut self, hooks: Hooks) -> Self {
+        self.hooks = hooks;
+        self
+    }
+
+    /// End lines written with [`Printer::write_line`] with `ending`.
+    /// Defaults to [`LineEnding::Lf`].
+    pub fn line_ending(mut self, ending: LineEnding) -> Self {
+        self.line_ending = ending;
+        self
+    }
+
+    /// Fit images to the paper width with `fit`. Defaults to
+    /// [`ImageFit::Error`].
+    #[cfg(feature = "image")]
+    pub fn image_fit(mut self, fit: ImageFit) -> Self {
+        self.image_fit = fit;
+        self
+    }
+
+    /// Create the printer and send the configured startup commands.
+    pub fn build(self) -> Result<Printer<T, P>, PrinterError<T::Error>> {
+        let mut printer = Printer {
+            profile: self.profile,
+            paper_width: self.paper_width.unwrap_or(self.profile.paper_width),
+            chunk_size: self.chunk_size,
+            cut_fallback: self.cut_fallback,
+            degradation: self.degradation,
+            hooks: self.hooks,
+            line_ending: self.line_ending,
+            startup: self.startup,
+            #[cfg(feature = "image")]
+            image_fit: self.image_fit,
+            ..Printer::new(self.transport)
+        }
+        .with_pacing(self.pacing);
+        printer.reinitialize()?;
+        if let Some(policy) = self.near_end_policy {
+            printer.set_near_end_policy(policy)?;
+        }
+        Ok(printer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{LimitedMockTransport, MockTransport};
+
+    #[test]
+    fn test_startup_sequence() {
+        let printer = Printer::builder(MockTransport::new())
+            .initialize(true)
+            .code_page(CodePage::Pc850)
+            .density(Density::Level6)
+            .build()
+            .unwrap();
+        assert_eq!(printer.code_page(), Some(CodePage::Pc850));
+        let expected = [0x1B, 0x40, 0x1B, 0x74, 0x02, 0x1D, 0x7C, 0x06];
+        assert_eq!(printer.transport().written(), expected);
+    }
+
+    #[test]
+    fn test_reconnect_resends_startup() {
+        let mut printer = Printer::builder(MockTransport::new())
+            .initialize(true)
+            .charset(Charset::Uk)
+            .line_spacing(24)
+            .build()
+            .unwrap();
+        let expected = [0x1B, 0x40, 0x1B, 0x52, 0x03, 0x1B, 0x33, 0x18];
+        assert_eq!(printer.transport().written(), expected);
+        printer.write("x").unwrap();
+        printer.reconnect(MockTransport::new()).unwrap();
+        assert_eq!(printer.transport().written(), expected);
+        printer.reinitialize().unwrap();
+        assert_eq!(printer.transport().written().len(), 2 * expected.len());
+    }
+
+    #[test]
+    fn test_line_ending() {
+        let mut printer = Printer::builder(MockTransport::new())
+            .line_ending(LineEnding::CrLf)
+            .build()
+            .unwrap();
+        printer.write_line("A").unwrap();
+        let mut job = crate::Job::<8>::for_printer(&printer);
+        job.write_line("B").unwrap();
+        assert_eq!(printer.transport().written(), b"A\r\n");
+        assert_eq!(job.as_bytes(), b"B\r\n");
+    }
+
+    #[test]
+    fn test_defaults_send_nothing() {
+        let printer = Printer::builder(MockTransport::new())
+            .profile(Profile::GENERIC_58MM)
+            .build()
+            .unwrap();
+        assert!(printer.transport().written().is_empty());
+        assert_eq!(printer.paper_width().dots(), 384);
+        assert_eq!(
+            Printer::builder(MockTransport::new())
+                .profile(Profile::GENERIC_58MM)
+                .paper_width(PaperWidth::Dots(360))
+                .build()
+                .unwrap()
+                .paper_width(),
+            PaperWidth::Dots(360)
+        );
+    }
+
+    #[test]
+    fn test_chunk_size() {
+        let mut printer = Printer::builder(LimitedMockTransport::new(4))
+            .chunk_size(4)
+            .build()
+            .unwrap();
+        printer.write("0123456789").unwrap();
+        assert_eq!(printer.transport().written(), b"0123456789");
+    }
+}
@@ -0,0 +1,120 @@
+use std::fmt::Write as _;
+use std::string::String;
+use std::vec::Vec;
+
+use super::{Command, Decoder, Write};
+
+/// Width of the command name column in snapshots.
+const LABEL_WIDTH: usize = 24;
+/// Bytes shown per snapshot line.
+const BYTES_PER_LINE: usize = 16;
+
+/// Transport that records output for snapshot ("golden") tests.
+///
+/// [`snapshot`](Capture::snapshot) turns the recorded bytes into a stable text
+/// form with one command per line: the command name (and text, for
+/// [`Command::Text`]) followed by its bytes in hex. Long commands continue on
+/// indented lines of 16 bytes. Commit the snapshot next to the test and
+/// compare against it, so a change in the emitted bytes shows up as a
+/// readable diff:
+///
+/// ```
+/// # use escpos_embedded::{Capture, CutMode, Printer};
+/// let mut printer = Printer::new(Capture::new());
+/// printer.write_line("Hi").unwrap();
+/// printer.cut(CutMode::Full).unwrap();
+/// assert_eq!(
+///     printer.transport().snapshot(),
+///     "Text \"Hi\"               48 69\n\
+///      LineFeed                0A\n\
+///      Cut                     1D 56 00\n"
+/// );
+/// ```
+#[derive(Debug, Default)]
+pub struct Capture {
+    bytes: Vec<u8>,
+}
+
+impl Capture {
+    /// Create an empty capture.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Raw bytes recorded so far.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Discard the recorded bytes.
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+    }
+
+    /// Annotated snapshot of the recorded bytes.
+    pub fn snapshot(&self) -> String {
+        Self::annotate(&self.bytes)
+    }
+
+    /// Annotated snapshot of any captured byte stream.
+    pub fn annotate(bytes: &[u8]) -> String {
+        let mut out = String::new();
+        let mut decoder = Decoder::new(bytes);
+        let mut start = 0;
+        while let Some(command) = decoder.next() {
+            let label = match command {
+                Command::Text(text) => std::format!("Text {:?}", text),
+                _ => String::from(command.name()),
+            };
+            let encoded = &bytes[start..decoder.position()];
+            start = decoder.position();
+            for (i, line) in encoded.chunks(BYTES_PER_LINE).enumerate() {
+                let label = if i == 0 { label.as_str() } else { "" };
+                let _ = write!(out, "{:width$}", label, width = LABEL_WIDTH - 1);
+                for byte in line {
+                    let _ = write!(out, " {:02X}", byte);
+                }
+                out.push('\n');
+            }
+        }
+        out
+    }
+}
+
+impl Write for Capture {
+    type Error = core::convert::Infallible;
+
+    fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        self.bytes.extend_from_slice(data);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Align, Printer};
+
+    #[test]
+    fn test_snapshot_unknown_and_long_commands() {
+        let mut printer = Printer::new(Capture::new());
+        printer.set_align(Align::Center).unwrap();
+        printer.raw(&[0x1B, 0x7A]).unwrap();
+        printer.write("0123456789ABCDEFG").unwrap();
+        let expected = "\
+SetAlign                1B 61 01
+Unknown                 1B 7A
+Text \"0123456789ABCDEFG\" 30 31 32 33 34 35 36 37 38 39 41 42 43 44 45 46
+                        47
+";
+        assert_eq!(printer.transport().snapshot(), expected);
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut capture = Capture::new();
+        capture.write(b"A").unwrap();
+        capture.clear();
+        assert_eq!(capture.snapshot(), "");
+    }
+}
@@ -0,0 +1,290 @@
+use super::{font, Align, Backend, BitImageScale, Command, PaperWidth, PrinterError, Write};
+
+/// Widest supported print head, in dots.
+const MAX_DOTS: usize = 576;
+const MAX_COLUMNS: usize = MAX_DOTS / font::WIDTH;
+/// Dots fed per text line, matching the ESC/POS default line spacing.
+const LINE_SPACING: u16 = 30;
+
+const DRAW_ROW: u8 = 0xA2;
+const FEED: u8 = 0xA1;
+const LATTICE: u8 = 0xA6;
+const ENERGY: u8 = 0xAF;
+
+const LATTICE_START: [u8; 11] = [
+    0xAA, 0x55, 0x17, 0x38, 0x44, 0x5F, 0x5F, 0x5F, 0x44, 0x38, 0x2C,
+];
+const LATTICE_END: [u8; 11] = [
+    0xAA, 0x55, 0x17, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x17,
+];
+
+/// Backend for BLE "cat printers" (GB01, GB02, MX06 and similar).
+///
+/// These printers don't understand ESC/POS; every operation is a packet of
+/// `51 78 cmd 00 len 00 data crc8 FF`. Text is rendered with the built-in
+/// 12x24 font and sent as raster rows, so only ASCII prints as-is. Feeding,
+/// alignment, density and raster images are translated; commands with no
+/// equivalent (styles, cutting, status queries) return
+/// [`PrinterError::Unsupported`].
+///
+/// ```
+/// # use escpos_embedded::{Backend, CatPrinter, MockTransport};
+/// let mut printer = CatPrinter::new(MockTransport::new());
+/// printer.feed(1).unwrap();
+/// assert_eq!(
+///     printer.transport().written(),
+///     [0x51, 0x78, 0xA1, 0x00, 0x02, 0x00, 0x1E, 0x00, 0x81, 0xFF]
+/// );
+/// ```
+pub struct CatPrinter<T> {
+    transport: T,
+    row_bytes: usize,
+    align: Align,
+    line: [char; MAX_COLUMNS],
+    line_len: usize,
+}
+
+impl<T: Write> CatPrinter<T> {
+    /// Create a backend for a 384-dot (58mm) printer.
+    pub fn new(transport: T) -> Self {
+        Self::for_paper(transport, PaperWidth::Mm58)
+    }
+
+    /// Create a backend for a printer with the given print head width.
+    ///
+    /// Widths above 576 dots are clamped.
+    pub fn for_paper(transport: T, paper: PaperWidth) -> Self {
+        Self {
+            transport,
+            row_bytes: usize::from(paper.dots()).min(MAX_DOTS) / 8,
+            align: Align::Left,
+            line: [' '; MAX_COLUMNS],
+            line_len: 0,
+        }
+    }
+
+    /// Borrow the underlying transport.
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// Mutably borrow the underlying transport.
+    pub fn transport_mut(&mut self) -> &mut T {
+        &mut self.transport
+    }
+
+    /// Consume the backend, returning the transport.
+    pub fn into_inner(self) -> T {
+        self.transport
+    }
+
+    fn packet(&mut self, cmd: u8, data: &[u8]) -> Result<(), T::Error> {
+        let len = (data.len() as u16).to_le_bytes();
+        self.transport
+            .write(&[0x51, 0x78, cmd, 0x00, len[0], len[1]])?;
+        self.transport.write(data)?;
+        self.transport.write(&[crc8(data), 0xFF])
+    }
+
+    fn feed_dots(&mut self, dots: u16) -> Result<(), T::Error> {
+        self.packet(FEED, &dots.to_le_bytes())
+    }
+
+    fn columns(&self) -> usize {
+        self.row_bytes * 8 / font::WIDTH
+    }
+
+    fn flush_line(&mut self) -> Result<(), T::Error> {
+        let used = self.line_len * font::WIDTH;
+        let left = match self.align {
+            Align::Left => 0,
+            Align::Center => (self.row_bytes * 8 - used) / 2,
+            Align::Right => self.row_bytes * 8 - used,
+        };
+        self.packet(LATTICE, &LATTICE_START)?;
+        for y in 0..font::HEIGHT {
+            let mut row = [0u8; MAX_DOTS / 8];
+            for (i, &ch) in self.line[..self.line_len].iter().enumerate() {
+                let bits = font::glyph(ch)[y];
+                for col in 0..font::WIDTH {
+                    if bits & (0x8000 >> col) != 0 {
+                        let x = left + i * font::WIDTH + col;
+                        row[x / 8] |= 1 << (x % 8);
+                    }
+                }
+            }
+            self.packet(DRAW_ROW, &row[..self.row_bytes])?;
+        }
+        self.packet(LATTICE, &LATTICE_END)?;
+        self.line_len = 0;
+        self.feed_dots(LINE_SPACING - font::HEIGHT as u16)
+    }
+
+    fn text(&mut self, text: &str) -> Result<(), T::Error> {
+        for ch in text.chars() {
+            if ch == '\n' {
+                self.flush_line()?;
+                continue;
+            }
+            if self.line_len == self.columns() {
+                self.flush_line()?;
+            }
+            self.line[self.line_len] = ch;
+            self.line_len += 1;
+        }
+        Ok(())
+    }
+
+    fn raster(&mut self, width: u16, height: u16, data: &[u8]) -> Result<(), T::Error> {
+        let stride = usize::from(width.div_ceil(8));
+        self.packet(LATTICE, &LATTICE_START)?;
+        for src in data.chunks(stride).take(usize::from(height)) {
+            let mut row = [0u8; MAX_DOTS / 8];
+            for (dst, byte) in row.iter_mut().zip(src) {
+                *dst = byte.reverse_bits();
+            }
+            self.packet(DRAW_ROW, &row[..self.row_bytes])?;
+        }
+        self.packet(LATTICE, &LATTICE_END)
+    }
+}
+
+impl<T: Write> Backend for CatPrinter<T> {
+    type Error = T::Error;
+
+    fn send(&mut self, command: &Command) -> Result<(), PrinterError<T::Error>> {
+        command.validate()?;
+        let result = match *command {
+            Command::Text(text) => self.text(text),
+            Command::LineFeed => self.flush_line(),
+            Command::Initialize => {
+                self.align = Align::Left;
+                self.line_len = 0;
+                Ok(())
+            }
+            Command::Feed(lines) => {
+                let pending = if self.line_len > 0 {
+                    self.flush_line()
+                } else {
+                    Ok(())
+                };
+                pending.and_then(|()| self.feed_dots(u16::from(lines) * LINE_SPACING))
+            }
+            Command::SetAlign(align) => {
+                self.align = align;
+                Ok(())
+            }
+            Command::SetDensity(density) => {
+                let energy = 0x1000 + 0x1C00 * u16::from(density.as_byte());
+                self.packet(ENERGY, &energy.to_le_bytes())
+            }
+            Command::RasterImage {
+                width,
+                height,
+                scale: BitImageScale::Normal,
+                data,
+            } => {
+                if usize::from(width) > self.row_bytes * 8 {
+                    return Err(PrinterError::InvalidParameter {
+                        command: "RasterImage",
+                        value: width.into(),
+                        range: 0..=(self.row_bytes as i64 * 8),
+                    });
+                }
+                self.raster(width, height, data)
+            }
+            Command::Raw(data) | Command::Vendor { data, .. } => self.transport.write(data),
+            _ => {
+                return Err(PrinterError::Unsupported {
+                    command: command.name(),
+                })
+            }
+        };
+        result.map_err(|error| PrinterError::Transport {
+            command: command.name(),
+            error,
+        })
+    }
+}
+
+/// CRC-8 (polynomial 0x07) over a packet's data.
+fn crc8(data: &[u8]) -> u8 {
+    data.iter().fold(0, |crc, &byte| {
+        (0..8).fold(crc ^ byte, |crc, _| {
+            if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockTransport;
+    use crate::{CutMode, Density};
+
+    fn packets(mut bytes: &[u8]) -> std::vec::Vec<(u8, &[u8])> {
+        let mut out = std::vec::Vec::new();
+        while !bytes.is_empty() {
+            assert_eq!(&bytes[..2], [0x51, 0x78]);
+            let len = usize::from(u16::from_le_bytes([bytes[4], bytes[5]]));
+            let data = &bytes[6..6 + len];
+            assert_eq!(bytes[6 + len], crc8(data));
+            assert_eq!(bytes[7 + len], 0xFF);
+            out.push((bytes[2], data));
+            bytes = &bytes[8 + len..];
+        }
+        out
+    }
+
+    #[test]
+    fn test_raster_rows_are_lsb_first() {
+        let mut printer = CatPrinter::new(MockTransport::new());
+        printer
+            .print_raster(16, 2, &[0x80, 0x01, 0xFF, 0x00])
+            .unwrap();
+        let written = printer.transport().written();
+        let packets = packets(written);
+        assert_eq!(packets.len(), 4);
+        assert_eq!(packets[0], (LATTICE, LATTICE_START.as_slice()));
+        assert_eq!(packets[1].1.len(), 48);
+        assert_eq!(packets[1].1[..2], [0x01, 0x80]);
+        assert_eq!(packets[2].1[..2], [0xFF, 0x00]);
+        assert_eq!(packets[3], (LATTICE, LATTICE_END.as_slice()));
+    }
+
+    #[test]
+    fn test_text_line_is_rendered() {
+        let mut printer = CatPrinter::new(MockTransport::new());
+        printer.set_align(Align::Right).unwrap();
+        printer.write_line("I").unwrap();
+        let written = printer.transport().written();
+        let packets = packets(written);
+        let rows = packets.iter().filter(|(cmd, _)| *cmd == DRAW_ROW).count();
+        assert_eq!(rows, font::HEIGHT);
+        assert!(packets
+            .iter()
+            .any(|(_, row)| row.len() == 48 && row[47] != 0));
+        assert_eq!(packets.last(), Some(&(FEED, [6u8, 0].as_slice())));
+    }
+
+    #[test]
+    fn test_density_and_unsupported() {
+        let mut printer = CatPrinter::new(MockTransport::new());
+        printer.set_density(Density::Level0).unwrap();
+        assert_eq!(
+            packets(printer.transport().written()),
+            [(ENERGY, [0x00, 0x10].as_slice())]
+        );
+        let err = printer.cut(CutMode::Full).unwrap_err();
+        assert_eq!(err, PrinterError::Unsupported { command: "Cut" });
+    }
+
+    #[test]
+    fn test_crc8() {
+        assert_eq!(crc8(b"123456789"), 0xF4);
+    }
+}
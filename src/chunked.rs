@@ -0,0 +1,53 @@
+use super::{Read, Write};
+
+/// Transport wrapper that splits every write into chunks of a maximum size.
+///
+/// This is useful for links with a limited MTU (e.g. BLE or some USB bridges)
+/// where a single large write would be truncated or rejected. All writes,
+/// including those made through [`Printer::raw`](crate::Printer::raw), are
+/// split before reaching the inner transport.
+pub struct Chunked<T> {
+    inner: T,
+    max_write: usize,
+}
+
+impl<T> Chunked<T> {
+    /// Wrap `inner`, limiting each write to at most `max_write` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_write` is zero.
+    pub fn new(inner: T, max_write: usize) -> Self {
+        assert!(max_write > 0, "max_write must be non-zero");
+        Self { inner, max_write }
+    }
+
+    /// Maximum number of bytes passed to the inner transport per write.
+    pub fn max_write(&self) -> usize {
+        self.max_write
+    }
+
+    /// Consume the wrapper, returning the inner transport.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: Write> Write for Chunked<T> {
+    type Error = T::Error;
+
+    fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        for chunk in data.chunks(self.max_write) {
+            self.inner.write(chunk)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Read> Read for Chunked<T> {
+    type Error = T::Error;
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        self.inner.read(buf)
+    }
+}
@@ -0,0 +1,116 @@
+use core::fmt;
+
+/// A calendar date and wall-clock time, as read from an RTC.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DateTime {
+    /// Full year, e.g. 2024.
+    pub year: u16,
+    /// Month, 1–12.
+    pub month: u8,
+    /// Day of the month, 1–31.
+    pub day: u8,
+    /// Hour, 0–23.
+    pub hour: u8,
+    /// Minute, 0–59.
+    pub minute: u8,
+    /// Second, 0–59.
+    pub second: u8,
+}
+
+impl DateTime {
+    /// Format with a `strftime`-style pattern.
+    ///
+    /// Supports `%Y` (year), `%y` (two-digit year), `%m`, `%d`, `%H`, `%M`,
+    /// `%S` (zero-padded month, day, hour, minute, second) and `%%`. Other
+    /// characters, including unknown `%` sequences, are printed as-is.
+    ///
+    /// ```
+    /// # use escpos_embedded::DateTime;
+    /// let time = DateTime { year: 2024, month: 3, day: 9, hour: 7, minute: 5, second: 0 };
+    /// assert_eq!(time.format("%d/%m/%Y %H:%M").to_string(), "09/03/2024 07:05");
+    /// ```
+    pub fn format<'a>(&self, pattern: &'a str) -> Formatted<'a> {
+        Formatted {
+            time: *self,
+            pattern,
+        }
+    }
+}
+
+/// A [`DateTime`] formatted with a pattern, returned by
+/// [`DateTime::format`].
+#[derive(Copy, Clone, Debug)]
+pub struct Formatted<'a> {
+    time: DateTime,
+    pattern: &'a str,
+}
+
+impl fmt::Display for Formatted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let t = &self.time;
+        let mut chars = self.pattern.chars();
+        while let Some(ch) = chars.next() {
+            if ch != '%' {
+                write!(f, "{}", ch)?;
+                continue;
+            }
+            match chars.next() {
+                Some('Y') => write!(f, "{:04}", t.year)?,
+                Some('y') => write!(f, "{:02}", t.year % 100)?,
+                Some('m') => write!(f, "{:02}", t.month)?,
+                Some('d') => write!(f, "{:02}", t.day)?,
+                Some('H') => write!(f, "{:02}", t.hour)?,
+                Some('M') => write!(f, "{:02}", t.minute)?,
+                Some('S') => write!(f, "{:02}", t.second)?,
+                Some('%') => f.write_str("%")?,
+                Some(other) => write!(f, "%{}", other)?,
+                None => f.write_str("%")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Source of the current date and time, typically an RTC driver.
+///
+/// Implemented for closures, so an RTC can be adapted in place:
+///
+/// ```
+/// # use escpos_embedded::{Clock, DateTime};
+/// let mut clock = || DateTime { year: 2024, month: 1, day: 1, ..DateTime::default() };
+/// assert_eq!(clock.now().year, 2024);
+/// ```
+pub trait Clock {
+    /// Read the current date and time.
+    fn now(&mut self) -> DateTime;
+}
+
+impl<F: FnMut() -> DateTime> Clock for F {
+    fn now(&mut self) -> DateTime {
+        self()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+
+    #[test]
+    fn test_format() {
+        let time = DateTime {
+            year: 2024,
+            month: 12,
+            day: 31,
+            hour: 23,
+            minute: 59,
+            second: 8,
+        };
+        assert_eq!(
+            time.format("%y-%m-%d %H:%M:%S 100%% %q%").to_string(),
+            "24-12-31 23:59:08 100% %q%"
+        );
+    }
+}
@@ -0,0 +1,121 @@
+use super::batch::Batch;
+use super::{BitImageMode, Command, Raster, Write};
+
+/// Motion units covered by each band, in every mode.
+const BAND: u16 = 24;
+
+/// A raster image sent as `ESC *` bands, resampled to the band mode's
+/// resolution.
+pub(crate) struct Columns<'a> {
+    pub(crate) data: &'a [u8],
+    pub(crate) width: u16,
+    pub(crate) height: u16,
+    pub(crate) mode: BitImageMode,
+}
+
+/// Whether pixel (`x`, `y`) of a packed `width` × `height` raster is set;
+/// pixels outside the image are blank.
+fn pixel(data: &[u8], width: u16, height: u16, x: u16, y: u16) -> bool {
+    if x >= width || y >= height {
+        return false;
+    }
+    let stride = usize::from(width.div_ceil(8));
+    let byte = data[usize::from(y) * stride + usize::from(x / 8)];
+    byte & (0x80 >> (x % 8)) != 0
+}
+
+/// Write `data` to `out` through `batch`.
+fn push<W: Write>(batch: &mut Batch, mut data: &[u8], out: &mut W) -> Result<(), W::Error> {
+    while let Some(full) = batch.fill(&mut data) {
+        out.write(full)?;
+    }
+    Ok(())
+}
+
+impl Columns<'_> {
+    fn pixel(&self, x: u16, y: u32) -> bool {
+        // Rows past the image, including any beyond `u16`, are blank.
+        u16::try_from(y).is_ok_and(|y| pixel(self.data, self.width, self.height, x, y))
+    }
+
+    /// Byte `k` of the column at image x-coordinate `x` in the band starting
+    /// at row `top`.
+    fn byte(&self, x: u16, top: u16, k: u16) -> u8 {
+        let step = u32::from(self.mode.dot_height());
+        (0..8u32)
+            .filter(|&bit| {
+                let row = u32::from(k) * 8 + bit;
+                self.pixel(x, u32::from(top) + row * step)
+            })
+            .fold(0, |byte, bit| byte | 0x80 >> bit)
+    }
+}
+
+impl Raster for Columns<'_> {
+    fn command(&self) -> Command<'_> {
+        Command::BitImage {
+            mode: self.mode,
+            width: self.width.div_ceil(self.mode.dot_width()),
+            data: &[],
+        }
+    }
+
+    fn encode<W: Write>(&self, out: &mut W) -> Result<(), W::Error> {
+        let step = self.mode.dot_width();
+        let columns = self.width.div_ceil(step).to_le_bytes();
+        out.write(&[0x1B, 0x33, BAND as u8])?;
+        let mut batch = Batch::new();
+        for top in (0..self.height).step_by(BAND.into()) {
+            let header = [0x1B, 0x2A, self.mode.as_byte(), columns[0], columns[1]];
+            push(&mut batch, &header, out)?;
+            for x in (0..self.width).step_by(step.into()) {
+                for k in 0..self.mode.bytes_per_column() {
+                    push(&mut batch, &[self.byte(x, top, k)], out)?;
+                }
+            }
+            push(&mut batch, b"\n", out)?;
+        }
+        push(&mut batch, &[0x1B, 0x32], out)?;
+        match batch.rest() {
+            [] => Ok(()),
+            rest => out.write(rest),
+        }
+    }
+}
+
+/// A raster image sent as the `GS *` downloaded bit image, padded to whole
+/// bytes each way.
+pub(crate) struct Downloaded<'a> {
+    pub(crate) data: &'a [u8],
+    pub(crate) width: u16,
+    pub(crate) height: u16,
+}
+
+impl Raster for Downloaded<'_> {
+    fn command(&self) -> Command<'_> {
+        Command::DefineDownloadedImage {
+            width: self.width.div_ceil(8) as u8,
+            height: self.height.div_ceil(8) as u8,
+            data: &[],
+        }
+    }
+
+    fn encode<W: Write>(&self, out: &mut W) -> Result<(), W::Error> {
+        let x = self.width.div_ceil(8);
+        let y = self.height.div_ceil(8);
+        out.write(&[0x1D, 0x2A, x as u8, y as u8])?;
+        let mut batch = Batch::new();
+        for column in 0..x * 8 {
+            for k in 0..y {
+                let byte = (0..8)
+                    .filter(|bit| pixel(self.data, self.width, self.height, column, k * 8 + bit))
+                    .fold(0, |byte, bit| byte | 0x80 >> bit);
+                push(&mut batch, &[byte], out)?;
+            }
+        }
+        match batch.rest() {
+            [] => Ok(()),
+            rest => out.write(rest),
+        }
+    }
+}
@@ -0,0 +1,979 @@
+use super::{
+    check_user_memory, Align, AsbFlags, BitImageMode, BitImageScale, Charset, CodePage, Color,
+    CustomSetting, CutMode, Density, Font, Justification, LabelStock, MacroMode, MarkPosition,
+    PaperSensors, PrintSpeed, PrinterError, PrinterIdKind, Stations, StatusRequest, TestPattern,
+    UnderlineMode, Write, MAX_MARK_OFFSET, USER_MEMORY_CHUNK,
+};
+
+/// A single printer operation, decoupled from transmission.
+///
+/// Commands can be built offline, stored in lists and encoded later with
+/// [`Command::encode`] or sent through [`Printer::send`](crate::Printer::send).
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Command<'a> {
+    /// Plain text.
+    Text(&'a str),
+    /// Line feed (`LF`), printing the buffered line.
+    LineFeed,
+    /// Carriage return (`CR`), which prints the buffered line on printers
+    /// configured for it and is ignored by most others.
+    CarriageReturn,
+    /// Discard the data of the current line in page mode (`CAN`).
+    CancelLine,
+    /// Move the print position to the start of the line (`GS T n`), first
+    /// printing the buffered data if `print` is set and discarding it
+    /// otherwise.
+    ToLineStart {
+        /// Print the buffered data instead of discarding it.
+        print: bool,
+    },
+    /// Reset the printer to its power-on settings (`ESC @`).
+    Initialize,
+    /// Select the character code table (`ESC t n`).
+    SelectCodePage(CodePage),
+    /// Select the international character set (`ESC R n`).
+    SetCharset(Charset),
+    /// Set the line spacing to the given number of motion units (`ESC 3 n`).
+    SetLineSpacing(u8),
+    /// Return to the default line spacing, about 3.75mm (`ESC 2`).
+    DefaultLineSpacing,
+    /// Feed the given number of lines (`ESC d n`).
+    Feed(u8),
+    /// Print the buffer and feed backwards by the given number of motion
+    /// units, at most 48 (`ESC K n`).
+    ReverseFeed(u8),
+    /// Print the buffer and feed by the given number of motion units
+    /// (`ESC J n`).
+    FeedUnits(u8),
+    /// Cut the paper (`GS V m`).
+    Cut(CutMode),
+    /// Feed to the cutting position plus `feed` motion units, then cut
+    /// (`GS V m n`).
+    FeedAndCut {
+        /// Cut to perform.
+        mode: CutMode,
+        /// Extra feed in vertical motion units.
+        feed: u8,
+    },
+    /// Enable or disable bold (`ESC E n`).
+    SetBold(bool),
+    /// Set underline mode (`ESC - n`).
+    SetUnderline(UnderlineMode),
+    /// Set alignment (`ESC a n`).
+    SetAlign(Align),
+    /// Select font (`ESC M n`).
+    SetFont(Font),
+    /// Set character size multipliers (`GS ! n`), each 0–7.
+    SetSize {
+        /// Width multiplier.
+        width: u8,
+        /// Height multiplier.
+        height: u8,
+    },
+    /// Enable or disable inverted printing (`GS B n`).
+    SetInvert(bool),
+    /// Enable or disable upside-down (180° rotated) printing (`ESC { n`).
+    SetUpsideDown(bool),
+    /// Set justification (`ESC a n`, same encoding as `SetAlign`).
+    SetJustification(Justification),
+    /// Set print density (`GS | n`).
+    SetDensity(Density),
+    /// Set print speed (`US P n`).
+    SetPrintSpeed(PrintSpeed),
+    /// Set the serial baud rate (vendor `ESC # # SBDR`).
+    SetBaudRate(u32),
+    /// Set the maximum print speed (vendor `ESC # # STSP`).
+    SetMaxSpeed(u8),
+    /// Enable or disable XON/XOFF flow control (vendor `ESC # # SFFC`).
+    SetSoftwareFlowControl(bool),
+    /// Enable or disable black mark detection.
+    SetBlackMark(bool),
+    /// Feed marked paper to the print start position (`GS FF`).
+    FeedToMark,
+    /// Print and feed to the next mark's print start position (`FF`).
+    PrintAndFeedToMark,
+    /// Print a test pattern on roll paper (`GS ( A`).
+    TestPrint(TestPattern),
+    /// Store bytes in NV user memory at `address` (`FS g 1`).
+    WriteUserMemory {
+        /// Offset into user memory.
+        address: u32,
+        /// Bytes to store, at most 80.
+        data: &'a [u8],
+    },
+    /// Request `len` bytes of NV user memory from `address` (`FS g 2`).
+    ReadUserMemory {
+        /// Offset into user memory.
+        address: u32,
+        /// Number of bytes to read, at most 80.
+        len: u16,
+    },
+    /// Set the idle time before the printer goes to sleep, in seconds;
+    /// 0 disables sleeping (`ESC 8 n t1 t2`, portable printers).
+    SetSleepTimeout(u16),
+    /// A `NUL` byte, ignored by the command parser but enough to wake a
+    /// sleeping printer's interface.
+    Wake,
+    /// Enter user setting mode (`GS ( E` function 1).
+    EnterUserSetting,
+    /// Leave user setting mode, resetting the printer (`GS ( E` function 2).
+    ExitUserSetting,
+    /// Change memory switch `switch` (1–8) (`GS ( E` function 3).
+    ///
+    /// Only the bits set in `mask` are changed, to the corresponding bits of
+    /// `value`.
+    SetMemorySwitch {
+        /// Memory switch number, 1–8.
+        switch: u8,
+        /// Bits to change.
+        mask: u8,
+        /// New values for the bits in `mask`.
+        value: u8,
+    },
+    /// Request the settings of memory switch `n` (`GS ( E` function 4).
+    TransmitMemorySwitch(u8),
+    /// Store a customized setting value (`GS ( E` function 5).
+    SetCustomValue {
+        /// Setting to change.
+        setting: CustomSetting,
+        /// Raw 16-bit value.
+        value: u16,
+    },
+    /// Request a stored customized setting value (`GS ( E` function 6).
+    TransmitCustomValue(CustomSetting),
+    /// Select the paper sensors that signal paper end (`ESC c 3 n`).
+    SetPaperSignalSensors(PaperSensors),
+    /// Select the paper sensors that stop printing (`ESC c 4 n`).
+    SetPaperStopSensors(PaperSensors),
+    /// Select the stations printed on (`ESC c 0 n`).
+    SelectPrintStations(Stations),
+    /// Select the stations that setting commands apply to (`ESC c 1 n`).
+    SelectCommandStations(Stations),
+    /// Set the slip insertion timeout in minutes and the delay in units of
+    /// 100ms before printing an inserted slip, each at most 64 (`ESC f`).
+    SetSlipWait {
+        /// How long to wait for a sheet to be inserted.
+        minutes: u8,
+        /// Delay after insertion before printing starts.
+        delay: u8,
+    },
+    /// Release the slip or validation sheet (`ESC q`).
+    ReleasePaper,
+    /// Read the E13B MICR line of the inserted check (`FS a 0 0`).
+    ReadMicr,
+    /// Eject the check from the MICR reader (`FS a 2`).
+    EjectCheck,
+    /// Transmit the MICR line read last (`FS b 1`).
+    TransmitMicr,
+    /// Select label stock and label length (`FS ( L` function 33).
+    SetLabelLayout {
+        /// Paper stock; [`LabelStock::Continuous`] turns label mode off.
+        stock: LabelStock,
+        /// Distance between label starts in 0.1mm, ignored for continuous
+        /// paper.
+        length: u16,
+    },
+    /// Feed to the next label's print start position (`FS ( L` function 67).
+    FeedToLabel,
+    /// Feed the current label to the cutting position (`FS ( L` function 66).
+    FeedLabelToCut,
+    /// Enable or disable the label peeler (`FS ( L` function 68).
+    SetPeeler(bool),
+    /// Request the peeler status (`DLE EOT 8 3`).
+    TransmitPeelerStatus,
+    /// Adjust a position relative to the black mark (`GS ( F`).
+    SetMarkOffset {
+        /// Position to adjust.
+        position: MarkPosition,
+        /// Offset in vertical motion units; negative moves backwards.
+        offset: i16,
+    },
+    /// Request the paper sensor status (`GS r 1`).
+    QueryPaperStatus,
+    /// Request a real-time status byte (`DLE EOT n`).
+    TransmitStatus(StatusRequest),
+    /// Discard the receive and print buffers, processed in real time
+    /// (`DLE DC4 8`).
+    ClearBuffers,
+    /// Request a printer ID byte (`GS I n`).
+    TransmitPrinterId(PrinterIdKind),
+    /// Ask the printer to send back process ID `n`, 0–9999, once it has
+    /// processed everything before it (`GS ( H` function 48).
+    TransmitProcessId(u16),
+    /// Enable or disable Automatic Status Back (`GS a n`).
+    SetAutoStatusBack(AsbFlags),
+    /// Start or end a macro definition (`GS :`).
+    ToggleMacroDefinition,
+    /// Execute the stored macro (`GS ^ r t m`).
+    ExecuteMacro {
+        /// Number of times to run the macro.
+        times: u8,
+        /// Wait between runs, in units of 100ms.
+        wait: u8,
+        /// When each repetition starts.
+        mode: MacroMode,
+    },
+    /// Raster bit image (`GS v 0`) with packed row-major data.
+    RasterImage {
+        /// Image width in pixels.
+        width: u16,
+        /// Image height in pixels.
+        height: u16,
+        /// Scaling applied by the printer (`m`).
+        scale: BitImageScale,
+        /// Packed bitmap data, 1 bit per pixel.
+        data: &'a [u8],
+    },
+    /// Column-format bit image (`ESC * m nL nH`), one band of the image.
+    BitImage {
+        /// Dot density, which also sets the band height.
+        mode: BitImageMode,
+        /// Number of columns.
+        width: u16,
+        /// Column data, top to bottom then left to right, with
+        /// [`BitImageMode::bytes_per_column`] bytes per column.
+        data: &'a [u8],
+    },
+    /// Define the downloaded bit image in RAM (`GS * x y`).
+    DefineDownloadedImage {
+        /// Image width in units of 8 dots.
+        width: u8,
+        /// Image height in units of 8 dots.
+        height: u8,
+        /// Column data, left to right, with `height` bytes per column and
+        /// `width * 8` columns.
+        data: &'a [u8],
+    },
+    /// Print the downloaded bit image (`GS / m`).
+    PrintDownloadedImage(BitImageScale),
+    /// Select the print color on two-color printers (`ESC r n`).
+    SetColor(Color),
+    /// Store a raster image in the print buffer in `color` without printing
+    /// it (`GS ( L` function 112, or `GS 8 L` when large).
+    ///
+    /// Images stored in different colors overlap; print them together with
+    /// [`Command::PrintGraphics`].
+    StoreGraphics {
+        /// Color to print the set pixels in.
+        color: Color,
+        /// Image width in dots.
+        width: u16,
+        /// Image height in dots.
+        height: u16,
+        /// Packed bitmap data, 1 bit per pixel, rows starting on a byte
+        /// boundary.
+        data: &'a [u8],
+    },
+    /// Print the graphics stored in the print buffer (`GS ( L` function 50).
+    PrintGraphics,
+    /// Bytes passed through unchanged.
+    Raw(&'a [u8]),
+    /// A vendor-specific sequence, passed through unchanged.
+    ///
+    /// Used by the [`vendor`](crate::vendor) extension traits; `name`
+    /// identifies the command in errors.
+    Vendor {
+        /// Name of the command.
+        name: &'static str,
+        /// Encoded bytes.
+        data: &'a [u8],
+    },
+    /// Unrecognised bytes produced by the [`Decoder`](crate::Decoder),
+    /// encoded unchanged.
+    Unknown(&'a [u8]),
+}
+
+/// Error returned when an encoded command doesn't fit in the output buffer.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BufferFull;
+
+struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    pos: usize,
+}
+
+impl Write for SliceWriter<'_> {
+    type Error = BufferFull;
+
+    fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        let end = self.pos + data.len();
+        if end > self.buf.len() {
+            return Err(BufferFull);
+        }
+        self.buf[self.pos..end].copy_from_slice(data);
+        self.pos = end;
+        Ok(())
+    }
+}
+
+/// Writer that only counts the bytes written to it.
+pub(crate) struct CountingWriter(pub(crate) usize);
+
+impl Write for CountingWriter {
+    type Error = core::convert::Infallible;
+
+    fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        self.0 += data.len();
+        Ok(())
+    }
+}
+
+/// `DLE DC4 8 1 3 20 1 6 2 8`.
+pub(crate) const CLEAR_BUFFERS: [u8; 10] =
+    [0x10, 0x14, 0x08, 0x01, 0x03, 0x14, 0x01, 0x06, 0x02, 0x08];
+
+/// Format `n` as ASCII decimal digits into `buf`.
+fn decimal(mut n: u16, buf: &mut [u8; 5]) -> &[u8] {
+    let mut start = buf.len();
+    loop {
+        start -= 1;
+        buf[start] = b'0' + (n % 10) as u8;
+        n /= 10;
+        if n == 0 {
+            return &buf[start..];
+        }
+    }
+}
+
+/// Largest process ID `GS ( H` can carry in its four digits.
+const MAX_PROCESS_ID: u16 = 9999;
+
+/// The four ASCII digits of a `GS ( H` process ID.
+pub(crate) fn process_id_digits(id: u16) -> [u8; 4] {
+    let digit = |place: u16| b'0' + (id / place % 10) as u8;
+    [digit(1000), digit(100), digit(10), digit(1)]
+}
+
+/// Most motion units `ESC K` feeds backwards.
+const MAX_REVERSE_FEED: u8 = 48;
+
+/// Fail with [`PrinterError::InvalidParameter`] unless `range` contains
+/// `value`.
+fn check_range<E>(
+    command: &Command,
+    value: i64,
+    range: core::ops::RangeInclusive<i64>,
+) -> Result<(), PrinterError<E>> {
+    if !range.contains(&value) {
+        return Err(PrinterError::InvalidParameter {
+            command: command.name(),
+            value,
+            range,
+        });
+    }
+    Ok(())
+}
+
+/// Number of bytes of packed data in a `width` × `height` image with
+/// `per_byte` pixels to a byte, each row starting on a byte boundary.
+///
+/// Empty images are rejected, since printers misparse the data that
+/// follows them, as are images whose size overflows `usize`.
+pub(crate) fn image_len<E>(
+    command: &'static str,
+    width: u16,
+    height: u16,
+    per_byte: u16,
+) -> Result<usize, PrinterError<E>> {
+    for value in [width, height] {
+        if value == 0 {
+            return Err(PrinterError::InvalidParameter {
+                command,
+                value: 0,
+                range: 1..=u16::MAX.into(),
+            });
+        }
+    }
+    usize::from(width.div_ceil(per_byte))
+        .checked_mul(usize::from(height))
+        .ok_or(PrinterError::ImageTooLarge {
+            command,
+            width,
+            height,
+        })
+}
+
+/// Bytes of bit image memory available to `GS *`.
+const DOWNLOADED_IMAGE_MEMORY: u16 = 1536;
+
+/// Number of data bytes in a `GS *` image `width` × `height` units of 8
+/// dots, checking it fits the printer's bit image memory.
+pub(crate) fn downloaded_image_len<E>(
+    command: &'static str,
+    width: u16,
+    height: u16,
+) -> Result<usize, PrinterError<E>> {
+    for (value, max) in [(width, 255), (height, 48)] {
+        if !(1..=max).contains(&value) {
+            return Err(PrinterError::InvalidParameter {
+                command,
+                value: value.into(),
+                range: 1..=max.into(),
+            });
+        }
+    }
+    let len = width * height;
+    if len > DOWNLOADED_IMAGE_MEMORY {
+        return Err(PrinterError::InvalidParameter {
+            command,
+            value: len.into(),
+            range: 1..=DOWNLOADED_IMAGE_MEMORY.into(),
+        });
+    }
+    Ok(usize::from(len) * 8)
+}
+
+/// `FS g f m a1 a2 a3 a4 nL nH`, with `m` fixed at 48.
+pub(crate) fn user_memory_header(f: u8, address: u32, len: u16) -> [u8; 10] {
+    let a = address.to_le_bytes();
+    let n = len.to_le_bytes();
+    [0x1C, 0x67, f, 0x30, a[0], a[1], a[2], a[3], n[0], n[1]]
+}
+
+/// Header for a `GS v 0` raster image of the given size.
+pub(crate) fn raster_header(width: u16, height: u16, scale: BitImageScale) -> [u8; 8] {
+    let x = width.div_ceil(8).to_le_bytes();
+    let y = height.to_le_bytes();
+    [0x1D, 0x76, 0x30, scale.as_byte(), x[0], x[1], y[0], y[1]]
+}
+
+/// Header for an `ESC *` bit image band of `width` columns.
+pub(crate) fn bit_image_header(mode: BitImageMode, width: u16) -> [u8; 5] {
+    let n = width.to_le_bytes();
+    [0x1B, 0x2A, mode.as_byte(), n[0], n[1]]
+}
+
+/// Header for storing a `width` by `height` graphic of `len` bytes in
+/// `color`: `GS ( L` function 112, or `GS 8 L` when too large for it.
+///
+/// Returns the header and its length.
+pub(crate) fn store_graphics_header(
+    color: Color,
+    width: u16,
+    height: u16,
+    len: usize,
+) -> ([u8; 17], usize) {
+    let mut header = [0u8; 17];
+    let params = [0x30, 0x70, 0x30, 0x01, 0x01, b'1' + color.as_byte()];
+    let total = (params.len() + 4 + len) as u32;
+    let start = match u16::try_from(total) {
+        Ok(total) => {
+            header[..5].copy_from_slice(&[0x1D, 0x28, 0x4C, total as u8, (total >> 8) as u8]);
+            5
+        }
+        Err(_) => {
+            header[..3].copy_from_slice(&[0x1D, 0x38, 0x4C]);
+            header[3..7].copy_from_slice(&total.to_le_bytes());
+            7
+        }
+    };
+    let w = width.to_le_bytes();
+    let h = height.to_le_bytes();
+    header[start..start + 6].copy_from_slice(&params);
+    header[start + 6..start + 10].copy_from_slice(&[w[0], w[1], h[0], h[1]]);
+    (header, start + 10)
+}
+
+impl Command<'_> {
+    /// Short name of this command, used as error context.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Command::Text(_) => "Text",
+            Command::LineFeed => "LineFeed",
+            Command::CarriageReturn => "CarriageReturn",
+            Command::CancelLine => "CancelLine",
+            Command::ToLineStart { .. } => "ToLineStart",
+            Command::Initialize => "Initialize",
+            Command::SelectCodePage(_) => "SelectCodePage",
+            Command::SetCharset(_) => "SetCharset",
+            Command::SetLineSpacing(_) => "SetLineSpacing",
+            Command::DefaultLineSpacing => "DefaultLineSpacing",
+            Command::Feed(_) => "Feed",
+            Command::ReverseFeed(_) => "ReverseFeed",
+            Command::FeedUnits(_) => "FeedUnits",
+            Command::Cut(_) => "Cut",
+            Command::FeedAndCut { .. } => "FeedAndCut",
+            Command::SetBold(_) => "SetBold",
+            Command::SetUnderline(_) => "SetUnderline",
+            Command::SetAlign(_) => "SetAlign",
+            Command::SetFont(_) => "SetFont",
+            Command::SetSize { .. } => "SetSize",
+            Command::SetInvert(_) => "SetInvert",
+            Command::SetUpsideDown(_) => "SetUpsideDown",
+            Command::SetJustification(_) => "SetJustification",
+            Command::SetDensity(_) => "SetDensity",
+            Command::SetPrintSpeed(_) => "SetPrintSpeed",
+            Command::SetBaudRate(_) => "SetBaudRate",
+            Command::SetMaxSpeed(_) => "SetMaxSpeed",
+            Command::SetSoftwareFlowControl(_) => "SetSoftwareFlowControl",
+            Command::SetBlackMark(_) => "SetBlackMark",
+            Command::TestPrint(_) => "TestPrint",
+            Command::WriteUserMemory { .. } => "WriteUserMemory",
+            Command::ReadUserMemory { .. } => "ReadUserMemory",
+            Command::SetSleepTimeout(_) => "SetSleepTimeout",
+            Command::Wake => "Wake",
+            Command::EnterUserSetting => "EnterUserSetting",
+            Command::ExitUserSetting => "ExitUserSetting",
+            Command::SetMemorySwitch { .. } => "SetMemorySwitch",
+            Command::TransmitMemorySwitch(_) => "TransmitMemorySwitch",
+            Command::SetCustomValue { .. } => "SetCustomValue",
+            Command::TransmitCustomValue(_) => "TransmitCustomValue",
+            Command::SetPaperSignalSensors(_) => "SetPaperSignalSensors",
+            Command::SetPaperStopSensors(_) => "SetPaperStopSensors",
+            Command::SelectPrintStations(_) => "SelectPrintStations",
+            Command::SelectCommandStations(_) => "SelectCommandStations",
+            Command::SetSlipWait { .. } => "SetSlipWait",
+            Command::ReleasePaper => "ReleasePaper",
+            Command::ReadMicr => "ReadMicr",
+            Command::EjectCheck => "EjectCheck",
+            Command::TransmitMicr => "TransmitMicr",
+            Command::SetLabelLayout { .. } => "SetLabelLayout",
+            Command::FeedToLabel => "FeedToLabel",
+            Command::FeedLabelToCut => "FeedLabelToCut",
+            Command::SetPeeler(_) => "SetPeeler",
+            Command::TransmitPeelerStatus => "TransmitPeelerStatus",
+            Command::FeedToMark => "FeedToMark",
+            Command::PrintAndFeedToMark => "PrintAndFeedToMark",
+            Command::SetMarkOffset { .. } => "SetMarkOffset",
+            Command::QueryPaperStatus => "QueryPaperStatus",
+            Command::TransmitStatus(_) => "TransmitStatus",
+            Command::ClearBuffers => "ClearBuffers",
+            Command::TransmitPrinterId(_) => "TransmitPrinterId",
+            Command::TransmitProcessId(_) => "TransmitProcessId",
+            Command::SetAutoStatusBack(_) => "SetAutoStatusBack",
+            Command::ToggleMacroDefinition => "ToggleMacroDefinition",
+            Command::ExecuteMacro { .. } => "ExecuteMacro",
+            Command::RasterImage { .. } => "RasterImage",
+            Command::BitImage { .. } => "BitImage",
+            Command::DefineDownloadedImage { .. } => "DefineDownloadedImage",
+            Command::PrintDownloadedImage(_) => "PrintDownloadedImage",
+            Command::SetColor(_) => "SetColor",
+            Command::StoreGraphics { .. } => "StoreGraphics",
+            Command::PrintGraphics => "PrintGraphics",
+            Command::Raw(_) => "Raw",
+            Command::Vendor { name, .. } => name,
+            Command::Unknown(_) => "Unknown",
+        }
+    }
+
+    /// Check the command's parameters against the ranges printers accept.
+    ///
+    /// [`encode`](Command::encode) sends whatever it is given, truncating
+    /// fields that don't fit; printers then misread the following bytes or
+    /// act on a different value. [`Printer::send`](crate::Printer::send)
+    /// calls this first and sends nothing on failure.
+    ///
+    /// ```
+    /// # use escpos_embedded::{Command, PrinterError};
+    /// let command = Command::SetSize { width: 8, height: 0 };
+    /// assert_eq!(
+    ///     command.validate::<()>(),
+    ///     Err(PrinterError::InvalidParameter {
+    ///         command: "SetSize",
+    ///         value: 8,
+    ///         range: 0..=7,
+    ///     })
+    /// );
+    /// ```
+    pub fn validate<E>(&self) -> Result<(), PrinterError<E>> {
+        match *self {
+            Command::ReverseFeed(units) => {
+                check_range(self, units.into(), 0..=MAX_REVERSE_FEED.into())
+            }
+            Command::SetSize { width, height } => {
+                check_range(self, width.into(), 0..=7)?;
+                check_range(self, height.into(), 0..=7)
+            }
+            Command::WriteUserMemory { address, data } => {
+                let len = data.len().try_into().unwrap_or(i64::MAX);
+                check_range(self, len, 0..=USER_MEMORY_CHUNK as i64)?;
+                check_user_memory(self.name(), address, data.len())
+            }
+            Command::ReadUserMemory { address, len } => {
+                check_range(self, len.into(), 0..=USER_MEMORY_CHUNK as i64)?;
+                check_user_memory(self.name(), address, len.into())
+            }
+            Command::SelectPrintStations(stations) | Command::SelectCommandStations(stations) => {
+                check_range(self, stations.0.into(), 1..=0x0F)
+            }
+            Command::SetSlipWait { minutes, delay } => {
+                check_range(self, minutes.into(), 0..=64)?;
+                check_range(self, delay.into(), 0..=64)
+            }
+            Command::SetMemorySwitch { switch, .. } | Command::TransmitMemorySwitch(switch) => {
+                check_range(self, switch.into(), 1..=8)
+            }
+            Command::SetCustomValue {
+                setting: CustomSetting::Density,
+                value,
+            } => check_range(self, (value as i16).into(), -6..=6),
+            Command::SetCustomValue {
+                setting: CustomSetting::PrintSpeed,
+                value,
+            } => check_range(self, value.into(), 1..=13),
+            Command::TransmitProcessId(id) => {
+                check_range(self, id.into(), 0..=MAX_PROCESS_ID.into())
+            }
+            Command::SetMarkOffset { offset, .. } => {
+                check_range(self, offset.into(), -MAX_MARK_OFFSET..=MAX_MARK_OFFSET)
+            }
+            Command::RasterImage {
+                width,
+                height,
+                data,
+                ..
+            }
+            | Command::StoreGraphics {
+                width,
+                height,
+                data,
+                ..
+            } => {
+                let expected = image_len(self.name(), width, height, 8)?;
+                if data.len() != expected {
+                    return Err(PrinterError::ImageSizeMismatch {
+                        command: self.name(),
+                        expected,
+                        actual: data.len(),
+                    });
+                }
+                Ok(())
+            }
+            Command::BitImage { mode, width, data } => {
+                let expected = image_len(self.name(), mode.bytes_per_column() * 8, width, 8)?;
+                if data.len() != expected {
+                    return Err(PrinterError::ImageSizeMismatch {
+                        command: self.name(),
+                        expected,
+                        actual: data.len(),
+                    });
+                }
+                Ok(())
+            }
+            Command::DefineDownloadedImage {
+                width,
+                height,
+                data,
+            } => {
+                let expected = downloaded_image_len(self.name(), width.into(), height.into())?;
+                if data.len() != expected {
+                    return Err(PrinterError::ImageSizeMismatch {
+                        command: self.name(),
+                        expected,
+                        actual: data.len(),
+                    });
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Write the encoded bytes of this command to `out`.
+    pub fn encode<W: Write>(&self, out: &mut W) -> Result<(), W::Error> {
+        match *self {
+            Command::Text(text) => out.write(text.as_bytes()),
+            Command::LineFeed => out.write(b"\n"),
+            Command::CarriageReturn => out.write(b"\r"),
+            Command::CancelLine => out.write(&[0x18]),
+            Command::ToLineStart { print } => out.write(&[0x1D, 0x54, print as u8]),
+            Command::Initialize => out.write(&[0x1B, 0x40]),
+            Command::SelectCodePage(page) => out.write(&[0x1B, 0x74, page.as_byte()]),
+            Command::SetCharset(charset) => out.write(&[0x1B, 0x52, charset.as_byte()]),
+            Command::SetLineSpacing(units) => out.write(&[0x1B, 0x33, units]),
+            Command::DefaultLineSpacing => out.write(&[0x1B, 0x32]),
+            Command::Feed(lines) => out.write(&[0x1B, 0x64, lines]),
+            Command::ReverseFeed(units) => out.write(&[0x1B, 0x4B, units]),
+            Command::FeedUnits(units) => out.write(&[0x1B, 0x4A, units]),
+            Command::Cut(CutMode::PartialThreePoints) => out.write(&[0x1B, 0x6D]),
+            Command::Cut(mode) => out.write(&[0x1D, 0x56, mode.as_byte()]),
+            Command::FeedAndCut { mode, feed } => {
+                out.write(&[0x1D, 0x56, 0x41 + mode.as_byte(), feed])
+            }
+            Command::SetBold(on) => out.write(&[0x1B, 0x45, on as u8]),
+            Command::SetUnderline(mode) => out.write(&[0x1B, 0x2D, mode.as_byte()]),
+            Command::SetAlign(align) => out.write(&[0x1B, 0x61, align.as_byte()]),
+            Command::SetFont(font) => out.write(&[0x1B, 0x4D, font.as_byte()]),
+            Command::SetSize { width, height } => {
+                let param = (width.min(7) << 4) | height.min(7);
+                out.write(&[0x1D, 0x21, param])
+            }
+            Command::SetInvert(on) => out.write(&[0x1D, 0x42, on as u8]),
+            Command::SetUpsideDown(on) => out.write(&[0x1B, 0x7B, on as u8]),
+            Command::SetJustification(mode) => out.write(&[0x1B, 0x61, mode.as_byte()]),
+            Command::SetDensity(level) => out.write(&[0x1D, 0x7C, level.as_byte()]),
+            Command::SetPrintSpeed(speed) => out.write(&[0x1F, 0x50, speed.as_byte()]),
+            Command::SetBaudRate(baud) => {
+                let b = baud.to_le_bytes();
+                out.write(&[
+                    0x1B, 0x23, 0x23, b'S', b'B', b'D', b'R', b[0], b[1], b[2], b[3],
+                ])
+            }
+            Command::SetMaxSpeed(speed) => {
+                out.write(&[0x1B, 0x23, 0x23, b'S', b'T', b'S', b'P', speed])
+            }
+            Command::SetSoftwareFlowControl(on) => {
+                out.write(&[0x1B, 0x23, 0x23, b'S', b'F', b'F', b'C', on as u8])
+            }
+            Command::SetBlackMark(on) => {
+                let flag = if on { 0x44 } else { 0x66 };
+                out.write(&[0x1F, 0x1B, 0x1F, 0x80, 0x04, 0x05, 0x06, flag])
+            }
+            Command::TestPrint(pattern) => {
+                out.write(&[0x1D, 0x28, 0x41, 0x02, 0x00, 0x00, pattern.as_byte()])
+            }
+            Command::WriteUserMemory { address, data } => {
+                let len = data.len().min(usize::from(u16::MAX));
+                out.write(&user_memory_header(b'1', address, len as u16))?;
+                out.write(&data[..len])
+            }
+            Command::ReadUserMemory { address, len } => {
+                out.write(&user_memory_header(b'2', address, len))
+            }
+            Command::SetSleepTimeout(seconds) => {
+                let [t1, t2] = seconds.to_le_bytes();
+                out.write(&[0x1B, 0x38, (seconds != 0) as u8, t1, t2])
+            }
+            Command::Wake => out.write(&[0x00]),
+            Command::EnterUserSetting => {
+                out.write(&[0x1D, 0x28, 0x45, 0x03, 0x00, 0x01, b'I', b'N'])
+            }
+            Command::ExitUserSetting => {
+                out.write(&[0x1D, 0x28, 0x45, 0x04, 0x00, 0x02, b'O', b'U', b'T'])
+            }
+            Command::SetMemorySwitch {
+                switch,
+                mask,
+                value,
+            } => {
+                // One ASCII digit per bit, switch 8 first; '2' leaves it alone.
+                let mut bits = [0u8; 8];
+                for (i, bit) in bits.iter_mut().enumerate() {
+                    let n = 7 - i;
+                    *bit = match (mask >> n & 1, value >> n & 1) {
+                        (0, _) => b'2',
+                        (_, 0) => b'0',
+                        _ => b'1',
+                    };
+                }
+                out.write(&[0x1D, 0x28, 0x45, 0x0A, 0x00, 0x03, switch])?;
+                out.write(&bits)
+            }
+            Command::TransmitMemorySwitch(switch) => {
+                out.write(&[0x1D, 0x28, 0x45, 0x02, 0x00, 0x04, switch])
+            }
+            Command::SetCustomValue { setting, value } => {
+                let [n_l, n_h] = value.to_le_bytes();
+                out.write(&[
+                    0x1D,
+                    0x28,
+                    0x45,
+                    0x04,
+                    0x00,
+                    0x05,
+                    setting.as_byte(),
+                    n_l,
+                    n_h,
+                ])
+            }
+            Command::TransmitCustomValue(setting) => {
+                out.write(&[0x1D, 0x28, 0x45, 0x02, 0x00, 0x06, setting.as_byte()])
+            }
+            Command::SetPaperSignalSensors(sensors) => out.write(&[0x1B, 0x63, 0x33, sensors.0]),
+            Command::SetPaperStopSensors(sensors) => out.write(&[0x1B, 0x63, 0x34, sensors.0]),
+            Command::SelectPrintStations(stations) => out.write(&[0x1B, 0x63, 0x30, stations.0]),
+            Command::SelectCommandStations(stations) => out.write(&[0x1B, 0x63, 0x31, stations.0]),
+            Command::SetSlipWait { minutes, delay } => out.write(&[0x1B, 0x66, minutes, delay]),
+            Command::ReleasePaper => out.write(&[0x1B, 0x71]),
+            Command::ReadMicr => out.write(&[0x1C, 0x61, 0x30, 0x00]),
+            Command::EjectCheck => out.write(&[0x1C, 0x61, 0x32]),
+            Command::TransmitMicr => out.write(&[0x1C, 0x62, 0x01]),
+            Command::SetLabelLayout { stock, length } => {
+                let mut digits = [0u8; 5];
+                let digits = match stock {
+                    LabelStock::Continuous => &[][..],
+                    _ => decimal(length, &mut digits),
+                };
+                // Function, stock and, for labels, the length and separator.
+                let len = 2 + digits.len() as u16 + !digits.is_empty() as u16;
+                let [p_l, p_h] = len.to_le_bytes();
+                out.write(&[0x1C, 0x28, 0x4C, p_l, p_h, 0x21, stock.as_byte()])?;
+                if digits.is_empty() {
+                    return Ok(());
+                }
+                out.write(digits)?;
+                out.write(b";")
+            }
+            Command::FeedToLabel => out.write(&[0x1C, 0x28, 0x4C, 0x02, 0x00, 0x43, 0x30]),
+            Command::FeedLabelToCut => out.write(&[0x1C, 0x28, 0x4C, 0x02, 0x00, 0x42, 0x30]),
+            Command::SetPeeler(on) => {
+                out.write(&[0x1C, 0x28, 0x4C, 0x02, 0x00, 0x44, 0x30 + on as u8])
+            }
+            Command::TransmitPeelerStatus => out.write(&[0x10, 0x04, 0x08, 0x03]),
+            Command::FeedToMark => out.write(&[0x1D, 0x0C]),
+            Command::PrintAndFeedToMark => out.write(&[0x0C]),
+            Command::SetMarkOffset { position, offset } => {
+                let n = offset.unsigned_abs().to_le_bytes();
+                let direction = (offset < 0) as u8;
+                out.write(&[
+                    0x1D,
+                    0x28,
+                    0x46,
+                    0x04,
+                    0x00,
+                    position.as_byte(),
+                    direction,
+                    n[0],
+                    n[1],
+                ])
+            }
+            Command::QueryPaperStatus => out.write(&[0x1D, 0x72, 0x01]),
+            Command::TransmitStatus(request) => out.write(&[0x10, 0x04, request.as_byte()]),
+            Command::ClearBuffers => out.write(&CLEAR_BUFFERS),
+            Command::TransmitPrinterId(kind) => out.write(&[0x1D, 0x49, kind.as_byte()]),
+            Command::TransmitProcessId(id) => {
+                let [d1, d2, d3, d4] = process_id_digits(id);
+                out.write(&[0x1D, 0x28, 0x48, 0x06, 0x00, 0x30, 0x30, d1, d2, d3, d4])
+            }
+            Command::SetAutoStatusBack(flags) => out.write(&[0x1D, 0x61, flags.0]),
+            Command::ToggleMacroDefinition => out.write(&[0x1D, 0x3A]),
+            Command::ExecuteMacro { times, wait, mode } => {
+                out.write(&[0x1D, 0x5E, times, wait, mode.as_byte()])
+            }
+            Command::RasterImage {
+                width,
+                height,
+                scale,
+                data,
+            } => {
+                out.write(&raster_header(width, height, scale))?;
+                out.write(data)
+            }
+            Command::BitImage { mode, width, data } => {
+                out.write(&bit_image_header(mode, width))?;
+                out.write(data)
+            }
+            Command::DefineDownloadedImage {
+                width,
+                height,
+                data,
+            } => {
+                out.write(&[0x1D, 0x2A, width, height])?;
+                out.write(data)
+            }
+            Command::PrintDownloadedImage(scale) => out.write(&[0x1D, 0x2F, scale.as_byte()]),
+            Command::SetColor(color) => out.write(&[0x1B, 0x72, color.as_byte()]),
+            Command::StoreGraphics {
+                color,
+                width,
+                height,
+                data,
+            } => {
+                let (header, len) = store_graphics_header(color, width, height, data.len());
+                out.write(&header[..len])?;
+                out.write(data)
+            }
+            Command::PrintGraphics => out.write(&[0x1D, 0x28, 0x4C, 0x02, 0x00, 0x30, 0x32]),
+            Command::Raw(data) | Command::Vendor { data, .. } | Command::Unknown(data) => {
+                out.write(data)
+            }
+        }
+    }
+
+    /// Encode this command into `buf`, returning the number of bytes written.
+    pub fn encode_to_slice(&self, buf: &mut [u8]) -> Result<usize, BufferFull> {
+        let mut writer = SliceWriter { buf, pos: 0 };
+        self.encode(&mut writer)?;
+        Ok(writer.pos)
+    }
+
+    /// Number of bytes this command encodes to.
+    pub fn encoded_len(&self) -> usize {
+        let mut counter = CountingWriter(0);
+        match self.encode(&mut counter) {
+            Ok(()) => counter.0,
+            Err(never) => match never {},
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_to_slice() {
+        let mut buf = [0u8; 8];
+        let n = Command::SetSize {
+            width: 9,
+            height: 1,
+        }
+        .encode_to_slice(&mut buf)
+        .unwrap();
+        assert_eq!(&buf[..n], &[0x1D, 0x21, 0x71]);
+    }
+
+    #[test]
+    fn test_validate() {
+        let invalid = |command, value, range| {
+            Err::<(), PrinterError<()>>(PrinterError::InvalidParameter {
+                command,
+                value,
+                range,
+            })
+        };
+        assert_eq!(Command::ReverseFeed(48).validate::<()>(), Ok(()));
+        assert_eq!(
+            Command::ReverseFeed(49).validate(),
+            invalid("ReverseFeed", 49, 0..=48)
+        );
+        assert_eq!(
+            Command::SetSize {
+                width: 0,
+                height: 9,
+            }
+            .validate(),
+            invalid("SetSize", 9, 0..=7)
+        );
+        assert_eq!(
+            Command::ReadUserMemory {
+                address: 0,
+                len: 81,
+            }
+            .validate(),
+            invalid("ReadUserMemory", 81, 0..=80)
+        );
+        assert_eq!(
+            Command::SetCustomValue {
+                setting: CustomSetting::Density,
+                value: -7i16 as u16,
+            }
+            .validate(),
+            invalid("SetCustomValue", -7, -6..=6)
+        );
+        assert_eq!(Command::Feed(255).validate::<()>(), Ok(()));
+    }
+
+    #[test]
+    fn test_encode_to_slice_full() {
+        let mut buf = [0u8; 2];
+        assert_eq!(
+            Command::Cut(CutMode::Full).encode_to_slice(&mut buf),
+            Err(BufferFull)
+        );
+    }
+
+    #[test]
+    fn test_encoded_len() {
+        let image = Command::RasterImage {
+            width: 9,
+            height: 2,
+            scale: BitImageScale::Normal,
+            data: &[0; 4],
+        };
+        assert_eq!(image.encoded_len(), 12);
+        assert_eq!(Command::Text("abc").encoded_len(), 3);
+    }
+}
@@ -0,0 +1,864 @@
+use super::command;
+use super::{
+    Align, AsbFlags, BitImageMode, BitImageScale, Charset, CodePage, Color, Command, CustomSetting,
+    CutMode, Density, Font, LabelStock, MacroMode, MarkPosition, PaperSensors, PrintSpeed,
+    PrinterIdKind, Stations, StatusRequest, TestPattern, UnderlineMode,
+};
+
+const NUL: u8 = 0x00;
+const DLE: u8 = 0x10;
+const ESC: u8 = 0x1B;
+const FS: u8 = 0x1C;
+const GS: u8 = 0x1D;
+const US: u8 = 0x1F;
+const LF: u8 = 0x0A;
+const FF: u8 = 0x0C;
+const CR: u8 = 0x0D;
+const CAN: u8 = 0x18;
+
+/// Parser turning an ESC/POS byte stream back into [`Command`]s.
+///
+/// Unrecognised sequences are yielded as [`Command::Unknown`]. Since the
+/// parameter length of an unknown command can't be known, only the prefix and
+/// function byte are consumed; any parameters that follow are decoded as
+/// ordinary data. Commands truncated by the end of the input are returned as a
+/// single `Unknown` covering the remaining bytes.
+///
+/// Raster image widths are recovered from the byte width, so they are always a
+/// multiple of 8. `ESC a` is always decoded as [`Command::SetAlign`], never
+/// [`Command::SetJustification`], as both share the same encoding.
+pub struct Decoder<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Decoder<'a> {
+    /// Create a decoder over a captured byte stream.
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    /// Offset of the next undecoded byte.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// The bytes not yet decoded.
+    pub fn remaining(&self) -> &'a [u8] {
+        &self.data[self.pos..]
+    }
+
+    fn peek(&self, offset: usize) -> Option<u8> {
+        self.data.get(self.pos + offset).copied()
+    }
+
+    /// Consume `len` bytes, yielding `command` if they are all available.
+    fn take(&mut self, len: usize, command: Command<'a>) -> Command<'a> {
+        if self.pos + len > self.data.len() {
+            return self.unknown(self.data.len() - self.pos);
+        }
+        self.pos += len;
+        command
+    }
+
+    fn unknown(&mut self, len: usize) -> Command<'a> {
+        let bytes = &self.data[self.pos..self.pos + len];
+        self.pos += len;
+        Command::Unknown(bytes)
+    }
+
+    fn text(&mut self) -> Command<'a> {
+        let run = self.data[self.pos..]
+            .iter()
+            .take_while(|&&b| is_text(b))
+            .count();
+        let bytes = &self.data[self.pos..self.pos + run];
+        match core::str::from_utf8(bytes) {
+            Ok(text) => self.take(run, Command::Text(text)),
+            Err(e) if e.valid_up_to() > 0 => {
+                let len = e.valid_up_to();
+                let text = core::str::from_utf8(&bytes[..len]).unwrap_or_default();
+                self.take(len, Command::Text(text))
+            }
+            Err(e) => self.unknown(e.error_len().unwrap_or(run)),
+        }
+    }
+
+    fn esc(&mut self) -> Command<'a> {
+        match self.peek(1) {
+            Some(b'@') => return self.take(2, Command::Initialize),
+            Some(b'c') => return self.paper_sensors(),
+            Some(b'm') => return self.take(2, Command::Cut(CutMode::PartialThreePoints)),
+            Some(b'i') => return self.take(2, Command::Cut(CutMode::Full)),
+            Some(b'2') => return self.take(2, Command::DefaultLineSpacing),
+            Some(b'q') => return self.take(2, Command::ReleasePaper),
+            Some(b'f') => return self.slip_wait(),
+            Some(b'8') => return self.sleep_timeout(),
+            Some(b'*') => return self.bit_image(),
+            _ => {}
+        }
+        let (Some(f), Some(n)) = (self.peek(1), self.peek(2)) else {
+            return self.unknown(self.remaining().len().min(2));
+        };
+        let command = match f {
+            b'd' => Command::Feed(n),
+            b'K' => Command::ReverseFeed(n),
+            b'J' => Command::FeedUnits(n),
+            b'3' => Command::SetLineSpacing(n),
+            b'R' => match Charset::from_byte(n) {
+                Some(charset) => Command::SetCharset(charset),
+                None => return self.unknown(3),
+            },
+            b'E' => Command::SetBold(n & 0x01 != 0),
+            b'{' => Command::SetUpsideDown(n & 0x01 != 0),
+            b'r' => match Color::from_byte(digit(n)) {
+                Some(color) => Command::SetColor(color),
+                None => return self.unknown(3),
+            },
+            b'-' => match UnderlineMode::from_byte(digit(n)) {
+                Some(mode) => Command::SetUnderline(mode),
+                None => return self.unknown(3),
+            },
+            b'a' => match Align::from_byte(digit(n)) {
+                Some(align) => Command::SetAlign(align),
+                None => return self.unknown(3),
+            },
+            b'M' => Command::SetFont(Font::from_byte(digit(n))),
+            b't' => match CodePage::from_byte(n) {
+                Some(page) => Command::SelectCodePage(page),
+                None => return self.unknown(3),
+            },
+            b'#' if n == b'#' => return self.vendor(),
+            _ => return self.unknown(2),
+        };
+        self.take(3, command)
+    }
+
+    fn bit_image(&mut self) -> Command<'a> {
+        let rest = &self.data[self.pos..];
+        let Some(&[m, n_l, n_h]) = rest.get(2..5) else {
+            return self.unknown(rest.len());
+        };
+        let Some(mode) = BitImageMode::from_byte(m) else {
+            return self.unknown(2);
+        };
+        let width = u16::from_le_bytes([n_l, n_h]);
+        let len = usize::from(width) * usize::from(mode.bytes_per_column());
+        match rest.get(5..5 + len) {
+            Some(data) => self.take(5 + len, Command::BitImage { mode, width, data }),
+            None => self.unknown(rest.len()),
+        }
+    }
+
+    fn slip_wait(&mut self) -> Command<'a> {
+        let rest = &self.data[self.pos..];
+        match rest.get(2..4) {
+            Some(&[minutes, delay]) => self.take(4, Command::SetSlipWait { minutes, delay }),
+            _ => self.unknown(rest.len()),
+        }
+    }
+
+    fn paper_sensors(&mut self) -> Command<'a> {
+        let rest = &self.data[self.pos..];
+        match rest.get(2..4) {
+            Some(&[b'3', n]) => self.take(4, Command::SetPaperSignalSensors(PaperSensors(n))),
+            Some(&[b'4', n]) => self.take(4, Command::SetPaperStopSensors(PaperSensors(n))),
+            Some(&[b'0', n]) => self.take(4, Command::SelectPrintStations(Stations(n))),
+            Some(&[b'1', n]) => self.take(4, Command::SelectCommandStations(Stations(n))),
+            Some(_) => self.unknown(2),
+            None => self.unknown(rest.len()),
+        }
+    }
+
+    fn sleep_timeout(&mut self) -> Command<'a> {
+        let rest = &self.data[self.pos..];
+        match rest.get(2..5) {
+            Some(&[n @ (0 | 1), t1, t2]) => {
+                let seconds = if n == 0 {
+                    0
+                } else {
+                    u16::from_le_bytes([t1, t2])
+                };
+                self.take(5, Command::SetSleepTimeout(seconds))
+            }
+            Some(_) => self.unknown(2),
+            None => self.unknown(rest.len()),
+        }
+    }
+
+    /// Vendor configuration commands of the form `ESC # # <name> <params>`.
+    fn vendor(&mut self) -> Command<'a> {
+        let rest = &self.data[self.pos..];
+        if rest.len() < 8 {
+            return self.unknown(rest.len());
+        }
+        let n = rest[7];
+        match &rest[3..7] {
+            b"SBDR" => match rest.get(7..11) {
+                Some(b) => {
+                    let baud = u32::from_le_bytes([b[0], b[1], b[2], b[3]]);
+                    self.take(11, Command::SetBaudRate(baud))
+                }
+                None => self.unknown(rest.len()),
+            },
+            b"STSP" => self.take(8, Command::SetMaxSpeed(n)),
+            b"SFFC" => self.take(8, Command::SetSoftwareFlowControl(n != 0)),
+            _ => self.unknown(3),
+        }
+    }
+
+    fn gs(&mut self) -> Command<'a> {
+        match self.peek(1) {
+            Some(b':') => return self.take(2, Command::ToggleMacroDefinition),
+            Some(b'^') => return self.execute_macro(),
+            Some(FF) => return self.take(2, Command::FeedToMark),
+            Some(b'(') if self.peek(2) == Some(b'F') => return self.mark_offset(),
+            Some(b'(') if self.peek(2) == Some(b'E') => return self.user_setting(),
+            Some(b'(') if self.peek(2) == Some(b'A') => return self.test_print(),
+            Some(b'(') if self.peek(2) == Some(b'H') => return self.process_id(),
+            Some(b'(' | b'8') if self.peek(2) == Some(b'L') => return self.graphics(),
+            Some(b'*') => return self.downloaded_image(),
+            _ => {}
+        }
+        let (Some(f), Some(n)) = (self.peek(1), self.peek(2)) else {
+            return self.unknown(self.remaining().len().min(2));
+        };
+        let command = match f {
+            b'V' if n == 0x41 || n == 0x42 => return self.feed_and_cut(n),
+            b'V' => match CutMode::from_byte(digit(n)) {
+                Some(mode) => Command::Cut(mode),
+                None => return self.unknown(3),
+            },
+            b'!' => Command::SetSize {
+                width: n >> 4,
+                height: n & 0x0F,
+            },
+            b'B' => Command::SetInvert(n & 0x01 != 0),
+            b'|' => match Density::from_byte(n) {
+                Some(level) => Command::SetDensity(level),
+                None => return self.unknown(3),
+            },
+            b'r' if n == 0x01 || n == b'1' => Command::QueryPaperStatus,
+            b'I' => match PrinterIdKind::from_byte(n) {
+                Some(kind) => Command::TransmitPrinterId(kind),
+                None => return self.unknown(3),
+            },
+            b'a' => Command::SetAutoStatusBack(AsbFlags(n)),
+            b'T' => match digit(n) {
+                0 | 1 => Command::ToLineStart {
+                    print: digit(n) == 1,
+                },
+                _ => return self.unknown(3),
+            },
+            b'/' => match BitImageScale::from_byte(digit(n)) {
+                Some(scale) => Command::PrintDownloadedImage(scale),
+                None => return self.unknown(3),
+            },
+            b'v' if n == b'0' => return self.raster(),
+            _ => return self.unknown(2),
+        };
+        self.take(3, command)
+    }
+
+    fn downloaded_image(&mut self) -> Command<'a> {
+        let rest = &self.data[self.pos..];
+        let Some(&[width, height]) = rest.get(2..4) else {
+            return self.unknown(rest.len());
+        };
+        let len = usize::from(width) * usize::from(height) * 8;
+        match rest.get(4..4 + len) {
+            Some(data) => self.take(
+                4 + len,
+                Command::DefineDownloadedImage {
+                    width,
+                    height,
+                    data,
+                },
+            ),
+            None => self.unknown(rest.len()),
+        }
+    }
+
+    fn feed_and_cut(&mut self, m: u8) -> Command<'a> {
+        let mode = if m == 0x41 {
+            CutMode::Full
+        } else {
+            CutMode::Partial
+        };
+        match self.peek(3) {
+            Some(feed) => self.take(4, Command::FeedAndCut { mode, feed }),
+            None => self.unknown(3),
+        }
+    }
+
+    /// User setting commands of the form `GS ( E pL pH fn <params>`.
+    fn user_setting(&mut self) -> Command<'a> {
+        let rest = &self.data[self.pos..];
+        let Some(&[p_l, p_h]) = rest.get(3..5) else {
+            return self.unknown(rest.len());
+        };
+        let len = 5 + usize::from(u16::from_le_bytes([p_l, p_h]));
+        let Some(params) = rest.get(5..len) else {
+            return self.unknown(rest.len());
+        };
+        let command = match params {
+            [0x01, b'I', b'N'] => Command::EnterUserSetting,
+            [0x02, b'O', b'U', b'T'] => Command::ExitUserSetting,
+            [0x03, switch, bits @ ..] if bits.len() == 8 => {
+                let mut mask = 0;
+                let mut value = 0;
+                for &bit in bits {
+                    mask <<= 1;
+                    value <<= 1;
+                    match bit {
+                        b'0' => mask |= 1,
+                        b'1' => {
+                            mask |= 1;
+                            value |= 1;
+                        }
+                        b'2' => {}
+                        _ => return self.unknown(len),
+                    }
+                }
+                Command::SetMemorySwitch {
+                    switch: *switch,
+                    mask,
+                    value,
+                }
+            }
+            [0x04, switch] => Command::TransmitMemorySwitch(*switch),
+            [0x05, a, n_l, n_h] => match CustomSetting::from_byte(*a) {
+                Some(setting) => Command::SetCustomValue {
+                    setting,
+                    value: u16::from_le_bytes([*n_l, *n_h]),
+                },
+                None => return self.unknown(len),
+            },
+            [0x06, a] => match CustomSetting::from_byte(*a) {
+                Some(setting) => Command::TransmitCustomValue(setting),
+                None => return self.unknown(len),
+            },
+            _ => return self.unknown(len),
+        };
+        self.take(len, command)
+    }
+
+    /// Graphics commands, `GS ( L pL pH <params>` and
+    /// `GS 8 L p1 p2 p3 p4 <params>`.
+    fn graphics(&mut self) -> Command<'a> {
+        let rest = &self.data[self.pos..];
+        let (start, len) = match rest.get(1) {
+            Some(b'(') => match rest.get(3..5) {
+                Some(&[p_l, p_h]) => (5, usize::from(u16::from_le_bytes([p_l, p_h]))),
+                _ => return self.unknown(rest.len()),
+            },
+            _ => match rest.get(3..7) {
+                Some(&[p1, p2, p3, p4]) => {
+                    let len = u32::from_le_bytes([p1, p2, p3, p4]);
+                    (7, usize::try_from(len).unwrap_or(usize::MAX))
+                }
+                _ => return self.unknown(rest.len()),
+            },
+        };
+        // `len` comes from the stream, so `start + len` may overflow.
+        let Some(params) = rest.get(start..).and_then(|rest| rest.get(..len)) else {
+            return self.unknown(rest.len());
+        };
+        let command = match params {
+            [0x30, 0x32 | 0x02] => Command::PrintGraphics,
+            [0x30, 0x70, 0x30, 0x01, 0x01, c, x_l, x_h, y_l, y_h, data @ ..] => {
+                let width = u16::from_le_bytes([*x_l, *x_h]);
+                let height = u16::from_le_bytes([*y_l, *y_h]);
+                let color = c.checked_sub(b'1').and_then(Color::from_byte);
+                match color {
+                    Some(color)
+                        if data.len() == usize::from(width.div_ceil(8)) * usize::from(height) =>
+                    {
+                        Command::StoreGraphics {
+                            color,
+                            width,
+                            height,
+                            data,
+                        }
+                    }
+                    _ => return self.unknown(start + len),
+                }
+            }
+            _ => return self.unknown(start + len),
+        };
+        self.take(start + len, command)
+    }
+
+    fn test_print(&mut self) -> Command<'a> {
+        let rest = &self.data[self.pos..];
+        match rest.get(3..7) {
+            Some(&[0x02, 0x00, n, m]) if digit(n) <= 2 => match TestPattern::from_byte(m) {
+                Some(pattern) => self.take(7, Command::TestPrint(pattern)),
+                None => self.unknown(7),
+            },
+            Some(_) => self.unknown(3),
+            None => self.unknown(rest.len()),
+        }
+    }
+
+    fn process_id(&mut self) -> Command<'a> {
+        let rest = &self.data[self.pos..];
+        match rest.get(3..11) {
+            Some(&[0x06, 0x00, 0x30, 0x30, ref digits @ ..])
+                if digits.iter().all(u8::is_ascii_digit) =>
+            {
+                let id = digits
+                    .iter()
+                    .fold(0, |id, &d| id * 10 + u16::from(d - b'0'));
+                self.take(11, Command::TransmitProcessId(id))
+            }
+            Some(_) => self.unknown(3),
+            None => self.unknown(rest.len()),
+        }
+    }
+
+    fn mark_offset(&mut self) -> Command<'a> {
+        let rest = &self.data[self.pos..];
+        let Some(&[p_l, p_h, a, m, n_l, n_h]) = rest.get(3..9) else {
+            return self.unknown(rest.len());
+        };
+        if [p_l, p_h] != [0x04, 0x00] {
+            return self.unknown(3);
+        }
+        let position = MarkPosition::from_byte(a);
+        let offset = i16::try_from(u16::from_le_bytes([n_l, n_h])).ok();
+        let command = match (position, offset, digit(m)) {
+            (Some(position), Some(n), 0) => Command::SetMarkOffset {
+                position,
+                offset: n,
+            },
+            (Some(position), Some(n), 1) => Command::SetMarkOffset {
+                position,
+                offset: -n,
+            },
+            _ => return self.unknown(9),
+        };
+        self.take(9, command)
+    }
+
+    fn execute_macro(&mut self) -> Command<'a> {
+        let rest = &self.data[self.pos..];
+        match rest.get(2..5) {
+            Some(&[times, wait, m]) => match MacroMode::from_byte(m) {
+                Some(mode) => self.take(5, Command::ExecuteMacro { times, wait, mode }),
+                None => self.unknown(5),
+            },
+            _ => self.unknown(rest.len()),
+        }
+    }
+
+    fn raster(&mut self) -> Command<'a> {
+        let rest = &self.data[self.pos..];
+        if rest.len() < 8 {
+            return self.unknown(rest.len());
+        }
+        let Some(scale) = BitImageScale::from_byte(digit(rest[3])) else {
+            return self.unknown(4);
+        };
+        let width_bytes = u16::from_le_bytes([rest[4], rest[5]]);
+        let height = u16::from_le_bytes([rest[6], rest[7]]);
+        let len = width_bytes as usize * height as usize;
+        match rest.get(8..8 + len) {
+            Some(data) => self.take(
+                8 + len,
+                Command::RasterImage {
+                    width: width_bytes.saturating_mul(8),
+                    height,
+                    scale,
+                    data,
+                },
+            ),
+            None => self.unknown(rest.len()),
+        }
+    }
+
+    fn dle(&mut self) -> Command<'a> {
+        let (Some(f), Some(n)) = (self.peek(1), self.peek(2)) else {
+            return self.unknown(self.remaining().len().min(2));
+        };
+        match (f, StatusRequest::from_byte(n)) {
+            (0x04, None) if n == 0x08 && self.peek(3) == Some(0x03) => {
+                self.take(4, Command::TransmitPeelerStatus)
+            }
+            (0x04, Some(request)) => self.take(3, Command::TransmitStatus(request)),
+            (0x04, None) => self.unknown(3),
+            (0x14, _) if self.remaining().starts_with(&command::CLEAR_BUFFERS) => {
+                self.take(command::CLEAR_BUFFERS.len(), Command::ClearBuffers)
+            }
+            _ => self.unknown(2),
+        }
+    }
+
+    /// Label commands of the form `FS ( L pL pH fn <params>`.
+    fn fs(&mut self) -> Command<'a> {
+        let rest = &self.data[self.pos..];
+        if rest.get(1) == Some(&b'g') {
+            return self.user_memory();
+        }
+        match rest[1..] {
+            [b'a', b'0', 0x00, ..] => return self.take(4, Command::ReadMicr),
+            [b'a', b'2', ..] => return self.take(3, Command::EjectCheck),
+            [b'b', 0x01, ..] => return self.take(3, Command::TransmitMicr),
+            _ => {}
+        }
+        if rest.get(1..3) != Some(b"(L") {
+            return self.unknown(rest.len().min(2));
+        }
+        let Some(&[p_l, p_h]) = rest.get(3..5) else {
+            return self.unknown(rest.len());
+        };
+        let len = 5 + usize::from(u16::from_le_bytes([p_l, p_h]));
+        let Some(params) = rest.get(5..len) else {
+            return self.unknown(rest.len());
+        };
+        let command = match params {
+            [0x43, 0x30 | 0x31] => Command::FeedToLabel,
+            [0x42, 0x30 | 0x31] => Command::FeedLabelToCut,
+            [0x44, m @ (0x30 | 0x31)] => Command::SetPeeler(*m == 0x31),
+            [0x21, b'0'] => Command::SetLabelLayout {
+                stock: LabelStock::Continuous,
+                length: 0,
+            },
+            [0x21, m, digits @ .., b';'] => {
+                let length = core::str::from_utf8(digits)
+                    .ok()
+                    .and_then(|digits| digits.parse().ok());
+                match (LabelStock::from_byte(*m), length) {
+                    (Some(stock), Some(length)) => Command::SetLabelLayout { stock, length },
+                    _ => return self.unknown(len),
+                }
+            }
+            _ => return self.unknown(len),
+        };
+        self.take(len, command)
+    }
+
+    /// NV user memory commands, `FS g 1` and `FS g 2`.
+    fn user_memory(&mut self) -> Command<'a> {
+        let rest = &self.data[self.pos..];
+        let Some(&[f, 0x30, a1, a2, a3, a4, n_l, n_h]) = rest.get(2..10) else {
+            return self.unknown(rest.len().min(3));
+        };
+        let address = u32::from_le_bytes([a1, a2, a3, a4]);
+        let len = u16::from_le_bytes([n_l, n_h]);
+        match f {
+            b'1' => match rest.get(10..10 + usize::from(len)) {
+                Some(data) => {
+                    self.take(10 + data.len(), Command::WriteUserMemory { address, data })
+                }
+                None => self.unknown(rest.len()),
+            },
+            b'2' => self.take(10, Command::ReadUserMemory { address, len }),
+            _ => self.unknown(3),
+        }
+    }
+
+    fn us(&mut self) -> Command<'a> {
+        match self.peek(1) {
+            Some(b'P') => match self.peek(2).and_then(PrintSpeed::from_byte) {
+                Some(speed) => self.take(3, Command::SetPrintSpeed(speed)),
+                None => self.unknown(self.remaining().len().min(3)),
+            },
+            Some(ESC) => {
+                let rest = &self.data[self.pos..];
+                match rest.get(..8) {
+                    Some([_, _, US, 0x80, 0x04, 0x05, 0x06, flag]) => {
+                        let on = *flag == 0x44;
+                        self.take(8, Command::SetBlackMark(on))
+                    }
+                    _ => self.unknown(2),
+                }
+            }
+            Some(_) => self.unknown(2),
+            None => self.unknown(1),
+        }
+    }
+}
+
+impl<'a> Iterator for Decoder<'a> {
+    type Item = Command<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let byte = self.peek(0)?;
+        Some(match byte {
+            NUL => self.take(1, Command::Wake),
+            LF => self.take(1, Command::LineFeed),
+            FF => self.take(1, Command::PrintAndFeedToMark),
+            CR => self.take(1, Command::CarriageReturn),
+            CAN => self.take(1, Command::CancelLine),
+            DLE => self.dle(),
+            ESC => self.esc(),
+            FS => self.fs(),
+            GS => self.gs(),
+            US => self.us(),
+            b if is_text(b) => self.text(),
+            _ => self.unknown(1),
+        })
+    }
+}
+
+/// Offset of the first unrecognised or truncated command in `data`.
+///
+/// Bytes outside commands that aren't valid UTF-8, such as code page text,
+/// are accepted.
+pub(crate) fn first_invalid(data: &[u8]) -> Option<usize> {
+    let mut decoder = Decoder::new(data);
+    loop {
+        let start = decoder.position();
+        if let Command::Unknown([DLE | ESC | FS | GS | US, ..]) = decoder.next()? {
+            return Some(start);
+        }
+    }
+}
+
+fn is_text(byte: u8) -> bool {
+    byte >= 0x20 && byte != 0x7F
+}
+
+/// Many parameters accept either a raw value or its ASCII digit.
+fn digit(n: u8) -> u8 {
+    if n.is_ascii_digit() {
+        n - b'0'
+    } else {
+        n
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Justification;
+    use std::vec::Vec;
+
+    #[test]
+    fn test_round_trip() {
+        let commands = [
+            Command::Initialize,
+            Command::SelectCodePage(CodePage::Wpc1252),
+            Command::SetCharset(Charset::Uk),
+            Command::SetCharset(Charset::Arabia),
+            Command::SetLineSpacing(24),
+            Command::DefaultLineSpacing,
+            Command::SetAlign(Align::Center),
+            Command::SetBold(true),
+            Command::Text("Total: 4.20"),
+            Command::LineFeed,
+            Command::CarriageReturn,
+            Command::Text("VOID"),
+            Command::CancelLine,
+            Command::ToLineStart { print: false },
+            Command::ToLineStart { print: true },
+            Command::SetUnderline(UnderlineMode::Double),
+            Command::SetFont(Font::FontB),
+            Command::SetFont(Font::FontC),
+            Command::SetFont(Font::Custom(0x61)),
+            Command::SetSize {
+                width: 1,
+                height: 2,
+            },
+            Command::SetInvert(true),
+            Command::SetUpsideDown(true),
+            Command::SetDensity(Density::Level5),
+            Command::SetPrintSpeed(PrintSpeed::Speed3),
+            Command::SetBaudRate(115_200),
+            Command::SetMaxSpeed(30),
+            Command::SetSoftwareFlowControl(true),
+            Command::SetBlackMark(false),
+            Command::SetMarkOffset {
+                position: MarkPosition::PrintStart,
+                offset: -120,
+            },
+            Command::FeedToMark,
+            Command::PrintAndFeedToMark,
+            Command::SetLabelLayout {
+                stock: LabelStock::BlackMark,
+                length: 1016,
+            },
+            Command::SetLabelLayout {
+                stock: LabelStock::Continuous,
+                length: 0,
+            },
+            Command::FeedToLabel,
+            Command::FeedLabelToCut,
+            Command::SetPeeler(true),
+            Command::SetPeeler(false),
+            Command::TransmitPeelerStatus,
+            Command::TestPrint(TestPattern::RollingPattern),
+            Command::TestPrint(TestPattern::HexDump),
+            Command::WriteUserMemory {
+                address: 16,
+                data: b"T-0042",
+            },
+            Command::ReadUserMemory {
+                address: 16,
+                len: 6,
+            },
+            Command::SetSleepTimeout(300),
+            Command::SetSleepTimeout(0),
+            Command::Wake,
+            Command::EnterUserSetting,
+            Command::SetMemorySwitch {
+                switch: 1,
+                mask: 0b0000_0110,
+                value: 0b0000_0100,
+            },
+            Command::TransmitMemorySwitch(1),
+            Command::SetCustomValue {
+                setting: CustomSetting::Density,
+                value: 65534,
+            },
+            Command::TransmitCustomValue(CustomSetting::PrintSpeed),
+            Command::ExitUserSetting,
+            Command::SetPaperSignalSensors(PaperSensors::END),
+            Command::SetPaperStopSensors(PaperSensors::NEAR_END),
+            Command::SelectPrintStations(Stations::RECEIPT | Stations::JOURNAL),
+            Command::SelectCommandStations(Stations::SLIP),
+            Command::SetSlipWait {
+                minutes: 2,
+                delay: 5,
+            },
+            Command::ReleasePaper,
+            Command::ReadMicr,
+            Command::EjectCheck,
+            Command::TransmitMicr,
+            Command::QueryPaperStatus,
+            Command::TransmitStatus(StatusRequest::Offline),
+            Command::ClearBuffers,
+            Command::TransmitPrinterId(PrinterIdKind::Firmware),
+            Command::TransmitProcessId(42),
+            Command::TransmitProcessId(9999),
+            Command::SetAutoStatusBack(AsbFlags::ONLINE | AsbFlags::PAPER),
+            Command::RasterImage {
+                width: 16,
+                height: 2,
+                scale: BitImageScale::Normal,
+                data: &[0xAA, 0x55, 0xFF, 0x00],
+            },
+            Command::RasterImage {
+                width: 8,
+                height: 1,
+                scale: BitImageScale::DoubleWidth,
+                data: &[0x3C],
+            },
+            Command::BitImage {
+                mode: BitImageMode::DoubleDensity24,
+                width: 2,
+                data: &[0x80, 0x00, 0x01, 0xFF, 0xFF, 0xFF],
+            },
+            Command::BitImage {
+                mode: BitImageMode::SingleDensity8,
+                width: 3,
+                data: &[0x18, 0x3C, 0x18],
+            },
+            Command::DefineDownloadedImage {
+                width: 1,
+                height: 1,
+                data: &[0x80, 0x40, 0x20, 0x10, 0x08, 0x04, 0x02, 0x01],
+            },
+            Command::PrintDownloadedImage(BitImageScale::Quadruple),
+            Command::SetColor(Color::Red),
+            Command::StoreGraphics {
+                color: Color::Black,
+                width: 12,
+                height: 2,
+                data: &[0xFF, 0xF0, 0x81, 0x10],
+            },
+            Command::StoreGraphics {
+                color: Color::Red,
+                width: 8,
+                height: 1,
+                data: &[0x3C],
+            },
+            Command::PrintGraphics,
+            Command::SetColor(Color::Black),
+            Command::ToggleMacroDefinition,
+            Command::ExecuteMacro {
+                times: 2,
+                wait: 5,
+                mode: MacroMode::WaitForButton,
+            },
+            Command::Feed(3),
+            Command::ReverseFeed(48),
+            Command::FeedUnits(160),
+            Command::FeedAndCut {
+                mode: CutMode::Full,
+                feed: 24,
+            },
+            Command::Cut(CutMode::PartialThreePoints),
+            Command::Cut(CutMode::Partial),
+        ];
+        let mut bytes = Vec::new();
+        for command in &commands {
+            bytes.extend(encode(command));
+        }
+        let decoded: Vec<_> = Decoder::new(&bytes).collect();
+        assert_eq!(decoded, commands);
+    }
+
+    #[test]
+    fn test_justification_decodes_as_align() {
+        let bytes = encode(&Command::SetJustification(Justification::Right));
+        let decoded: Vec<_> = Decoder::new(&bytes).collect();
+        assert_eq!(decoded, [Command::SetAlign(Align::Right)]);
+    }
+
+    #[test]
+    fn test_unknown_sequences() {
+        let bytes = [0x1B, 0x7A, b'A', 0x07, 0x1D, 0x56];
+        let decoded: Vec<_> = Decoder::new(&bytes).collect();
+        assert_eq!(
+            decoded,
+            [
+                Command::Unknown(&[0x1B, 0x7A]),
+                Command::Text("A"),
+                Command::Unknown(&[0x07]),
+                Command::Unknown(&[0x1D, 0x56]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_label_feeds() {
+        let bytes = b"\x1C(L\x02\x00C0\x1C(L\x02\x00B1\x1C(L\x02\x00A0";
+        let decoded: Vec<_> = Decoder::new(bytes).collect();
+        assert_eq!(
+            decoded,
+            [
+                Command::FeedToLabel,
+                Command::FeedLabelToCut,
+                Command::Unknown(b"\x1C(L\x02\x00A0"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_huge_graphics_length() {
+        let bytes = [0x1D, 0x38, 0x4C, 0xFF, 0xFF, 0xFF, 0xFF, 0x30, 0x70];
+        let decoded: Vec<_> = Decoder::new(&bytes).collect();
+        assert_eq!(decoded, [Command::Unknown(&bytes)]);
+    }
+
+    #[test]
+    fn test_ascii_digit_parameters() {
+        let bytes = [0x1B, 0x61, b'2', 0x1D, 0x56, b'1'];
+        let decoded: Vec<_> = Decoder::new(&bytes).collect();
+        assert_eq!(
+            decoded,
+            [
+                Command::SetAlign(Align::Right),
+                Command::Cut(CutMode::Partial)
+            ]
+        );
+    }
+
+    fn encode(command: &Command) -> Vec<u8> {
+        let mut buf = [0u8; 64];
+        let n = command.encode_to_slice(&mut buf).unwrap();
+        buf[..n].to_vec()
+    }
+}
@@ -0,0 +1,241 @@
+use super::{Align, Command, Delay, Features, Printer, PrinterError, UnderlineMode, Write};
+
+/// What to do when the profile says the printer lacks a [`Features`]
+/// command.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Fallback {
+    /// Return [`PrinterError::Unsupported`].
+    #[default]
+    Error,
+    /// Send nothing and carry on.
+    Skip,
+    /// Produce a similar result with commands the printer does have.
+    Emulate,
+}
+
+/// Per-feature [`Fallback`]s for printers whose profile lacks some
+/// [`Features`].
+///
+/// One receipt layout can then serve a mixed fleet: alignment and
+/// underlines are sent as commands where the profile allows, and emulated
+/// or dropped elsewhere.
+///
+/// ```
+/// # use escpos_embedded::{Align, Degradation, Fallback, Features, Printer, Profile, Recorder};
+/// let basic = Profile { features: Features::NONE, ..Profile::GENERIC_58MM };
+/// let mut printer = Printer::builder(Recorder::<64>::new())
+///     .profile(basic)
+///     .degradation(Degradation::new().align(Fallback::Emulate).underline(Fallback::Skip))
+///     .build()
+///     .unwrap();
+/// printer.set_align(Align::Right).unwrap();
+/// printer.write_line("TOTAL 4.20").unwrap();
+/// assert!(printer.transport().as_bytes().starts_with(b"      "));
+/// ```
+///
+/// Emulation only applies to [`Printer::write_line`], which knows where a
+/// line starts and ends:
+///
+/// - alignment pads the line with spaces to the
+///   [columns](Printer::columns) of the current font and size;
+/// - underlines are printed as a rule of `-` (or `=` for
+///   [`Double`](UnderlineMode::Double)) on the next line.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Degradation {
+    align: Fallback,
+    underline: Fallback,
+}
+
+impl Degradation {
+    /// Return errors for every missing feature.
+    pub const fn new() -> Self {
+        Self {
+            align: Fallback::Error,
+            underline: Fallback::Error,
+        }
+    }
+
+    /// Use `fallback` for every missing feature.
+    pub const fn all(fallback: Fallback) -> Self {
+        Self {
+            align: fallback,
+            underline: fallback,
+        }
+    }
+
+    /// Handle missing [`ALIGN`](Features::ALIGN) with `fallback`.
+    pub const fn align(mut self, fallback: Fallback) -> Self {
+        self.align = fallback;
+        self
+    }
+
+    /// Handle missing [`UNDERLINE`](Features::UNDERLINE) with `fallback`.
+    pub const fn underline(mut self, fallback: Fallback) -> Self {
+        self.underline = fallback;
+        self
+    }
+}
+
+/// Styles emulated for [`Printer::write_line`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Emulation {
+    pub(crate) align: Align,
+    pub(crate) underline: UnderlineMode,
+}
+
+impl Emulation {
+    pub(crate) const fn new() -> Self {
+        Self {
+            align: Align::Left,
+            underline: UnderlineMode::None,
+        }
+    }
+}
+
+impl<T: Write, P: Delay> Printer<T, P> {
+    /// The fallback to use for `feature`, or `None` if the profile has it.
+    ///
+    /// Returns [`PrinterError::Unsupported`] for [`Fallback::Error`].
+    pub(crate) fn fallback(
+        &self,
+        feature: Features,
+        command: &'static str,
+    ) -> Result<Option<Fallback>, PrinterError<<T as Write>::Error>> {
+        if self.profile.features.contains(feature) {
+            return Ok(None);
+        }
+        let fallback = match feature {
+            Features::ALIGN => self.degradation.align,
+            Features::UNDERLINE => self.degradation.underline,
+            _ => Fallback::Error,
+        };
+        match fallback {
+            Fallback::Error => Err(PrinterError::Unsupported { command }),
+            fallback => Ok(Some(fallback)),
+        }
+    }
+
+    /// Leading spaces for a line of `len` characters under the emulated
+    /// alignment.
+    pub(crate) fn emulated_indent(&self, len: usize) -> usize {
+        let spare = self.columns().saturating_sub(len);
+        match self.emulation.align {
+            Align::Left => 0,
+            Align::Center => spare / 2,
+            Align::Right => spare,
+        }
+    }
+
+    /// Write `byte`, an ASCII character, `count` times.
+    pub(crate) fn write_repeated(
+        &mut self,
+        byte: u8,
+        mut count: usize,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        let buf = [byte; 32];
+        while count > 0 {
+            let n = count.min(buf.len());
+            let text = core::str::from_utf8(&buf[..n]).unwrap_or_default();
+            self.send(&Command::Text(text))?;
+            count -= n;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockTransport;
+    use crate::{PaperWidth, Profile};
+
+    fn printer(degradation: Degradation) -> Printer<MockTransport> {
+        let mut printer = Printer::new(MockTransport::new());
+        printer.profile.features = Features::NONE;
+        printer.paper_width = PaperWidth::Dots(120);
+        printer.degradation = degradation;
+        printer
+    }
+
+    #[test]
+    fn test_error_by_default() {
+        let mut printer = printer(Degradation::new());
+        assert_eq!(
+            printer.set_align(Align::Center),
+            Err(PrinterError::Unsupported {
+                command: "SetAlign"
+            })
+        );
+        assert_eq!(
+            printer.set_underline(UnderlineMode::Single),
+            Err(PrinterError::Unsupported {
+                command: "SetUnderline"
+            })
+        );
+        assert!(printer.transport.written().is_empty());
+    }
+
+    #[test]
+    fn test_skip() {
+        let mut printer = printer(Degradation::all(Fallback::Skip));
+        printer.set_align(Align::Right).unwrap();
+        printer.set_underline(UnderlineMode::Single).unwrap();
+        printer.write_line("ab").unwrap();
+        assert_eq!(printer.transport.written(), b"ab\n");
+    }
+
+    #[test]
+    fn test_emulate() {
+        // 120 dots is 10 columns of Font A.
+        let mut printer = printer(Degradation::all(Fallback::Emulate));
+        printer.set_align(Align::Center).unwrap();
+        printer.set_underline(UnderlineMode::Double).unwrap();
+        printer.write_line("abcd").unwrap();
+        printer.set_align(Align::Right).unwrap();
+        printer.set_underline(UnderlineMode::None).unwrap();
+        printer.write_line("xy").unwrap();
+        printer.write_line("far too long").unwrap();
+        assert_eq!(
+            printer.transport.written(),
+            b"   abcd\n   ====\n        xy\nfar too long\n"
+        );
+
+        printer.initialize().unwrap();
+        printer.transport.take_written();
+        printer.write_line("ab").unwrap();
+        assert_eq!(printer.transport.written(), b"ab\n");
+    }
+
+    #[test]
+    fn test_emulate_print_lines() {
+        let lines = ["abcd", "xy"];
+        let mut batched = printer(Degradation::all(Fallback::Emulate));
+        batched.set_align(Align::Center).unwrap();
+        batched.set_underline(UnderlineMode::Single).unwrap();
+        batched.print_lines(lines).unwrap();
+        let mut expected = printer(Degradation::all(Fallback::Emulate));
+        expected.set_align(Align::Center).unwrap();
+        expected.set_underline(UnderlineMode::Single).unwrap();
+        for line in lines {
+            expected.write_line(line).unwrap();
+        }
+        assert_eq!(batched.transport.written(), expected.transport.written());
+        assert_eq!(
+            batched.transport.written(),
+            b"   abcd\n   ----\n    xy\n    --\n"
+        );
+    }
+
+    #[test]
+    fn test_supported_commands_are_sent() {
+        let mut printer = Printer::new(MockTransport::new());
+        printer.degradation = Degradation::all(Fallback::Emulate);
+        assert_eq!(printer.profile().features, Profile::GENERIC.features);
+        printer.set_align(Align::Center).unwrap();
+        printer.write_line("ab").unwrap();
+        assert_eq!(printer.transport.written(), b"\x1B\x61\x01ab\n");
+    }
+}
@@ -0,0 +1,248 @@
+use core::convert::Infallible;
+use std::string::String;
+use std::vec::Vec;
+
+use serde::{Deserialize, Serialize};
+
+use super::command::CountingWriter;
+use super::{Align, CutMode, Delay, Printer, PrinterError, UnderlineMode, Write};
+
+/// A receipt described as data, for rendering on any printer.
+///
+/// Lets a backend send receipts to thin clients that only deserialize and
+/// render them. In JSON a document is an object with an `elements` array;
+/// each element is an object whose `type` selects one of the [`Element`]
+/// variants in snake case, with the variant's fields alongside:
+///
+/// ```json
+/// {
+///   "elements": [
+///     { "type": "text", "text": "CAFE", "bold": true, "align": "Center", "width": 2, "height": 2 },
+///     { "type": "row", "left": "Coffee", "right": "3.50" },
+///     { "type": "rule" },
+///     { "type": "table", "rows": [["Tea", "2"], ["Cake", "1"]] },
+///     { "type": "markdown", "text": "**Thank you!**" },
+///     { "type": "feed", "lines": 3 },
+///     { "type": "cut", "mode": "Partial" }
+///   ]
+/// }
+/// ```
+///
+/// Omitted optional fields keep the printer's defaults.
+///
+/// ```
+/// # use escpos_embedded::{Document, Printer, Recorder};
+/// let doc = Document::from_json(r#"{"elements": [{"type": "text", "text": "Hi"}]}"#).unwrap();
+/// let mut printer = Printer::new(Recorder::<64>::new());
+/// doc.render(&mut printer).unwrap();
+/// assert_eq!(printer.transport().as_bytes(), b"Hi\n");
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Document {
+    /// Content, printed in order.
+    pub elements: Vec<Element>,
+}
+
+/// One part of a [`Document`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Element {
+    /// A line of text with optional styling, reset afterwards.
+    Text {
+        /// Text to print; it wraps on the printer if too long.
+        text: String,
+        /// Print in bold.
+        #[serde(default)]
+        bold: bool,
+        /// Underline the text.
+        #[serde(default)]
+        underline: bool,
+        /// Alignment, left if omitted.
+        #[serde(default)]
+        align: Option<Align>,
+        /// Character width multiplier, 1–8.
+        #[serde(default)]
+        width: Option<u8>,
+        /// Character height multiplier, 1–8.
+        #[serde(default)]
+        height: Option<u8>,
+    },
+    /// Two texts at either end of a line, e.g. an item and its price.
+    Row {
+        /// Text on the left, shortened if both don't fit.
+        left: String,
+        /// Text on the right.
+        right: String,
+    },
+    /// A dashed line across the paper.
+    Rule,
+    /// Rows of cells; see [`Printer::print_table`].
+    Table {
+        /// Cells, row by row.
+        rows: Vec<Vec<String>>,
+    },
+    /// Markdown text; see [`Printer::print_markdown`].
+    Markdown {
+        /// Markdown source.
+        text: String,
+    },
+    /// Blank lines.
+    Feed {
+        /// Number of lines to feed.
+        lines: u16,
+    },
+    /// Cut the paper, fully if no mode is given.
+    Cut {
+        /// Cut to perform.
+        #[serde(default)]
+        mode: Option<CutMode>,
+    },
+}
+
+impl Document {
+    /// Parse a document from JSON.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Serialize the document to JSON.
+    pub fn to_json(&self) -> String {
+        // Can't fail: every field serializes to a JSON value.
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Number of bytes [`render`](Document::render) would send to
+    /// `printer`, given its current settings.
+    ///
+    /// Nothing is sent. Combine with the link speed to warn about slow
+    /// receipts, as [`Job::transmit_ms`](crate::Job::transmit_ms) does for
+    /// jobs.
+    pub fn estimated_bytes<T: Write, P: Delay>(
+        &self,
+        printer: &Printer<T, P>,
+    ) -> Result<usize, PrinterError<Infallible>> {
+        let mut counter = Printer::new(CountingWriter(0));
+        counter.copy_settings(printer);
+        self.render(&mut counter)?;
+        Ok(counter.transport.0)
+    }
+
+    /// Print the document.
+    pub fn render<T: Write, P: Delay>(
+        &self,
+        printer: &mut Printer<T, P>,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        for element in &self.elements {
+            element.render(printer)?;
+        }
+        Ok(())
+    }
+}
+
+impl Element {
+    fn render<T: Write, P: Delay>(
+        &self,
+        printer: &mut Printer<T, P>,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        match self {
+            Element::Text {
+                text,
+                bold,
+                underline,
+                align,
+                width,
+                height,
+            } => {
+                let sized = width.is_some() || height.is_some();
+                if *bold {
+                    printer.set_bold(true)?;
+                }
+                if *underline {
+                    printer.set_underline(UnderlineMode::Single)?;
+                }
+                if let Some(align) = align {
+                    printer.set_align(*align)?;
+                }
+                if sized {
+                    printer.try_set_size(width.unwrap_or(1), height.unwrap_or(1))?;
+                }
+                printer.write_line(text)?;
+                if sized {
+                    printer.set_size(0, 0)?;
+                }
+                if align.is_some() {
+                    printer.set_align(Align::Left)?;
+                }
+                if *underline {
+                    printer.set_underline(UnderlineMode::None)?;
+                }
+                if *bold {
+                    printer.set_bold(false)?;
+                }
+                Ok(())
+            }
+            Element::Row { left, right } => {
+                let columns = printer.columns();
+                let right_len = right.chars().count();
+                let room = columns.saturating_sub(right_len + 1);
+                let left: String = left.chars().take(room).collect();
+                let gap = columns.saturating_sub(left.chars().count() + right_len);
+                let mut line = left;
+                line.extend(core::iter::repeat_n(' ', gap.max(1)));
+                line.push_str(right);
+                printer.write_line(&line)
+            }
+            Element::Rule => printer.write_line(&"-".repeat(printer.columns())),
+            Element::Table { rows } => printer.print_table(rows),
+            Element::Markdown { text } => printer.print_markdown(text),
+            Element::Feed { lines } => printer.feed(*lines),
+            Element::Cut { mode } => printer.cut(mode.unwrap_or(CutMode::Full)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockTransport;
+    use crate::PaperWidth;
+
+    #[test]
+    fn test_json_round_trip() {
+        let json = r#"{"elements": [
+            {"type": "text", "text": "Hi", "bold": true, "width": 2},
+            {"type": "row", "left": "Tea", "right": "2.00"},
+            {"type": "cut"}
+        ]}"#;
+        let doc = Document::from_json(json).unwrap();
+        assert_eq!(
+            doc.elements[1],
+            Element::Row {
+                left: "Tea".into(),
+                right: "2.00".into()
+            }
+        );
+        assert_eq!(Document::from_json(&doc.to_json()).unwrap(), doc);
+        assert!(Document::from_json(r#"{"elements": [{"type": "logo"}]}"#).is_err());
+    }
+
+    #[test]
+    fn test_render() {
+        let doc = Document::from_json(
+            r#"{"elements": [
+                {"type": "text", "text": "Hi", "bold": true},
+                {"type": "row", "left": "Cappuccino", "right": "3.50"},
+                {"type": "cut", "mode": "Partial"}
+            ]}"#,
+        )
+        .unwrap();
+        let mut printer = Printer::builder(MockTransport::new())
+            .paper_width(PaperWidth::Dots(144))
+            .build()
+            .unwrap();
+        doc.render(&mut printer).unwrap();
+        let expected: &[u8] = b"\x1BE\x01Hi\n\x1BE\x00Cappucc 3.50\n\x1DV\x01";
+        assert_eq!(printer.transport().written(), expected);
+        assert_eq!(doc.estimated_bytes(&printer), Ok(expected.len()));
+    }
+}
@@ -0,0 +1,285 @@
+use std::string::String;
+use std::vec::Vec;
+
+use super::{Align, Command, CutMode, Decoder, Font, PaperWidth, UnderlineMode, Write};
+
+#[derive(Copy, Clone)]
+struct Cell {
+    ch: char,
+    bold: bool,
+    underline: bool,
+    width: usize,
+}
+
+/// Transport that renders an ASCII approximation of the printed receipt.
+///
+/// Bytes written to the emulator are decoded with [`Decoder`] and laid out
+/// on a fixed number of columns when [`render`](TextEmulator::render) is
+/// called:
+///
+/// - text is word-wrapped and aligned according to `ESC a`;
+/// - bold runs are wrapped in `*` and underlined runs in `_` (markers don't
+///   count towards the line width);
+/// - characters printed at `n`x width are followed by `n - 1` spaces;
+/// - cuts are drawn as a line of dashes labelled `CUT` or `PARTIAL CUT`;
+/// - raster images are shown as an `[image WxH]` placeholder.
+///
+/// Trailing whitespace is trimmed from every line so that receipts can be
+/// compared against string literals in tests.
+pub struct TextEmulator {
+    columns: usize,
+    bytes: Vec<u8>,
+}
+
+impl TextEmulator {
+    /// Create an emulator for paper holding `columns` characters per line.
+    pub fn new(columns: usize) -> Self {
+        Self {
+            columns,
+            bytes: Vec::new(),
+        }
+    }
+
+    /// Create an emulator as wide as `paper` in Font A.
+    pub fn for_paper(paper: PaperWidth) -> Self {
+        Self::new(paper.columns(Font::FontA).into())
+    }
+
+    /// Raw bytes received so far.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Render everything received so far.
+    pub fn render(&self) -> String {
+        let mut layout = Layout::new(self.columns);
+        for command in Decoder::new(&self.bytes) {
+            layout.apply(&command);
+        }
+        layout.finish()
+    }
+}
+
+impl Write for TextEmulator {
+    type Error = core::convert::Infallible;
+
+    fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        self.bytes.extend_from_slice(data);
+        Ok(())
+    }
+}
+
+struct Layout {
+    columns: usize,
+    lines: Vec<String>,
+    line: Vec<Cell>,
+    line_align: Align,
+    align: Align,
+    bold: bool,
+    underline: bool,
+    char_width: usize,
+}
+
+impl Layout {
+    fn new(columns: usize) -> Self {
+        Self {
+            columns,
+            lines: Vec::new(),
+            line: Vec::new(),
+            line_align: Align::Left,
+            align: Align::Left,
+            bold: false,
+            underline: false,
+            char_width: 1,
+        }
+    }
+
+    fn apply(&mut self, command: &Command) {
+        match *command {
+            Command::Text(text) => text.chars().for_each(|ch| self.push(ch)),
+            Command::LineFeed | Command::PrintAndFeedToMark => self.flush(),
+            Command::Feed(lines) => (0..lines).for_each(|_| self.flush()),
+            Command::ToLineStart { print: false } => self.line.clear(),
+            // Feeds of a few dots don't make a text line of their own.
+            Command::FeedUnits(_) if !self.line.is_empty() => self.flush(),
+            Command::Cut(mode) | Command::FeedAndCut { mode, .. } => {
+                if !self.line.is_empty() {
+                    self.flush();
+                }
+                let label = match mode {
+                    CutMode::Full => " CUT ",
+                    CutMode::Partial | CutMode::PartialThreePoints => " PARTIAL CUT ",
+                };
+                self.lines.push(self.banner(label));
+            }
+            Command::Initialize => {
+                self.bold = false;
+                self.underline = false;
+                self.char_width = 1;
+                self.set_align(Align::Left);
+            }
+            Command::SetBold(on) => self.bold = on,
+            Command::SetUnderline(mode) => self.underline = mode != UnderlineMode::None,
+            Command::SetAlign(align) => self.set_align(align),
+            Command::SetJustification(mode) => self.set_align(match mode {
+                crate::Justification::Left => Align::Left,
+                crate::Justification::Center => Align::Center,
+                crate::Justification::Right => Align::Right,
+            }),
+            Command::SetSize { width, .. } => self.char_width = width.min(7) as usize + 1,
+            Command::RasterImage { width, height, .. } => {
+                if !self.line.is_empty() {
+                    self.flush();
+                }
+                let label = std::format!("[image {}x{}]", width, height);
+                let pad = self.columns.saturating_sub(label.len()) / 2;
+                self.lines.push(std::format!("{:pad$}{}", "", label));
+            }
+            _ => {}
+        }
+    }
+
+    fn set_align(&mut self, align: Align) {
+        self.align = align;
+        // ESC a only takes effect at the beginning of a line.
+        if self.line.is_empty() {
+            self.line_align = align;
+        }
+    }
+
+    fn banner(&self, label: &str) -> String {
+        let dashes = self.columns.saturating_sub(label.len());
+        let left = dashes / 2;
+        let mut out = String::new();
+        out.extend(core::iter::repeat_n('-', left));
+        out.push_str(label);
+        out.extend(core::iter::repeat_n('-', dashes - left));
+        out
+    }
+
+    fn width(cells: &[Cell]) -> usize {
+        cells.iter().map(|c| c.width).sum()
+    }
+
+    fn push(&mut self, ch: char) {
+        let cell = Cell {
+            ch,
+            bold: self.bold,
+            underline: self.underline,
+            width: self.char_width,
+        };
+        if Self::width(&self.line) + cell.width <= self.columns {
+            self.line.push(cell);
+            return;
+        }
+        if ch == ' ' {
+            self.flush();
+            return;
+        }
+        // Wrap at the last space on the line, if there is one.
+        let carry = match self.line.iter().rposition(|c| c.ch == ' ') {
+            Some(space) => {
+                let carry = self.line.split_off(space + 1);
+                self.line.pop();
+                carry
+            }
+            None => Vec::new(),
+        };
+        self.flush();
+        self.line = carry;
+        self.push(ch);
+    }
+
+    fn flush(&mut self) {
+        while self.line.last().is_some_and(|c| c.ch == ' ') {
+            self.line.pop();
+        }
+        let pad = self.columns.saturating_sub(Self::width(&self.line));
+        let indent = match self.line_align {
+            Align::Left => 0,
+            Align::Center => pad / 2,
+            Align::Right => pad,
+        };
+        let mut out = String::new();
+        out.extend(core::iter::repeat_n(' ', indent));
+        let (mut bold, mut underline) = (false, false);
+        for cell in &self.line {
+            if cell.underline != underline {
+                out.push('_');
+                underline = cell.underline;
+            }
+            if cell.bold != bold {
+                out.push('*');
+                bold = cell.bold;
+            }
+            out.push(cell.ch);
+            out.extend(core::iter::repeat_n(' ', cell.width - 1));
+        }
+        if bold {
+            out.push('*');
+        }
+        if underline {
+            out.push('_');
+        }
+        self.lines.push(String::from(out.trim_end()));
+        self.line.clear();
+        self.line_align = self.align;
+    }
+
+    fn finish(mut self) -> String {
+        if !self.line.is_empty() {
+            self.flush();
+        }
+        self.lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Printer;
+
+    #[test]
+    fn test_render_receipt() {
+        let mut printer = Printer::new(TextEmulator::new(16));
+        printer.set_align(Align::Center).unwrap();
+        printer.set_bold(true).unwrap();
+        printer.write_line("SHOP").unwrap();
+        printer.set_bold(false).unwrap();
+        printer.set_align(Align::Right).unwrap();
+        printer.write_line("4.20").unwrap();
+        printer.set_align(Align::Left).unwrap();
+        printer.write("Thank you for shopping").unwrap();
+        printer.feed(1).unwrap();
+        printer.cut(CutMode::Full).unwrap();
+        let expected = "      *SHOP*\n            4.20\nThank you for\nshopping\n----- CUT ------";
+        assert_eq!(printer.into_inner().render(), expected);
+    }
+
+    #[test]
+    fn test_render_wide_and_underline() {
+        let mut printer = Printer::new(TextEmulator::new(8));
+        printer.set_underline(UnderlineMode::Single).unwrap();
+        printer.write("ab").unwrap();
+        printer.set_underline(UnderlineMode::None).unwrap();
+        printer.set_size(1, 0).unwrap();
+        printer.write_line("cdef").unwrap();
+        assert_eq!(printer.into_inner().render(), "_ab_c d e\nf");
+    }
+
+    #[test]
+    fn test_hard_wrap_long_word() {
+        let mut printer = Printer::new(TextEmulator::new(4));
+        printer.write_line("abcdefg").unwrap();
+        assert_eq!(printer.into_inner().render(), "abcd\nefg");
+    }
+
+    #[test]
+    fn test_to_line_start_discards_line() {
+        let mut printer = Printer::new(TextEmulator::new(16));
+        printer.write("Total 1234").unwrap();
+        printer.to_line_start().unwrap();
+        printer.write_line("Total 12.34").unwrap();
+        assert_eq!(printer.into_inner().render(), "Total 12.34");
+    }
+}
@@ -0,0 +1,244 @@
+use core::fmt;
+use core::ops::RangeInclusive;
+
+use super::Status;
+
+/// Errors returned by [`Printer`](crate::Printer) operations.
+///
+/// `E` is the transport's error type. Every variant records the name of the
+/// command that failed (see [`Command::name`](crate::Command::name)).
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PrinterError<E> {
+    /// The transport failed while sending or receiving `command`.
+    Transport {
+        /// Command being processed.
+        command: &'static str,
+        /// Underlying transport error.
+        error: E,
+    },
+    /// A parameter was outside the range accepted by `command`.
+    InvalidParameter {
+        /// Command being built.
+        command: &'static str,
+        /// Value that was rejected.
+        value: i64,
+        /// Accepted values.
+        range: RangeInclusive<i64>,
+    },
+    /// `command` isn't supported by the printer.
+    Unsupported {
+        /// Command that was refused.
+        command: &'static str,
+    },
+    /// Image data doesn't match the image dimensions.
+    ImageSizeMismatch {
+        /// Command being built.
+        command: &'static str,
+        /// Number of bytes implied by the dimensions.
+        expected: usize,
+        /// Number of bytes supplied.
+        actual: usize,
+    },
+    /// An image is too large for its data to be addressed on this target.
+    ///
+    /// Only possible where `usize` is narrower than 32 bits.
+    ImageTooLarge {
+        /// Command being built.
+        command: &'static str,
+        /// Image width in pixels.
+        width: u16,
+        /// Image height in pixels.
+        height: u16,
+    },
+    /// `command` couldn't be encoded, e.g. because an output buffer was full.
+    Encode {
+        /// Command being encoded.
+        command: &'static str,
+    },
+    /// `command` was cancelled before it finished.
+    Cancelled {
+        /// Command that was cancelled.
+        command: &'static str,
+    },
+    /// The printer's reply to `command` was missing or malformed.
+    InvalidResponse {
+        /// Command that expected a reply.
+        command: &'static str,
+    },
+    /// Data passed through to the printer isn't a valid command stream.
+    InvalidData {
+        /// Command being sent.
+        command: &'static str,
+        /// Offset of the first invalid byte.
+        offset: usize,
+    },
+    /// The printer reported a status that prevents printing.
+    NotReady {
+        /// Command that checked the status.
+        command: &'static str,
+        /// Status reported by the printer.
+        status: Status,
+    },
+}
+
+impl<E> PrinterError<E> {
+    /// Name of the command that failed.
+    pub fn command(&self) -> &'static str {
+        match *self {
+            PrinterError::Transport { command, .. }
+            | PrinterError::InvalidParameter { command, .. }
+            | PrinterError::Unsupported { command }
+            | PrinterError::ImageSizeMismatch { command, .. }
+            | PrinterError::ImageTooLarge { command, .. }
+            | PrinterError::Encode { command }
+            | PrinterError::Cancelled { command }
+            | PrinterError::InvalidResponse { command }
+            | PrinterError::InvalidData { command, .. }
+            | PrinterError::NotReady { command, .. } => command,
+        }
+    }
+
+    /// The transport error, if this is a [`PrinterError::Transport`].
+    pub fn transport_error(&self) -> Option<&E> {
+        match self {
+            PrinterError::Transport { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Convert the transport error type, keeping the command context.
+    pub fn map_transport<F>(self, f: impl FnOnce(E) -> F) -> PrinterError<F> {
+        match self {
+            PrinterError::Transport { command, error } => PrinterError::Transport {
+                command,
+                error: f(error),
+            },
+            PrinterError::InvalidParameter {
+                command,
+                value,
+                range,
+            } => PrinterError::InvalidParameter {
+                command,
+                value,
+                range,
+            },
+            PrinterError::Unsupported { command } => PrinterError::Unsupported { command },
+            PrinterError::ImageSizeMismatch {
+                command,
+                expected,
+                actual,
+            } => PrinterError::ImageSizeMismatch {
+                command,
+                expected,
+                actual,
+            },
+            PrinterError::ImageTooLarge {
+                command,
+                width,
+                height,
+            } => PrinterError::ImageTooLarge {
+                command,
+                width,
+                height,
+            },
+            PrinterError::Encode { command } => PrinterError::Encode { command },
+            PrinterError::Cancelled { command } => PrinterError::Cancelled { command },
+            PrinterError::InvalidResponse { command } => PrinterError::InvalidResponse { command },
+            PrinterError::InvalidData { command, offset } => {
+                PrinterError::InvalidData { command, offset }
+            }
+            PrinterError::NotReady { command, status } => {
+                PrinterError::NotReady { command, status }
+            }
+        }
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for PrinterError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrinterError::Transport { command, error } => {
+                write!(f, "{}: transport error: {}", command, error)
+            }
+            PrinterError::InvalidParameter {
+                command,
+                value,
+                range,
+            } => write!(
+                f,
+                "{}: parameter {} outside {}..={}",
+                command,
+                value,
+                range.start(),
+                range.end()
+            ),
+            PrinterError::Unsupported { command } => write!(f, "{}: unsupported", command),
+            PrinterError::ImageSizeMismatch {
+                command,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{}: expected {} bytes of image data, got {}",
+                command, expected, actual
+            ),
+            PrinterError::ImageTooLarge {
+                command,
+                width,
+                height,
+            } => write!(f, "{}: {}x{} image is too large", command, width, height),
+            PrinterError::Encode { command } => write!(f, "{}: encoding failed", command),
+            PrinterError::Cancelled { command } => write!(f, "{}: cancelled", command),
+            PrinterError::InvalidResponse { command } => {
+                write!(f, "{}: invalid response from printer", command)
+            }
+            PrinterError::InvalidData { command, offset } => {
+                write!(f, "{}: invalid data at byte {}", command, offset)
+            }
+            PrinterError::NotReady { command, status } => {
+                write!(f, "{}: printer not ready ({:?})", command, status)
+            }
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> core::error::Error for PrinterError<E> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+
+    #[test]
+    fn test_display() {
+        let err: PrinterError<&str> = PrinterError::Transport {
+            command: "Cut",
+            error: "timeout",
+        };
+        assert_eq!(err.to_string(), "Cut: transport error: timeout");
+        let err: PrinterError<&str> = PrinterError::InvalidParameter {
+            command: "SetSize",
+            value: 9,
+            range: 1..=8,
+        };
+        assert_eq!(err.to_string(), "SetSize: parameter 9 outside 1..=8");
+        let err: PrinterError<&str> = PrinterError::ImageTooLarge {
+            command: "RasterImage",
+            width: 640,
+            height: 4000,
+        };
+        assert_eq!(err.to_string(), "RasterImage: 640x4000 image is too large");
+    }
+
+    #[test]
+    fn test_map_transport() {
+        let err = PrinterError::Transport {
+            command: "Feed",
+            error: 5u8,
+        };
+        let mapped = err.map_transport(u32::from);
+        assert_eq!(mapped.transport_error(), Some(&5u32));
+        assert_eq!(mapped.command(), "Feed");
+    }
+}
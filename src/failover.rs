@@ -0,0 +1,186 @@
+use core::fmt;
+
+use super::{Delay, Job, Printer, PrinterError, Read, Write};
+
+/// Which printer of a [`Failover`] pair printed a job.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Destination {
+    /// The primary printer.
+    Primary,
+    /// The backup printer, after the primary failed.
+    Backup,
+}
+
+/// Error from [`Failover`] when neither printer printed the job.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FailoverError<A, B> {
+    /// Why the primary printer failed.
+    pub primary: PrinterError<A>,
+    /// Why the backup printer failed.
+    pub backup: PrinterError<B>,
+}
+
+impl<A: fmt::Display, B: fmt::Display> fmt::Display for FailoverError<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "primary: {}; backup: {}", self.primary, self.backup)
+    }
+}
+
+impl<A, B> core::error::Error for FailoverError<A, B>
+where
+    A: fmt::Debug + fmt::Display,
+    B: fmt::Debug + fmt::Display,
+{
+}
+
+/// A primary printer with a backup that takes over when it fails.
+///
+/// Jobs go to the primary printer. If sending fails, the whole job is sent
+/// again to the backup, so receipts still come out when the primary jams or
+/// drops off the network:
+///
+/// ```
+/// # use escpos_embedded::{Destination, Failover, Job, Printer, Write};
+/// # fn print<A: Write, B: Write>(front: Printer<A>, back_office: Printer<B>) {
+/// let mut printers = Failover::new(front, back_office);
+/// let mut receipt = Job::<256>::new();
+/// receipt.write_line("Thank you!").unwrap();
+/// match printers.submit(&mut receipt) {
+///     Ok(Destination::Primary) => {}
+///     Ok(Destination::Backup) => { /* tell the customer where to collect it */ }
+///     Err(_) => { /* neither printer is working */ }
+/// }
+/// # }
+/// ```
+///
+/// A transport failure part-way through leaves a partial receipt on the
+/// primary printer. Use [`submit_checked`](Failover::submit_checked) to
+/// fail over before anything is sent when the primary reports itself not
+/// ready.
+pub struct Failover<A: Write, B: Write, P: Delay = (), Q: Delay = ()> {
+    primary: Printer<A, P>,
+    backup: Printer<B, Q>,
+}
+
+impl<A: Write, B: Write, P: Delay, Q: Delay> Failover<A, B, P, Q> {
+    /// Pair `primary` with `backup`.
+    pub fn new(primary: Printer<A, P>, backup: Printer<B, Q>) -> Self {
+        Self { primary, backup }
+    }
+
+    /// Borrow the primary printer.
+    pub fn primary(&mut self) -> &mut Printer<A, P> {
+        &mut self.primary
+    }
+
+    /// Borrow the backup printer.
+    pub fn backup(&mut self) -> &mut Printer<B, Q> {
+        &mut self.backup
+    }
+
+    /// Split the pair back into the primary and backup printers.
+    pub fn into_inner(self) -> (Printer<A, P>, Printer<B, Q>) {
+        (self.primary, self.backup)
+    }
+
+    /// Send `job` to the primary printer, or to the backup if that fails.
+    pub fn submit<const N: usize>(
+        &mut self,
+        job: &mut Job<N>,
+    ) -> Result<Destination, FailoverError<A::Error, B::Error>> {
+        let primary = match job.submit(&mut self.primary) {
+            Ok(()) => return Ok(Destination::Primary),
+            Err(err) => err,
+        };
+        match job.submit(&mut self.backup) {
+            Ok(()) => Ok(Destination::Backup),
+            Err(backup) => Err(FailoverError { primary, backup }),
+        }
+    }
+}
+
+impl<A, B, P: Delay, Q: Delay> Failover<A, B, P, Q>
+where
+    A: Write + Read<Error = <A as Write>::Error>,
+    B: Write + Read<Error = <B as Write>::Error>,
+{
+    /// Like [`submit`](Failover::submit), but check each printer's status
+    /// first with [`Job::submit_checked`].
+    ///
+    /// A primary printer that is offline, out of paper or in an error state
+    /// is skipped without sending anything to it.
+    pub fn submit_checked<const N: usize>(
+        &mut self,
+        job: &mut Job<N>,
+    ) -> Result<Destination, FailoverError<<A as Write>::Error, <B as Write>::Error>> {
+        let primary = match job.submit_checked(&mut self.primary) {
+            Ok(()) => return Ok(Destination::Primary),
+            Err(err) => err,
+        };
+        match job.submit_checked(&mut self.backup) {
+            Ok(()) => Ok(Destination::Backup),
+            Err(backup) => Err(FailoverError { primary, backup }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{FaultError, FaultyTransport, MockTransport};
+
+    fn job() -> Job<64> {
+        let mut job = Job::new();
+        job.write_line("Receipt").unwrap();
+        job
+    }
+
+    #[test]
+    fn test_submit_uses_primary() {
+        let mut printers = Failover::new(
+            Printer::new(MockTransport::new()),
+            Printer::new(MockTransport::new()),
+        );
+        assert_eq!(printers.submit(&mut job()), Ok(Destination::Primary));
+        let (primary, backup) = printers.into_inner();
+        assert_eq!(primary.transport().written(), b"Receipt\n");
+        assert!(backup.transport().written().is_empty());
+    }
+
+    #[test]
+    fn test_submit_fails_over() {
+        let mut printers = Failover::new(
+            Printer::new(FaultyTransport::new(MockTransport::new()).fail_write(1)),
+            Printer::new(MockTransport::new()),
+        );
+        let mut job = job();
+        assert_eq!(printers.submit(&mut job), Ok(Destination::Backup));
+        assert_eq!(printers.backup().transport().written(), job.as_bytes());
+    }
+
+    #[test]
+    fn test_submit_reports_both_errors() {
+        let mut printers = Failover::new(
+            Printer::new(FaultyTransport::new(MockTransport::new()).fail_write(1)),
+            Printer::new(FaultyTransport::new(MockTransport::new()).fail_write(1)),
+        );
+        let err = printers.submit(&mut job()).unwrap_err();
+        assert_eq!(err.primary.transport_error(), Some(&FaultError::Injected));
+        assert_eq!(err.backup.transport_error(), Some(&FaultError::Injected));
+    }
+
+    #[test]
+    fn test_submit_checked_skips_unready_primary() {
+        // Roll paper sensor reports paper end.
+        let paper_out = MockTransport::with_responses(&[0x00, 0x00, 0x00, 0x60]);
+        let mut printers = Failover::new(
+            Printer::new(paper_out),
+            Printer::new(MockTransport::with_responses(&[0x00; 4])),
+        );
+        assert_eq!(printers.submit_checked(&mut job()), Ok(Destination::Backup));
+        let (primary, backup) = printers.into_inner();
+        assert!(!primary.transport().written().ends_with(b"Receipt\n"));
+        assert!(backup.transport().written().ends_with(b"Receipt\n"));
+    }
+}
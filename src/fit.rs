@@ -0,0 +1,172 @@
+use super::batch::Batch;
+use super::{command, BitImageScale, Command, ImageFit, Raster, Write};
+
+/// An image adjusted to the printable width by an [`ImageFit`] policy.
+pub(crate) struct Fitted<'a> {
+    data: &'a [u8],
+    src_width: u16,
+    src_height: u16,
+    width: u16,
+    height: u16,
+    mode: Mode,
+    scale: BitImageScale,
+}
+
+enum Mode {
+    /// Printed as is.
+    Keep,
+    /// Output column `x` shows source column `x + offset`.
+    Crop(u16),
+    /// Rows are preceded by this many blank bytes.
+    Pad(u16),
+    /// Nearest-neighbour scaling to the output size.
+    Scale,
+}
+
+impl<'a> Fitted<'a> {
+    /// Apply `fit` to a `width` × `height` image for paper `dots` wide.
+    ///
+    /// `data` must already match the dimensions.
+    pub(crate) fn new(
+        data: &'a [u8],
+        width: u16,
+        height: u16,
+        fit: ImageFit,
+        dots: u16,
+        scale: BitImageScale,
+    ) -> Self {
+        let (out_width, out_height, mode) = match fit {
+            ImageFit::Center if width < dots => {
+                let pad = (dots / 8).saturating_sub(width.div_ceil(8)) / 2;
+                (width.div_ceil(8) * 8 + pad * 8, height, Mode::Pad(pad))
+            }
+            _ if width <= dots => (width, height, Mode::Keep),
+            ImageFit::Error => (width, height, Mode::Keep),
+            ImageFit::Crop => (dots, height, Mode::Crop(0)),
+            ImageFit::Center => (dots, height, Mode::Crop((width - dots) / 2)),
+            ImageFit::Scale => {
+                let scaled = u32::from(height) * u32::from(dots) / u32::from(width);
+                (dots, (scaled as u16).max(1), Mode::Scale)
+            }
+        };
+        Self {
+            data,
+            src_width: width,
+            src_height: height,
+            width: out_width,
+            height: out_height,
+            mode,
+            scale,
+        }
+    }
+
+    fn pixel(&self, x: u16, y: u16) -> bool {
+        let stride = usize::from(self.src_width.div_ceil(8));
+        let byte = self.data[usize::from(y) * stride + usize::from(x / 8)];
+        byte & (0x80 >> (x % 8)) != 0
+    }
+
+    /// Byte `column` of output row `y`.
+    fn byte(&self, column: u16, y: u16) -> u8 {
+        let stride = self.src_width.div_ceil(8);
+        let (offset, y) = match self.mode {
+            Mode::Keep => {
+                return self.data[usize::from(y) * usize::from(stride) + usize::from(column)];
+            }
+            Mode::Pad(pad) => {
+                return match column.checked_sub(pad) {
+                    Some(column) if column < stride => {
+                        self.data[usize::from(y) * usize::from(stride) + usize::from(column)]
+                    }
+                    _ => 0,
+                };
+            }
+            Mode::Crop(offset) => (offset, y),
+            Mode::Scale => (
+                0,
+                (u32::from(y) * u32::from(self.src_height) / u32::from(self.height)) as u16,
+            ),
+        };
+        (0..8)
+            .filter(|bit| column * 8 + bit < self.width)
+            .filter(|bit| {
+                let x = column * 8 + bit;
+                match self.mode {
+                    Mode::Scale => {
+                        let sx = u32::from(x) * u32::from(self.src_width) / u32::from(self.width);
+                        self.pixel(sx as u16, y)
+                    }
+                    _ => self.pixel(x + offset, y),
+                }
+            })
+            .fold(0, |byte, bit| byte | 0x80 >> bit)
+    }
+
+    /// Width of the printed image in dots.
+    pub(crate) fn width(&self) -> u16 {
+        self.width
+    }
+}
+
+impl Raster for Fitted<'_> {
+    fn command(&self) -> Command<'_> {
+        Command::RasterImage {
+            width: self.width,
+            height: self.height,
+            scale: self.scale,
+            data: match self.mode {
+                Mode::Keep => self.data,
+                _ => &[],
+            },
+        }
+    }
+
+    fn encode<W: Write>(&self, out: &mut W) -> Result<(), W::Error> {
+        out.write(&command::raster_header(self.width, self.height, self.scale))?;
+        if let Mode::Keep = self.mode {
+            return out.write(self.data);
+        }
+        let mut batch = Batch::new();
+        for y in 0..self.height {
+            for column in 0..self.width.div_ceil(8) {
+                let byte = [self.byte(column, y)];
+                if let Some(full) = batch.fill(&mut &byte[..]) {
+                    out.write(full)?;
+                }
+            }
+        }
+        match batch.rest() {
+            [] => Ok(()),
+            rest => out.write(rest),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockTransport;
+    use std::vec::Vec;
+
+    fn fit(data: &[u8], width: u16, height: u16, fit: ImageFit, dots: u16) -> Vec<u8> {
+        let mut out = MockTransport::new();
+        Fitted::new(data, width, height, fit, dots, BitImageScale::Normal)
+            .encode(&mut out)
+            .unwrap();
+        out.take_written().split_off(8)
+    }
+
+    #[test]
+    fn test_crop_and_center() {
+        let data = [0b1010_1010, 0b1111_0000];
+        assert_eq!(fit(&data, 16, 1, ImageFit::Crop, 8), [0b1010_1010]);
+        assert_eq!(fit(&data, 16, 1, ImageFit::Center, 8), [0b1010_1111]);
+        assert_eq!(fit(&[0xFF], 8, 1, ImageFit::Center, 32), [0, 0xFF]);
+    }
+
+    #[test]
+    fn test_scale() {
+        let data = [0b1100_1100, 0b0011_0011, 0xFF, 0xFF];
+        assert_eq!(fit(&data, 16, 2, ImageFit::Scale, 8), [0b1010_0101]);
+    }
+}
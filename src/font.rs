@@ -0,0 +1,213 @@
+//! Built-in 12x24 bitmap font used by the raster emulator and the
+//! cat printer backend.
+//!
+//! Glyphs cover printable ASCII (0x20–0x7E) and were rasterised from DejaVu
+//! Sans Mono. Each row is a `u16` with the leftmost pixel in the MSB.
+
+/// Glyph cell width in dots (Font A).
+pub const WIDTH: usize = 12;
+/// Glyph cell height in dots (Font A).
+pub const HEIGHT: usize = 24;
+
+/// Look up the glyph for `ch`, substituting `?` for unsupported characters.
+pub fn glyph(ch: char) -> &'static [u16; HEIGHT] {
+    let index = match ch {
+        ' '..='~' => ch as usize - 0x20,
+        _ => '?' as usize - 0x20,
+    };
+    &GLYPHS[index]
+}
+
+#[rustfmt::skip]
+static GLYPHS: [[u16; HEIGHT]; 95] = [
+    // ' '
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // '!'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0000, 0x0000, 0x0600, 0x0600, 0x0600, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // '"'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0900, 0x1980, 0x1980, 0x1980, 0x1980, 0x1980, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // '#'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0640, 0x0440, 0x0CC0, 0x0CC0, 0x7FF0, 0x1980, 0x1980, 0x1980, 0xFFE0, 0xFFE0, 0x3300, 0x3300, 0x3200, 0x2200, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // '$'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0200, 0x0200, 0x0F80, 0x1FC0, 0x3200, 0x3200, 0x3200, 0x3E00, 0x0F80, 0x03C0, 0x0260, 0x0260, 0x0260, 0x3FC0, 0x1F80, 0x0200, 0x0200, 0x0200, 0x0000, 0x0000],
+    // '%'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x3800, 0x6C00, 0xC600, 0xC600, 0x4C00, 0x7CE0, 0x0380, 0x1C00, 0x71E0, 0x0320, 0x0630, 0x0630, 0x0360, 0x01C0, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // '&'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0F00, 0x1F80, 0x3000, 0x3000, 0x3000, 0x1800, 0x1C00, 0x3C00, 0x6630, 0x4330, 0x43A0, 0x61E0, 0x60E0, 0x79E0, 0x1F70, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // '\''
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // '('
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0100, 0x0300, 0x0200, 0x0600, 0x0600, 0x0C00, 0x0C00, 0x0C00, 0x0C00, 0x0C00, 0x0C00, 0x0C00, 0x0600, 0x0600, 0x0600, 0x0300, 0x0300, 0x0100, 0x0000, 0x0000],
+    // ')'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0800, 0x0C00, 0x0400, 0x0600, 0x0600, 0x0300, 0x0300, 0x0300, 0x0300, 0x0300, 0x0300, 0x0300, 0x0600, 0x0600, 0x0600, 0x0C00, 0x0C00, 0x0800, 0x0000, 0x0000],
+    // '*'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0600, 0x0600, 0x2640, 0x1F80, 0x0700, 0x0F00, 0x36C0, 0x0600, 0x0600, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // '+'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0600, 0x0600, 0x0600, 0x0600, 0x7FE0, 0x7FE0, 0x0600, 0x0600, 0x0600, 0x0600, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // ','
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0600, 0x0600, 0x0600, 0x0E00, 0x0C00, 0x0C00, 0x0000, 0x0000],
+    // '-'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x1F80, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // '.'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0600, 0x0600, 0x0600, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // '/'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0040, 0x00C0, 0x00C0, 0x0180, 0x0180, 0x0300, 0x0300, 0x0600, 0x0600, 0x0C00, 0x0C00, 0x1800, 0x1800, 0x3000, 0x3000, 0x3000, 0x6000, 0x0000, 0x0000, 0x0000],
+    // '0'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0F00, 0x1F80, 0x38C0, 0x30C0, 0x30E0, 0x6060, 0x6660, 0x6660, 0x6660, 0x6060, 0x70E0, 0x30C0, 0x30C0, 0x19C0, 0x1F80, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // '1'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0600, 0x3F00, 0x3F00, 0x0300, 0x0300, 0x0300, 0x0300, 0x0300, 0x0300, 0x0300, 0x0300, 0x0300, 0x0300, 0x1FE0, 0x1FE0, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // '2'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x1F00, 0x3F80, 0x21C0, 0x00C0, 0x00C0, 0x00C0, 0x01C0, 0x0180, 0x0300, 0x0600, 0x0C00, 0x1800, 0x3800, 0x7FC0, 0x7FC0, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // '3'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x1F00, 0x3F80, 0x01C0, 0x00C0, 0x00C0, 0x00C0, 0x0F80, 0x0F80, 0x01C0, 0x00C0, 0x00E0, 0x00E0, 0x00C0, 0x73C0, 0x7F80, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // '4'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0180, 0x0380, 0x0780, 0x0580, 0x0D80, 0x0980, 0x1980, 0x3180, 0x2180, 0x6180, 0x7FE0, 0x7FE0, 0x0180, 0x0180, 0x0180, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // '5'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x3F80, 0x3F80, 0x3000, 0x3000, 0x3000, 0x3E00, 0x3F80, 0x21C0, 0x00C0, 0x00C0, 0x00C0, 0x00C0, 0x00C0, 0x7380, 0x7F00, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // '6'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0780, 0x1FC0, 0x3800, 0x3000, 0x3000, 0x6700, 0x7F80, 0x78C0, 0x70E0, 0x7060, 0x7060, 0x3060, 0x30C0, 0x39C0, 0x1F80, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // '7'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x3FC0, 0x7FE0, 0x00C0, 0x00C0, 0x0180, 0x0180, 0x0180, 0x0300, 0x0300, 0x0600, 0x0600, 0x0600, 0x0C00, 0x0C00, 0x1C00, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // '8'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0F00, 0x3FC0, 0x30C0, 0x30C0, 0x30C0, 0x30C0, 0x1F80, 0x1F80, 0x39C0, 0x70E0, 0x6060, 0x6060, 0x70E0, 0x39C0, 0x1F80, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // '9'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0F00, 0x3F80, 0x30C0, 0x60C0, 0x60C0, 0x60E0, 0x60E0, 0x30E0, 0x3FE0, 0x1F60, 0x0040, 0x00C0, 0x00C0, 0x3380, 0x3F00, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // ':'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0600, 0x0600, 0x0600, 0x0000, 0x0000, 0x0000, 0x0000, 0x0600, 0x0600, 0x0600, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // ';'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0600, 0x0600, 0x0600, 0x0000, 0x0000, 0x0000, 0x0000, 0x0600, 0x0600, 0x0600, 0x0E00, 0x0C00, 0x0C00, 0x0000, 0x0000],
+    // '<'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0060, 0x01E0, 0x0F80, 0x3C00, 0x7000, 0x7800, 0x1F00, 0x03C0, 0x00E0, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // '='
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x7FE0, 0x7FE0, 0x0000, 0x0000, 0x7FE0, 0x7FE0, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // '>'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x6000, 0x7800, 0x1F00, 0x03C0, 0x00E0, 0x01E0, 0x0F80, 0x3C00, 0x7000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // '?'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0F00, 0x3F80, 0x30C0, 0x00C0, 0x00C0, 0x01C0, 0x0380, 0x0700, 0x0600, 0x0600, 0x0600, 0x0000, 0x0400, 0x0600, 0x0600, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // '@'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0700, 0x1FC0, 0x3060, 0x6020, 0x6130, 0x47F0, 0xCC70, 0xCC30, 0xCC30, 0xCC30, 0xCC30, 0x47F0, 0x63A0, 0x6000, 0x3000, 0x1C40, 0x0FC0, 0x0000, 0x0000],
+    // 'A'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0600, 0x0F00, 0x0F00, 0x0F00, 0x0980, 0x1980, 0x1980, 0x1980, 0x30C0, 0x30C0, 0x3FC0, 0x70E0, 0x6060, 0x6060, 0xE070, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'B'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x3E00, 0x3FC0, 0x30C0, 0x30E0, 0x30E0, 0x30C0, 0x3FC0, 0x3F80, 0x30C0, 0x3060, 0x3060, 0x3060, 0x3060, 0x3FC0, 0x3F80, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'C'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x07C0, 0x1FE0, 0x1800, 0x3000, 0x3000, 0x7000, 0x7000, 0x7000, 0x7000, 0x7000, 0x3000, 0x3000, 0x3800, 0x1CE0, 0x0FC0, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'D'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x3C00, 0x7F00, 0x61C0, 0x60C0, 0x60C0, 0x60E0, 0x6060, 0x6060, 0x6060, 0x6060, 0x60E0, 0x60C0, 0x61C0, 0x7F80, 0x7E00, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'E'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x3FC0, 0x3FE0, 0x3000, 0x3000, 0x3000, 0x3000, 0x3FC0, 0x3FC0, 0x3000, 0x3000, 0x3000, 0x3000, 0x3000, 0x3FE0, 0x3FE0, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'F'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x1FE0, 0x3FE0, 0x3000, 0x3000, 0x3000, 0x3000, 0x3FC0, 0x3FC0, 0x3000, 0x3000, 0x3000, 0x3000, 0x3000, 0x3000, 0x3000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'G'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0780, 0x1FC0, 0x3840, 0x3000, 0x7000, 0x6000, 0x6000, 0x61C0, 0x61E0, 0x6060, 0x6060, 0x3060, 0x3060, 0x1CE0, 0x0FC0, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'H'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x2040, 0x6060, 0x6060, 0x6060, 0x6060, 0x6060, 0x7FE0, 0x7FE0, 0x6060, 0x6060, 0x6060, 0x6060, 0x6060, 0x6060, 0x6060, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'I'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x3FC0, 0x3FC0, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x3FC0, 0x3FC0, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'J'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0F80, 0x0F80, 0x0180, 0x0180, 0x0180, 0x0180, 0x0180, 0x0180, 0x0180, 0x0180, 0x0180, 0x0180, 0x4180, 0x7380, 0x7F00, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'K'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x2060, 0x60E0, 0x61C0, 0x6380, 0x6300, 0x6600, 0x7C00, 0x7E00, 0x7700, 0x6300, 0x6180, 0x61C0, 0x60C0, 0x6060, 0x6070, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'L'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x3000, 0x3000, 0x3000, 0x3000, 0x3000, 0x3000, 0x3000, 0x3000, 0x3000, 0x3000, 0x3000, 0x3000, 0x3000, 0x3FE0, 0x3FE0, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'M'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x6060, 0x70E0, 0x70E0, 0x79E0, 0x79E0, 0x6960, 0x6F60, 0x6F60, 0x6660, 0x6660, 0x6060, 0x6060, 0x6060, 0x6060, 0x6060, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'N'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x3040, 0x7060, 0x7860, 0x7860, 0x6C60, 0x6C60, 0x6460, 0x6660, 0x6660, 0x6360, 0x6360, 0x61E0, 0x61E0, 0x61E0, 0x60E0, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'O'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0F00, 0x1F80, 0x30C0, 0x30C0, 0x7060, 0x6060, 0x6060, 0x6060, 0x6060, 0x6060, 0x6060, 0x70E0, 0x30C0, 0x39C0, 0x1F80, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'P'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x3F00, 0x3FC0, 0x30E0, 0x3060, 0x3060, 0x3060, 0x30E0, 0x3FC0, 0x3F80, 0x3000, 0x3000, 0x3000, 0x3000, 0x3000, 0x3000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'Q'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0F00, 0x1F80, 0x30C0, 0x30C0, 0x7060, 0x6060, 0x6060, 0x6060, 0x6060, 0x6060, 0x6060, 0x70E0, 0x30C0, 0x39C0, 0x1F80, 0x0380, 0x00C0, 0x0000, 0x0000, 0x0000],
+    // 'R'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x3E00, 0x7F80, 0x61C0, 0x60C0, 0x60C0, 0x60C0, 0x60C0, 0x7F80, 0x7F00, 0x6180, 0x60C0, 0x60C0, 0x6060, 0x6060, 0x6030, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'S'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0F80, 0x3FC0, 0x3040, 0x6000, 0x6000, 0x7000, 0x3C00, 0x1F80, 0x03C0, 0x00E0, 0x0060, 0x0060, 0x00E0, 0x79C0, 0x3F80, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'T'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x7FE0, 0xFFF0, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'U'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x2040, 0x6060, 0x6060, 0x6060, 0x6060, 0x6060, 0x6060, 0x6060, 0x6060, 0x6060, 0x6060, 0x7060, 0x30C0, 0x39C0, 0x1F80, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'V'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x4020, 0x6060, 0x6060, 0x6060, 0x30C0, 0x30C0, 0x30C0, 0x1980, 0x1980, 0x1980, 0x1980, 0x0F00, 0x0F00, 0x0F00, 0x0600, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'W'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0xC030, 0xC030, 0xC030, 0xC030, 0x4630, 0x6660, 0x6F60, 0x6F60, 0x6F60, 0x6960, 0x6960, 0x39C0, 0x39C0, 0x30C0, 0x30C0, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'X'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x6060, 0x7060, 0x30C0, 0x18C0, 0x1980, 0x0F00, 0x0700, 0x0600, 0x0F00, 0x0D80, 0x1980, 0x38C0, 0x30C0, 0x6060, 0xE070, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'Y'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x4020, 0x6060, 0x70E0, 0x30C0, 0x1980, 0x1980, 0x0F00, 0x0F00, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'Z'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x3FE0, 0x3FE0, 0x0060, 0x00C0, 0x01C0, 0x0180, 0x0300, 0x0700, 0x0600, 0x0C00, 0x1C00, 0x1800, 0x3000, 0x3FE0, 0x7FE0, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // '['
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0F80, 0x0E00, 0x0C00, 0x0C00, 0x0C00, 0x0C00, 0x0C00, 0x0C00, 0x0C00, 0x0C00, 0x0C00, 0x0C00, 0x0C00, 0x0C00, 0x0C00, 0x0C00, 0x0F80, 0x0700, 0x0000, 0x0000],
+    // '\\'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x6000, 0x6000, 0x3000, 0x3000, 0x1800, 0x1800, 0x0C00, 0x0C00, 0x0600, 0x0600, 0x0300, 0x0300, 0x0180, 0x0180, 0x00C0, 0x00C0, 0x00C0, 0x0000, 0x0000, 0x0000],
+    // ']'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x1F00, 0x0300, 0x0300, 0x0300, 0x0300, 0x0300, 0x0300, 0x0300, 0x0300, 0x0300, 0x0300, 0x0300, 0x0300, 0x0300, 0x0300, 0x0300, 0x1F00, 0x0E00, 0x0000, 0x0000],
+    // '^'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0600, 0x0F00, 0x1F80, 0x1980, 0x30C0, 0x6060, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // '_'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0xFFF0],
+    // '`'
+    [0x0000, 0x0000, 0x0000, 0x1800, 0x0C00, 0x0600, 0x0200, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'a'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x3F80, 0x39C0, 0x00C0, 0x00C0, 0x1FC0, 0x3FC0, 0x70C0, 0x60C0, 0x60C0, 0x33C0, 0x3FC0, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'b'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x3000, 0x3000, 0x3000, 0x3000, 0x3F80, 0x3DC0, 0x30C0, 0x3060, 0x3060, 0x3060, 0x3060, 0x3060, 0x30C0, 0x39C0, 0x3F80, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'c'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0FC0, 0x1CC0, 0x3800, 0x3000, 0x3000, 0x3000, 0x3000, 0x3000, 0x3800, 0x1C40, 0x0FC0, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'd'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x00C0, 0x00C0, 0x00C0, 0x00C0, 0x1FC0, 0x3BC0, 0x30C0, 0x60C0, 0x60C0, 0x60C0, 0x60C0, 0x60C0, 0x30C0, 0x39C0, 0x1FC0, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'e'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0F80, 0x3DC0, 0x30C0, 0x6060, 0x7FE0, 0x7FE0, 0x6000, 0x6000, 0x3000, 0x38E0, 0x1FC0, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'f'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x03C0, 0x0700, 0x0600, 0x0600, 0x3FC0, 0x3FC0, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'g'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x1FC0, 0x3BC0, 0x30C0, 0x60C0, 0x60C0, 0x60C0, 0x60C0, 0x60C0, 0x30C0, 0x3FC0, 0x1EC0, 0x00C0, 0x00C0, 0x3180, 0x3F80, 0x0000],
+    // 'h'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x3000, 0x3000, 0x3000, 0x3000, 0x3780, 0x3DC0, 0x30C0, 0x30C0, 0x30C0, 0x30C0, 0x30C0, 0x30C0, 0x30C0, 0x30C0, 0x30C0, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'i'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0600, 0x0600, 0x0000, 0x0000, 0x3E00, 0x1E00, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0700, 0x3FE0, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'j'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0300, 0x0300, 0x0000, 0x0000, 0x1F00, 0x1F00, 0x0300, 0x0300, 0x0300, 0x0300, 0x0300, 0x0300, 0x0300, 0x0300, 0x0300, 0x0300, 0x0300, 0x0E00, 0x3C00, 0x0000],
+    // 'k'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x3000, 0x3000, 0x3000, 0x3000, 0x30E0, 0x31C0, 0x3380, 0x3700, 0x3E00, 0x3F00, 0x3380, 0x3180, 0x30C0, 0x30E0, 0x3060, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'l'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x7C00, 0x0C00, 0x0C00, 0x0C00, 0x0C00, 0x0C00, 0x0C00, 0x0C00, 0x0C00, 0x0C00, 0x0C00, 0x0C00, 0x0600, 0x07C0, 0x03C0, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'm'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x7DC0, 0x7F60, 0x6660, 0x6660, 0x6660, 0x6660, 0x6660, 0x6660, 0x6660, 0x6660, 0x6660, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'n'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x3780, 0x3DC0, 0x30C0, 0x30C0, 0x30C0, 0x30C0, 0x30C0, 0x30C0, 0x30C0, 0x30C0, 0x30C0, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'o'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x1F80, 0x39C0, 0x30C0, 0x70E0, 0x6060, 0x6060, 0x6060, 0x7060, 0x30C0, 0x39C0, 0x1F80, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'p'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x3F80, 0x3DC0, 0x30C0, 0x3060, 0x3060, 0x3060, 0x3060, 0x3060, 0x30C0, 0x39C0, 0x3F80, 0x3000, 0x3000, 0x3000, 0x3000, 0x0000],
+    // 'q'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x1FC0, 0x3BC0, 0x30C0, 0x70C0, 0x60C0, 0x60C0, 0x60C0, 0x70C0, 0x30C0, 0x39C0, 0x1FC0, 0x00C0, 0x00C0, 0x00C0, 0x00C0, 0x0000],
+    // 'r'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x19E0, 0x1FE0, 0x1C00, 0x1C00, 0x1C00, 0x1800, 0x1800, 0x1800, 0x1800, 0x1800, 0x1800, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 's'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x1F80, 0x39C0, 0x3000, 0x3000, 0x3C00, 0x1F80, 0x01C0, 0x00C0, 0x00C0, 0x31C0, 0x3F80, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 't'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0C00, 0x0C00, 0x0C00, 0x7FC0, 0x3FC0, 0x0C00, 0x0C00, 0x0C00, 0x0C00, 0x0C00, 0x0C00, 0x0C00, 0x07C0, 0x07C0, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'u'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x30C0, 0x30C0, 0x30C0, 0x30C0, 0x30C0, 0x30C0, 0x30C0, 0x30C0, 0x30C0, 0x39C0, 0x1FC0, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'v'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x6060, 0x6060, 0x30C0, 0x30C0, 0x30C0, 0x1980, 0x1980, 0x0900, 0x0F00, 0x0F00, 0x0600, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'w'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0xC030, 0xC030, 0x4030, 0x6660, 0x6660, 0x6660, 0x6F60, 0x2940, 0x39C0, 0x39C0, 0x30C0, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'x'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x7060, 0x30C0, 0x1980, 0x0F00, 0x0F00, 0x0600, 0x0F00, 0x1980, 0x1980, 0x30C0, 0x6060, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // 'y'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x6060, 0x3060, 0x30C0, 0x30C0, 0x18C0, 0x1980, 0x1980, 0x0D00, 0x0F00, 0x0700, 0x0600, 0x0600, 0x0E00, 0x1C00, 0x3800, 0x0000],
+    // 'z'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x3FC0, 0x1FC0, 0x01C0, 0x0180, 0x0300, 0x0600, 0x0C00, 0x1C00, 0x1800, 0x3FC0, 0x3FC0, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+    // '{'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x03C0, 0x0700, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x3C00, 0x3C00, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0700, 0x03C0, 0x0000, 0x0000],
+    // '|'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600],
+    // '}'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x3C00, 0x0E00, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x03C0, 0x03C0, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0600, 0x0E00, 0x3C00, 0x0000, 0x0000],
+    // '~'
+    [0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x1800, 0x7FE0, 0x43E0, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000],
+];
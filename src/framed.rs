@@ -0,0 +1,331 @@
+use super::{Read, Write};
+
+/// Maximum number of payload bytes carried by a single frame.
+pub const FRAME_MAX_PAYLOAD: usize = 128;
+
+const SOF: u8 = 0x7E;
+const ACK: u8 = 0x06;
+
+/// Errors produced by a [`Framed`] link.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FramedError<E> {
+    /// The underlying transport failed.
+    Transport(E),
+    /// A frame was not acknowledged after all retransmissions.
+    Timeout,
+}
+
+impl<E> From<E> for FramedError<E> {
+    fn from(err: E) -> Self {
+        FramedError::Transport(err)
+    }
+}
+
+enum Packet {
+    Ack(u8),
+    Data(u8, usize),
+    Nothing,
+}
+
+/// Reliable framing layer for lossy links (e.g. DIY radio or RS-485).
+///
+/// The same wrapper is used on both ends of the link: the host wraps its
+/// radio/UART transport and hands it to [`Printer`](crate::Printer), while
+/// the printer-side MCU wraps its end and forwards whatever it reads to the
+/// printer.
+///
+/// Data is sent in frames of `SOF seq len payload crc16` and each frame must be
+/// acknowledged with `ACK seq !seq`. Unacknowledged frames are retransmitted,
+/// and duplicate frames (where only the ACK was lost) are acknowledged again
+/// but not delivered twice.
+///
+/// The inner transport's `read` must return `Ok(0)` when no data arrives
+/// within its timeout; this is how a missing acknowledgement is detected.
+pub struct Framed<T> {
+    inner: T,
+    retries: u8,
+    tx_seq: u8,
+    last_rx_seq: Option<u8>,
+    frame: [u8; FRAME_MAX_PAYLOAD],
+    rx: [u8; FRAME_MAX_PAYLOAD],
+    rx_pos: usize,
+    rx_len: usize,
+}
+
+impl<T> Framed<T> {
+    /// Wrap `inner`, retransmitting each frame up to 3 times.
+    pub fn new(inner: T) -> Self {
+        Self::with_retries(inner, 3)
+    }
+
+    /// Wrap `inner`, retransmitting each frame up to `retries` times.
+    pub fn with_retries(inner: T, retries: u8) -> Self {
+        Self {
+            inner,
+            retries,
+            tx_seq: 0,
+            last_rx_seq: None,
+            frame: [0; FRAME_MAX_PAYLOAD],
+            rx: [0; FRAME_MAX_PAYLOAD],
+            rx_pos: 0,
+            rx_len: 0,
+        }
+    }
+
+    /// Consume the wrapper, returning the inner transport.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T, E> Framed<T>
+where
+    T: Read<Error = E> + Write<Error = E>,
+{
+    fn read_byte(&mut self) -> Result<Option<u8>, E> {
+        let mut b = [0u8; 1];
+        match self.inner.read(&mut b)? {
+            0 => Ok(None),
+            _ => Ok(Some(b[0])),
+        }
+    }
+
+    /// Receive the next packet into `self.frame`, returning `Nothing` when the
+    /// link is idle or a corrupt frame was discarded.
+    fn receive_packet(&mut self) -> Result<Packet, E> {
+        let Some(start) = self.read_byte()? else {
+            return Ok(Packet::Nothing);
+        };
+        match start {
+            ACK => {
+                let (Some(seq), Some(check)) = (self.read_byte()?, self.read_byte()?) else {
+                    return Ok(Packet::Nothing);
+                };
+                if seq == !check {
+                    Ok(Packet::Ack(seq))
+                } else {
+                    Ok(Packet::Nothing)
+                }
+            }
+            SOF => {
+                let (Some(seq), Some(len)) = (self.read_byte()?, self.read_byte()?) else {
+                    return Ok(Packet::Nothing);
+                };
+                let len = len as usize;
+                if len > FRAME_MAX_PAYLOAD {
+                    return Ok(Packet::Nothing);
+                }
+                for i in 0..len {
+                    let Some(b) = self.read_byte()? else {
+                        return Ok(Packet::Nothing);
+                    };
+                    self.frame[i] = b;
+                }
+                let (Some(lo), Some(hi)) = (self.read_byte()?, self.read_byte()?) else {
+                    return Ok(Packet::Nothing);
+                };
+                let crc = crc16(crc16(0xFFFF, &[seq, len as u8]), &self.frame[..len]);
+                if crc == u16::from_le_bytes([lo, hi]) {
+                    Ok(Packet::Data(seq, len))
+                } else {
+                    Ok(Packet::Nothing)
+                }
+            }
+            _ => Ok(Packet::Nothing),
+        }
+    }
+
+    /// Acknowledge a received data frame and queue it for delivery if it is
+    /// new and there is room.
+    fn accept_data(&mut self, seq: u8, len: usize) -> Result<(), E> {
+        if self.last_rx_seq == Some(seq) {
+            return self.inner.write(&[ACK, seq, !seq]);
+        }
+        if self.rx_pos < self.rx_len {
+            // Still holding undelivered data; let the peer retransmit later.
+            return Ok(());
+        }
+        self.rx[..len].copy_from_slice(&self.frame[..len]);
+        self.rx_pos = 0;
+        self.rx_len = len;
+        self.last_rx_seq = Some(seq);
+        self.inner.write(&[ACK, seq, !seq])
+    }
+
+    fn send_frame(&mut self, payload: &[u8]) -> Result<(), FramedError<E>> {
+        let seq = self.tx_seq;
+        let len = payload.len() as u8;
+        let crc = crc16(crc16(0xFFFF, &[seq, len]), payload).to_le_bytes();
+        for _ in 0..=self.retries {
+            self.inner.write(&[SOF, seq, len])?;
+            self.inner.write(payload)?;
+            self.inner.write(&crc)?;
+            loop {
+                match self.receive_packet()? {
+                    Packet::Ack(s) if s == seq => {
+                        self.tx_seq = seq.wrapping_add(1);
+                        return Ok(());
+                    }
+                    Packet::Ack(_) => {}
+                    Packet::Data(s, l) => self.accept_data(s, l)?,
+                    Packet::Nothing => break,
+                }
+            }
+        }
+        Err(FramedError::Timeout)
+    }
+}
+
+impl<T, E> Write for Framed<T>
+where
+    T: Read<Error = E> + Write<Error = E>,
+{
+    type Error = FramedError<E>;
+
+    fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        for chunk in data.chunks(FRAME_MAX_PAYLOAD) {
+            self.send_frame(chunk)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, E> Read for Framed<T>
+where
+    T: Read<Error = E> + Write<Error = E>,
+{
+    type Error = FramedError<E>;
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        if self.rx_pos == self.rx_len {
+            if let Packet::Data(seq, len) = self.receive_packet()? {
+                self.accept_data(seq, len)?;
+            }
+        }
+        let n = core::cmp::min(buf.len(), self.rx_len - self.rx_pos);
+        buf[..n].copy_from_slice(&self.rx[self.rx_pos..self.rx_pos + n]);
+        self.rx_pos += n;
+        Ok(n)
+    }
+}
+
+/// CRC-16/CCITT-FALSE, continuing from `crc`.
+fn crc16(mut crc: u16, data: &[u8]) -> u16 {
+    for &b in data {
+        crc ^= (b as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::vec;
+    use std::vec::Vec;
+
+    struct Link {
+        rx: VecDeque<u8>,
+        tx: Vec<u8>,
+    }
+
+    impl Link {
+        fn new(rx: &[u8]) -> Self {
+            Self {
+                rx: rx.iter().copied().collect(),
+                tx: Vec::new(),
+            }
+        }
+    }
+
+    impl Write for Link {
+        type Error = core::convert::Infallible;
+
+        fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+            self.tx.extend_from_slice(data);
+            Ok(())
+        }
+    }
+
+    impl Read for Link {
+        type Error = core::convert::Infallible;
+
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            let mut n = 0;
+            while n < buf.len() {
+                match self.rx.pop_front() {
+                    Some(b) => buf[n] = b,
+                    None => break,
+                }
+                n += 1;
+            }
+            Ok(n)
+        }
+    }
+
+    fn frame(seq: u8, payload: &[u8]) -> Vec<u8> {
+        let len = payload.len() as u8;
+        let crc = crc16(crc16(0xFFFF, &[seq, len]), payload).to_le_bytes();
+        let mut out = vec![SOF, seq, len];
+        out.extend_from_slice(payload);
+        out.extend_from_slice(&crc);
+        out
+    }
+
+    #[test]
+    fn test_crc16() {
+        assert_eq!(crc16(0xFFFF, b"123456789"), 0x29B1);
+    }
+
+    #[test]
+    fn test_write_acked() {
+        let mut link = Framed::new(Link::new(&[ACK, 0, 0xFF]));
+        link.write(b"Hi").unwrap();
+        assert_eq!(link.into_inner().tx, frame(0, b"Hi"));
+    }
+
+    #[test]
+    fn test_write_retransmits_then_times_out() {
+        let mut link = Framed::with_retries(Link::new(&[]), 2);
+        assert_eq!(link.write(b"Hi"), Err(FramedError::Timeout));
+        assert_eq!(link.into_inner().tx, frame(0, b"Hi").repeat(3));
+    }
+
+    #[test]
+    fn test_read_acks_and_delivers() {
+        let mut link = Framed::new(Link::new(&frame(5, b"abc")));
+        let mut buf = [0u8; 8];
+        assert_eq!(link.read(&mut buf).unwrap(), 3);
+        assert_eq!(&buf[..3], b"abc");
+        assert_eq!(link.into_inner().tx, vec![ACK, 5, !5]);
+    }
+
+    #[test]
+    fn test_read_duplicate_not_delivered_twice() {
+        let mut rx = frame(1, b"x");
+        rx.extend(frame(1, b"x"));
+        let mut link = Framed::new(Link::new(&rx));
+        let mut buf = [0u8; 8];
+        assert_eq!(link.read(&mut buf).unwrap(), 1);
+        assert_eq!(link.read(&mut buf).unwrap(), 0);
+        assert_eq!(link.into_inner().tx, vec![ACK, 1, !1, ACK, 1, !1]);
+    }
+
+    #[test]
+    fn test_read_rejects_bad_crc() {
+        let mut rx = frame(0, b"abc");
+        rx[4] ^= 0xFF;
+        let mut link = Framed::new(Link::new(&rx));
+        let mut buf = [0u8; 8];
+        assert_eq!(link.read(&mut buf).unwrap(), 0);
+        assert!(link.into_inner().tx.is_empty());
+    }
+}
@@ -0,0 +1,96 @@
+use super::batch::Batch;
+use super::{command, BitImageScale, Command, PrinterError, Raster, Write};
+
+/// Rows printed per band; the paper is fed back this far between passes.
+pub(crate) const BAND: u16 = 24;
+
+/// A 4-level grayscale image for [`Printer::print_image_gray`].
+///
+/// Pixels are packed 2 bits each, four to a byte with the leftmost pixel in
+/// the most significant bits, and rows start on a byte boundary. `0` is
+/// white and `3` is black.
+///
+/// [`Printer::print_image_gray`]: crate::Printer::print_image_gray
+pub struct GrayImage<D>
+where
+    D: AsRef<[u8]>,
+{
+    /// Image width in pixels.
+    pub width: u16,
+    /// Image height in pixels.
+    pub height: u16,
+    /// Packed pixel data (row-major, 2 bits per pixel).
+    pub data: D,
+}
+
+impl<D: AsRef<[u8]>> GrayImage<D> {
+    /// Return the image data if its length matches the dimensions.
+    pub(crate) fn check_size<E>(&self) -> Result<&[u8], PrinterError<E>> {
+        let data = self.data.as_ref();
+        let expected = command::image_len("GrayImage", self.width, self.height, 4)?;
+        if data.len() != expected {
+            return Err(PrinterError::ImageSizeMismatch {
+                command: "GrayImage",
+                expected,
+                actual: data.len(),
+            });
+        }
+        Ok(data)
+    }
+}
+
+/// One pass over a band of a grayscale image: every pixel at least `level`
+/// dark is printed black.
+pub(crate) struct Pass<'a> {
+    pub(crate) data: &'a [u8],
+    pub(crate) width: u16,
+    pub(crate) rows: core::ops::Range<u16>,
+    pub(crate) level: u8,
+}
+
+impl Pass<'_> {
+    fn byte(&self, column: u16, y: u16) -> u8 {
+        let stride = usize::from(self.width.div_ceil(4));
+        (0..8)
+            .filter(|bit| column * 8 + bit < self.width)
+            .filter(|bit| {
+                let x = usize::from(column * 8 + bit);
+                let byte = self.data[usize::from(y) * stride + x / 4];
+                (byte >> (6 - 2 * (x % 4))) & 0x03 >= self.level
+            })
+            .fold(0, |byte, bit| byte | 0x80 >> bit)
+    }
+}
+
+impl Raster for Pass<'_> {
+    fn command(&self) -> Command<'_> {
+        Command::RasterImage {
+            width: self.width,
+            height: self.rows.end - self.rows.start,
+            scale: BitImageScale::Normal,
+            data: &[],
+        }
+    }
+
+    fn encode<W: Write>(&self, out: &mut W) -> Result<(), W::Error> {
+        let height = self.rows.end - self.rows.start;
+        out.write(&command::raster_header(
+            self.width,
+            height,
+            BitImageScale::Normal,
+        ))?;
+        let mut batch = Batch::new();
+        for y in self.rows.clone() {
+            for column in 0..self.width.div_ceil(8) {
+                let byte = [self.byte(column, y)];
+                if let Some(full) = batch.fill(&mut &byte[..]) {
+                    out.write(full)?;
+                }
+            }
+        }
+        match batch.rest() {
+            [] => Ok(()),
+            rest => out.write(rest),
+        }
+    }
+}
@@ -0,0 +1,242 @@
+use super::{Command, PrinterError, Write};
+
+/// Callbacks run before output reaches the transport.
+///
+/// Lets an application meter, audit or block what a printer sends in one
+/// place, without wrapping the transport. Configure them with
+/// [`PrinterBuilder::hooks`](crate::PrinterBuilder::hooks):
+///
+/// - `on_command` sees every [`Command`] passed to
+///   [`Printer::send`](crate::Printer::send), and every command in a
+///   [`replay`](crate::Printer::replay) or a submitted
+///   [`Job`](crate::Job). Text written in batches, by
+///   [`print_lines`](crate::Printer::print_lines) or an
+///   [`AnsiWriter`](crate::AnsiWriter), is seen as [`Command::Text`], or
+///   [`Command::Raw`] if a batch isn't valid UTF-8. Images are seen as
+///   their image command before any data is sent; when the bitmap is
+///   converted on the way out, as for fitted, column or grayscale images,
+///   its `data` is empty. The data chunks of
+///   `print_image_with_delay`
+///   are only seen by `on_bytes`;
+/// - `on_bytes` sees every write to the transport, after chunking.
+///
+/// Returning `false` vetoes the output: nothing more of the command is sent
+/// and the printer method fails with [`PrinterError::Cancelled`]. Pieces of
+/// a chunked command written before the veto have already been sent.
+///
+/// ```
+/// # use escpos_embedded::{Command, Hooks, Printer, PrinterError, Recorder};
+/// use core::sync::atomic::{AtomicUsize, Ordering};
+///
+/// static SENT: AtomicUsize = AtomicUsize::new(0);
+///
+/// let hooks = Hooks::new()
+///     .on_command(|command| command.name() != "ExecuteMacro")
+///     .on_bytes(|bytes| {
+///         SENT.fetch_add(bytes.len(), Ordering::Relaxed);
+///         true
+///     });
+/// let mut printer = Printer::builder(Recorder::<64>::new()).hooks(hooks).build().unwrap();
+/// printer.write_line("Hi").unwrap();
+/// assert_eq!(SENT.load(Ordering::Relaxed), 3);
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Hooks {
+    on_command: Option<fn(&Command) -> bool>,
+    on_bytes: Option<fn(&[u8]) -> bool>,
+}
+
+impl Hooks {
+    /// No hooks.
+    pub const fn new() -> Self {
+        Self {
+            on_command: None,
+            on_bytes: None,
+        }
+    }
+
+    /// Call `hook` with each command before it is encoded.
+    pub const fn on_command(mut self, hook: fn(&Command) -> bool) -> Self {
+        self.on_command = Some(hook);
+        self
+    }
+
+    /// Call `hook` with the data of each transport write.
+    pub const fn on_bytes(mut self, hook: fn(&[u8]) -> bool) -> Self {
+        self.on_bytes = Some(hook);
+        self
+    }
+
+    /// Run the command hook, failing if it vetoes `command`.
+    pub(crate) fn check<E>(&self, command: &Command) -> Result<(), PrinterError<E>> {
+        match self.on_command {
+            Some(hook) if !hook(command) => Err(PrinterError::Cancelled {
+                command: command.name(),
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Writer running the byte hook before every write.
+///
+/// After a veto, later writes are dropped; [`finish`](Hooked::finish) then
+/// reports the cancellation.
+pub(crate) struct Hooked<W> {
+    inner: W,
+    on_bytes: Option<fn(&[u8]) -> bool>,
+    vetoed: bool,
+}
+
+impl<W: Write> Hooked<W> {
+    pub(crate) fn new(inner: W, hooks: Hooks) -> Self {
+        Self {
+            inner,
+            on_bytes: hooks.on_bytes,
+            vetoed: false,
+        }
+    }
+
+    /// Turn the result of writing `command` into a printer result.
+    pub(crate) fn finish(
+        self,
+        command: &'static str,
+        result: Result<(), W::Error>,
+    ) -> Result<(), PrinterError<W::Error>> {
+        result.map_err(|error| PrinterError::Transport { command, error })?;
+        if self.vetoed {
+            return Err(PrinterError::Cancelled { command });
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for Hooked<W> {
+    type Error = W::Error;
+
+    fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        if self.vetoed {
+            return Ok(());
+        }
+        if let Some(hook) = self.on_bytes {
+            if !hook(data) {
+                self.vetoed = true;
+                return Ok(());
+            }
+        }
+        self.inner.write(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockTransport;
+    use crate::{CutMode, Job, Printer};
+
+    #[test]
+    fn test_command_veto() {
+        let hooks = Hooks::new().on_command(|command| !matches!(command, Command::Cut(_)));
+        let mut printer = Printer::builder(MockTransport::new())
+            .hooks(hooks)
+            .build()
+            .unwrap();
+        printer.feed(1).unwrap();
+        assert_eq!(
+            printer.cut(CutMode::Full),
+            Err(PrinterError::Cancelled { command: "Cut" })
+        );
+        assert_eq!(
+            printer.replay(b"A\x1dV\x00", false),
+            Err(PrinterError::Cancelled { command: "Cut" })
+        );
+        assert_eq!(printer.transport().written(), b"\x1bd\x01A");
+    }
+
+    #[test]
+    fn test_command_veto_blocks_job() {
+        let hooks = Hooks::new().on_command(|command| !matches!(command, Command::Cut(_)));
+        let mut printer = Printer::builder(MockTransport::new())
+            .hooks(hooks)
+            .build()
+            .unwrap();
+        let mut job = Job::<16>::new();
+        job.write_line("A").unwrap();
+        job.cut(CutMode::Full).unwrap();
+        assert_eq!(
+            job.submit(&mut printer),
+            Err(PrinterError::Cancelled { command: "Cut" })
+        );
+        assert_eq!(printer.transport().written(), b"A\n");
+    }
+
+    #[test]
+    fn test_command_veto_sees_text_batches() {
+        let hooks = Hooks::new().on_command(|command| match command {
+            Command::Text(text) => !text.contains("VOID"),
+            _ => true,
+        });
+        let mut printer = Printer::builder(MockTransport::new())
+            .hooks(hooks)
+            .build()
+            .unwrap();
+        printer.print_lines(["A", "B"]).unwrap();
+        assert_eq!(
+            printer.print_lines(["VOID"]),
+            Err(PrinterError::Cancelled { command: "Text" })
+        );
+        assert_eq!(
+            printer.ansi().write(b"\x1b[1mVOID"),
+            Err(PrinterError::Cancelled { command: "Text" })
+        );
+        assert_eq!(printer.transport().written(), b"A\nB\n\x1bE\x01");
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_command_veto_sees_images() {
+        let hooks = Hooks::new().on_command(|command| {
+            !matches!(
+                command,
+                Command::RasterImage { .. } | Command::BitImage { .. }
+            )
+        });
+        let mut printer = Printer::builder(MockTransport::new())
+            .hooks(hooks)
+            .build()
+            .unwrap();
+        let image = crate::Image {
+            width: 8,
+            height: 1,
+            data: [0xFF],
+        };
+        assert_eq!(
+            printer.print_image(&image),
+            Err(PrinterError::Cancelled {
+                command: "RasterImage"
+            })
+        );
+        assert_eq!(
+            printer.print_image_columns(&image, crate::BitImageMode::SingleDensity8),
+            Err(PrinterError::Cancelled {
+                command: "BitImage"
+            })
+        );
+        assert!(printer.transport().written().is_empty());
+    }
+
+    #[test]
+    fn test_bytes_veto_after_chunk() {
+        let hooks = Hooks::new().on_bytes(|bytes| !bytes.contains(&b'!'));
+        let mut printer = Printer::builder(MockTransport::new())
+            .chunk_size(4)
+            .hooks(hooks)
+            .build()
+            .unwrap();
+        assert_eq!(
+            printer.write("abcd!efgh"),
+            Err(PrinterError::Cancelled { command: "Text" })
+        );
+        assert_eq!(printer.transport().written(), b"abcd");
+    }
+}
@@ -0,0 +1,378 @@
+use core::convert::Infallible;
+
+use super::Delay;
+use super::{
+    Align, AsbFlags, BitImageScale, BorderStyle, Charset, Clock, CodePage, Color, Command, CutMode,
+    Density, Font, Justification, LabelStock, LatencyReport, MacroMode, MarkPosition, MicrLine,
+    NearEndPolicy, PaperSensors, PrintSpeed, Printer, PrinterError, PrinterIdKind, Profile, Read,
+    Scale, Stations, Status, StatusEvent, StatusRequest, StatusWatcher, TestPattern, UnderlineMode,
+    Write,
+};
+#[cfg(feature = "image")]
+use super::{BitImageMode, DiagnosticPattern, GrayImage, Image, TimingModel, TwoColorImage};
+
+/// Borrowed view of a [`Printer`] whose transport cannot fail.
+///
+/// Returned by [`Printer::infallible`]. Each method mirrors the one on
+/// `Printer` but returns its value directly instead of a `Result`, which is
+/// convenient for in-memory buffers and mocks.
+///
+/// Transport errors can't happen, but crate-level errors such as
+/// [`PrinterError::ImageSizeMismatch`] still can; they panic here. Call the
+/// `Printer` method directly to handle them instead.
+pub struct InfalliblePrinter<'a, T: Write, P: Delay = ()> {
+    printer: &'a mut Printer<T, P>,
+}
+
+#[track_caller]
+fn into_ok<V>(result: Result<V, PrinterError<Infallible>>) -> V {
+    match result {
+        Ok(value) => value,
+        Err(PrinterError::Transport { error, .. }) => match error {},
+        Err(err) => panic!("{:?}", err),
+    }
+}
+
+macro_rules! forward {
+    ($($(#[$meta:meta])* fn $name:ident(&mut self $(, $arg:ident: $ty:ty)*) $(-> $ret:ty)?;)*) => {
+        $(
+            $(#[$meta])*
+            pub fn $name(&mut self $(, $arg: $ty)*) $(-> $ret)? {
+                into_ok(self.printer.$name($($arg),*))
+            }
+        )*
+    };
+}
+
+impl<T, P: Delay> Printer<T, P>
+where
+    T: Write<Error = Infallible>,
+{
+    /// Borrow the printer through an API that doesn't return `Result`s.
+    pub fn infallible(&mut self) -> InfalliblePrinter<'_, T, P> {
+        InfalliblePrinter { printer: self }
+    }
+}
+
+impl<T, P: Delay> InfalliblePrinter<'_, T, P>
+where
+    T: Write<Error = Infallible>,
+{
+    forward! {
+        /// Encode and send a single command.
+        fn send(&mut self, command: &Command);
+        /// Reset the printer to its power-on settings (`ESC @`).
+        fn initialize(&mut self);
+        /// Select the character code table used for bytes 0x80–0xFF.
+        fn set_code_page(&mut self, code_page: CodePage);
+        /// Select the international character set (`ESC R`).
+        fn set_charset(&mut self, charset: Charset);
+        /// Set the line spacing to `units` motion units (`ESC 3`).
+        fn set_line_spacing(&mut self, units: u8);
+        /// Return to the default line spacing (`ESC 2`).
+        fn reset_line_spacing(&mut self);
+        /// Send the startup sequence the printer was created with.
+        fn reinitialize(&mut self);
+        /// Replace the transport and send the startup sequence over it.
+        fn reconnect(&mut self, transport: T);
+        /// Write raw text to the printer.
+        fn write(&mut self, text: &str);
+        /// Write text followed by a newline.
+        fn write_line(&mut self, text: &str);
+        /// Write text word-wrapped to the paper width.
+        fn write_wrapped(&mut self, text: &str);
+        /// Print a small subset of Markdown with printer styling.
+        fn print_markdown(&mut self, text: &str);
+        /// Print a horizontal line across the paper in `style`.
+        fn write_rule(&mut self, style: BorderStyle);
+        /// Discard the text of the current line in page mode (`CAN`).
+        fn cancel_line(&mut self);
+        /// Discard the partly composed line and move back to its start
+        /// (`GS T 0`).
+        fn to_line_start(&mut self);
+        /// Feed the specified number of lines.
+        fn feed(&mut self, lines: u16);
+        /// Print the buffer and feed the paper by `mm` millimetres (`ESC J`).
+        fn feed_paper_mm(&mut self, mm: u16);
+        /// Cut the paper using the given mode.
+        fn cut(&mut self, mode: CutMode);
+        /// Cut, then feed backwards by `units` motion units.
+        fn cut_and_reverse_feed(&mut self, mode: CutMode, units: u8);
+        /// Feed to the cutting position, plus `feed` motion units, and cut.
+        fn cut_with_feed(&mut self, mode: CutMode, feed: u8);
+        /// Enable or disable bold mode.
+        fn set_bold(&mut self, on: bool);
+        /// Set underline mode.
+        fn set_underline(&mut self, mode: UnderlineMode);
+        /// Set text alignment.
+        fn set_align(&mut self, align: Align);
+        /// Select printer font.
+        fn set_font(&mut self, font: Font);
+        /// Set character size using width and height multipliers.
+        fn set_size(&mut self, width: u8, height: u8);
+        /// Set character size to `width`x by `height`x, each 1–8.
+        fn try_set_size(&mut self, width: u8, height: u8);
+        /// Set character size to `width` by `height` times normal.
+        fn set_scale(&mut self, width: Scale, height: Scale);
+        /// Print characters twice as wide, at normal height.
+        fn set_double_width(&mut self);
+        /// Print characters twice as tall, at normal width.
+        fn set_double_height(&mut self);
+        /// Print characters twice as wide and twice as tall.
+        fn set_quad_size(&mut self);
+        /// Return to normal character size.
+        fn reset_size(&mut self);
+        /// Enable or disable inverted printing.
+        fn set_invert(&mut self, on: bool);
+        /// Select the print color on two-color printers (`ESC r`).
+        fn set_color(&mut self, color: Color);
+        /// Print the downloaded bit image (`GS /`).
+        fn print_downloaded_image(&mut self, scale: BitImageScale);
+        /// Enable or disable upside-down printing (`ESC {`).
+        fn set_upside_down(&mut self, on: bool);
+        /// Set text justification.
+        fn set_justification(&mut self, mode: Justification);
+        /// Set print density level.
+        fn set_density(&mut self, level: Density);
+        /// Set print speed.
+        fn set_print_speed(&mut self, speed: PrintSpeed);
+        /// Set the serial baud rate used by the printer.
+        fn set_baud_rate(&mut self, baud: u32);
+        /// Configure the maximum print speed of the printer.
+        fn set_max_speed(&mut self, speed: u8);
+        /// Enable or disable software flow control (XON/XOFF).
+        fn set_software_flow_control(&mut self, enable: bool);
+        /// Enable or disable black mark detection.
+        fn set_black_mark(&mut self, on: bool);
+        /// Feed marked paper to the print start position (`GS FF`).
+        fn feed_to_mark(&mut self);
+        /// Print and feed to the next mark's print start position (`FF`).
+        fn print_and_feed_to_mark(&mut self);
+        /// Enable or disable the label peeler (`FS ( L D`).
+        fn set_peeler(&mut self, on: bool);
+        /// Print the model's self-test page.
+        fn print_self_test(&mut self);
+        /// Put the printer into hexadecimal dump mode.
+        fn enter_hex_dump(&mut self);
+        /// Print a test pattern (`GS ( A`).
+        fn test_print(&mut self, pattern: TestPattern);
+        /// Store `data` in NV user memory at `address`.
+        fn write_user_memory(&mut self, address: u32, data: &[u8]);
+        /// Put the printer to sleep after `seconds` idle, or never with 0.
+        fn set_sleep_timeout(&mut self, seconds: u16);
+        /// Leave user setting mode, resetting the printer.
+        fn exit_user_setting(&mut self);
+        /// Change the bits in `mask` of memory switch `switch` to `value`.
+        fn set_memory_switch(&mut self, switch: u8, mask: u8, value: u8);
+        /// Store the default print density (-6 to 6) in the printer.
+        fn store_density(&mut self, level: i8);
+        /// Store the default print speed level (1 to 13) in the printer.
+        fn store_print_speed(&mut self, speed: u8);
+        /// Select the paper sensors that signal paper end (`ESC c 3`).
+        fn set_paper_signal_sensors(&mut self, sensors: PaperSensors);
+        /// Select the paper sensors that stop printing (`ESC c 4`).
+        fn set_paper_stop_sensors(&mut self, sensors: PaperSensors);
+        /// Print on `stations` of a hybrid printer from now on.
+        fn select_station(&mut self, stations: Stations);
+        /// Set the slip insertion timeout and the delay before printing.
+        fn set_slip_wait(&mut self, minutes: u8, delay: u8);
+        /// Release the slip or validation sheet (`ESC q`).
+        fn eject_slip(&mut self);
+        /// Eject the check from the MICR reader (`FS a 2`).
+        fn eject_check(&mut self);
+        /// Program the paper sensors for `policy`.
+        fn set_near_end_policy(&mut self, policy: NearEndPolicy);
+        /// Select label stock and label length in 0.1mm (`FS ( L`).
+        fn set_label_layout(&mut self, stock: LabelStock, length: u16);
+        /// Feed to the next label's print start position.
+        fn feed_to_label(&mut self);
+        /// Feed the current label to the cutting position.
+        fn feed_label_to_cut(&mut self);
+        /// Shift the print start or cut position relative to the black mark.
+        fn set_mark_offset(&mut self, position: MarkPosition, offset: i16);
+        /// Ask the printer to reply with process ID `id` once it has
+        /// processed everything sent before (`GS ( H`).
+        fn request_process_id(&mut self, id: u16);
+        /// Select which status changes the printer reports automatically.
+        fn set_auto_status_back(&mut self, flags: AsbFlags);
+        /// Start recording a macro in the printer (`GS :`).
+        fn start_macro(&mut self);
+        /// Stop recording a macro (`GS :`).
+        fn end_macro(&mut self);
+        /// Run the stored macro `times` times (`GS ^ r t m`).
+        fn execute_macro(&mut self, times: u8, wait: u8, mode: MacroMode);
+        /// Send raw bytes directly to the printer.
+        fn raw(&mut self, data: &[u8]);
+        /// Send a captured ESC/POS stream.
+        fn replay(&mut self, data: &[u8], validate: bool);
+    }
+
+    #[cfg(feature = "image")]
+    /// Print a black & white image using ESC/POS raster format.
+    pub fn print_image<D>(&mut self, image: &Image<D>)
+    where
+        D: AsRef<[u8]>,
+    {
+        into_ok(self.printer.print_image(image))
+    }
+
+    #[cfg(feature = "image")]
+    /// Print a black & white image with the printer scaling it up.
+    pub fn print_image_scaled<D>(&mut self, image: &Image<D>, scale: BitImageScale)
+    where
+        D: AsRef<[u8]>,
+    {
+        into_ok(self.printer.print_image_scaled(image, scale))
+    }
+
+    #[cfg(feature = "image")]
+    /// Print a black & white image as column-format bit images (`ESC *`).
+    pub fn print_image_columns<D>(&mut self, image: &Image<D>, mode: BitImageMode)
+    where
+        D: AsRef<[u8]>,
+    {
+        into_ok(self.printer.print_image_columns(image, mode))
+    }
+
+    #[cfg(feature = "image")]
+    /// Print a software test pattern across the full paper width.
+    pub fn print_test_pattern(&mut self, pattern: DiagnosticPattern, height: u16) {
+        into_ok(self.printer.print_test_pattern(pattern, height))
+    }
+
+    #[cfg(feature = "image")]
+    /// Store `image` as the downloaded bit image in RAM (`GS *`).
+    pub fn define_downloaded_image<D>(&mut self, image: &Image<D>)
+    where
+        D: AsRef<[u8]>,
+    {
+        into_ok(self.printer.define_downloaded_image(image))
+    }
+
+    #[cfg(feature = "image")]
+    /// Print an image while pausing between chunks according to a timing model.
+    pub fn print_image_with_delay<D, Del>(
+        &mut self,
+        image: &Image<D>,
+        model: &TimingModel,
+        delay: &mut Del,
+    ) where
+        D: AsRef<[u8]>,
+        Del: Delay,
+    {
+        into_ok(self.printer.print_image_with_delay(image, model, delay))
+    }
+
+    #[cfg(feature = "image")]
+    /// Print a 4-level grayscale image by printing it several times.
+    pub fn print_image_gray<D>(&mut self, image: &GrayImage<D>)
+    where
+        D: AsRef<[u8]>,
+    {
+        into_ok(self.printer.print_image_gray(image))
+    }
+
+    #[cfg(feature = "image")]
+    /// Print a black and red image on a two-color printer.
+    pub fn print_image_two_color<D>(&mut self, image: &TwoColorImage<D>)
+    where
+        D: AsRef<[u8]>,
+    {
+        into_ok(self.printer.print_image_two_color(image))
+    }
+
+    /// Print formatted text without allocating.
+    pub fn print_fmt(&mut self, args: core::fmt::Arguments<'_>) {
+        into_ok(self.printer.print_fmt(args))
+    }
+
+    /// Print the time read from `clock`, formatted with `pattern`, followed
+    /// by the configured line ending.
+    pub fn print_timestamp<C: Clock>(&mut self, clock: &mut C, pattern: &str) {
+        into_ok(self.printer.print_timestamp(clock, pattern))
+    }
+
+    /// Print each of `lines` followed by the configured line ending.
+    pub fn print_lines<'b>(&mut self, lines: impl IntoIterator<Item = &'b str>) {
+        into_ok(self.printer.print_lines(lines))
+    }
+
+    /// Wake a sleeping printer, then wait `ms` milliseconds.
+    pub fn wake<D: Delay>(&mut self, delay: &mut D, ms: u32) {
+        into_ok(self.printer.wake(delay, ms))
+    }
+}
+
+impl<T, P: Delay> InfalliblePrinter<'_, T, P>
+where
+    T: Write<Error = Infallible> + Read<Error = Infallible>,
+{
+    forward! {
+        /// Query the paper sensor status using `GS r 1`.
+        fn paper_status(&mut self) -> u8;
+        /// Query a single real-time status byte using `DLE EOT n`.
+        fn transmit_status(&mut self, request: StatusRequest) -> u8;
+        /// Query all four real-time status bytes and decode them.
+        fn status(&mut self) -> Status;
+        /// Whether the roll paper is nearly used up (`DLE EOT 4`).
+        fn paper_near_end(&mut self) -> bool;
+        /// Query a printer ID byte using `GS I n`.
+        fn printer_id(&mut self, kind: PrinterIdKind) -> u8;
+        /// Read a pending Automatic Status Back report, if any.
+        fn read_auto_status(&mut self) -> Option<Status>;
+        /// Read the MICR line of the check in the slip station.
+        fn read_check(&mut self) -> MicrLine;
+        /// Identify the printer with `GS I` and switch to a matching profile.
+        fn detect_profile(&mut self) -> Profile;
+        /// Like `detect_profile`, looking the model ID up in `models` first.
+        fn detect_profile_with(&mut self, models: &[(u8, Profile)]) -> Profile;
+        /// Whether a peeled label is waiting to be taken (`DLE EOT 8 3`).
+        fn label_waiting(&mut self) -> bool;
+        /// Enter user setting mode, waiting for the acknowledgement.
+        fn enter_user_setting(&mut self);
+        /// Read memory switch `switch` (1–8).
+        fn memory_switch(&mut self, switch: u8) -> u8;
+        /// Fill `buf` from NV user memory starting at `address`.
+        fn read_user_memory(&mut self, address: u32, buf: &mut [u8]);
+        /// Read the stored print density.
+        fn stored_density(&mut self) -> i8;
+        /// Read the stored print speed level.
+        fn stored_print_speed(&mut self) -> u8;
+    }
+
+    /// Read every pending Automatic Status Back report, reporting changes
+    /// through `watcher`.
+    pub fn poll_status_events(
+        &mut self,
+        watcher: &mut StatusWatcher,
+        on_event: impl FnMut(StatusEvent),
+    ) -> Option<Status> {
+        into_ok(self.printer.poll_status_events(watcher, on_event))
+    }
+
+    /// Wait until the peeled label has been taken, polling every `poll_ms`
+    /// milliseconds at most `max_polls` times.
+    pub fn wait_label_taken<D: Delay>(
+        &mut self,
+        delay: &mut D,
+        poll_ms: u32,
+        max_polls: u32,
+    ) -> bool {
+        into_ok(self.printer.wait_label_taken(delay, poll_ms, max_polls))
+    }
+
+    /// Time `rounds` printer status round trips.
+    pub fn measure_status_latency(
+        &mut self,
+        millis: impl FnMut() -> u32,
+        rounds: u8,
+    ) -> LatencyReport {
+        into_ok(self.printer.measure_status_latency(millis, rounds))
+    }
+
+    /// Wait for the reply to `request_process_id` with `id`, passing any
+    /// Automatic Status Back packets received first to `on_status`.
+    pub fn wait_process_id(&mut self, id: u16, on_status: impl FnMut(Status)) {
+        into_ok(self.printer.wait_process_id(id, on_status))
+    }
+}
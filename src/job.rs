@@ -0,0 +1,1079 @@
+use core::convert::Infallible;
+use core::ops::{Deref, DerefMut};
+#[cfg(feature = "async")]
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use super::{
+    command, Align, BitImageScale, Command, Decoder, Delay, Printer, PrinterError, Read, Recorder,
+    Stations, Write,
+};
+#[cfg(feature = "async")]
+use super::{AsyncPrinter, AsyncWrite};
+
+/// A receipt built up front and sent to the printer in one call.
+///
+/// A job is a [`Printer`] writing into a fixed-capacity [`Recorder`], so all
+/// the usual printer methods are available while building it. Nothing
+/// reaches the real printer until [`submit`](Job::submit), which writes the
+/// whole receipt while holding the printer, so a half-built receipt is never
+/// interleaved with other output.
+///
+/// The receipt is written one command at a time and the job remembers how
+/// much got through. If the transport fails part-way, fix the link and call
+/// [`resume`](Job::resume) to carry on from the start of the command that
+/// failed instead of reprinting the whole receipt.
+///
+/// ```
+/// # use escpos_embedded::{CutMode, Job, Printer, Write, PrinterError};
+/// # fn print<T: Write>(printer: &mut Printer<T>) -> Result<(), PrinterError<T::Error>> {
+/// let mut job = Job::<256>::for_printer(printer);
+/// job.write_line("Table 4").unwrap();
+/// job.cut(CutMode::Partial).unwrap();
+/// job.submit(printer)
+/// # }
+/// ```
+pub struct Job<const N: usize> {
+    printer: Printer<Recorder<N>>,
+    sent: usize,
+    elapsed_ms: Option<u32>,
+}
+
+impl<const N: usize> Job<N> {
+    /// Create an empty job using the default profile.
+    pub fn new() -> Self {
+        Self {
+            printer: Printer::new(Recorder::new()),
+            sent: 0,
+            elapsed_ms: None,
+        }
+    }
+
+    /// Create an empty job sharing `printer`'s settings, so text wraps and
+    /// commands degrade as they would on the printer.
+    ///
+    /// Copies the profile, paper width, code page, font and character
+    /// width, cut fallback, [`Degradation`](crate::Degradation) policy,
+    /// line ending and, with the `image` feature, image fit.
+    pub fn for_printer<T: Write, P: Delay>(printer: &Printer<T, P>) -> Self {
+        let mut job = Self::new();
+        job.printer.copy_settings(printer);
+        job
+    }
+
+    /// The encoded receipt.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.printer.transport.as_bytes()
+    }
+
+    /// Number of bytes the job will send.
+    pub fn byte_len(&self) -> usize {
+        self.as_bytes().len()
+    }
+
+    /// Estimated time in milliseconds to send the job over a serial link at
+    /// `baud`, assuming 8N1 framing (10 bits per byte) and no flow control
+    /// pauses.
+    ///
+    /// Useful for warning about slow receipts, e.g. large logos on a
+    /// 9600-baud link.
+    pub fn transmit_ms(&self, baud: u32) -> u32 {
+        let bits = self.byte_len() as u64 * 10;
+        let ms = (bits * 1000).div_ceil(u64::from(baud.max(1)));
+        ms.min(u64::from(u32::MAX)) as u32
+    }
+
+    /// Discard everything added so far.
+    pub fn clear(&mut self) {
+        self.printer.transport.clear();
+        self.sent = 0;
+        self.elapsed_ms = None;
+    }
+
+    /// Number of bytes written successfully by the last submission.
+    ///
+    /// Always falls on a command boundary.
+    pub fn sent(&self) -> usize {
+        self.sent
+    }
+
+    /// Whether the whole job has been sent.
+    pub fn is_complete(&self) -> bool {
+        self.sent == self.as_bytes().len()
+    }
+
+    /// Statistics for the part of the job sent by the last submission.
+    ///
+    /// ```
+    /// # use escpos_embedded::{CutMode, Job, Printer, Recorder};
+    /// let mut printer = Printer::new(Recorder::<64>::new());
+    /// let mut job = Job::<64>::new();
+    /// job.write_line("Total 4.20").unwrap();
+    /// job.cut(CutMode::Full).unwrap();
+    /// job.submit(&mut printer).unwrap();
+    /// let stats = job.stats();
+    /// assert_eq!((stats.bytes, stats.commands), (14, 3));
+    /// ```
+    pub fn stats(&self) -> JobStats {
+        let mut stats = JobStats {
+            bytes: self.sent,
+            elapsed_ms: self.elapsed_ms,
+            ..JobStats::default()
+        };
+        for command in Decoder::new(&self.as_bytes()[..self.sent]) {
+            stats.commands += 1;
+            if matches!(
+                command,
+                Command::RasterImage { .. }
+                    | Command::BitImage { .. }
+                    | Command::StoreGraphics { .. }
+                    | Command::DefineDownloadedImage { .. }
+            ) {
+                stats.images += 1;
+            }
+        }
+        stats
+    }
+
+    /// Like [`submit`](Job::submit), also recording how long sending took.
+    ///
+    /// `millis` reads a millisecond counter, e.g. a hardware timer; it is
+    /// called before and after sending and may wrap. The time is reported
+    /// by [`stats`](Job::stats), even if sending failed.
+    pub fn submit_timed<T: Write, P: Delay>(
+        &mut self,
+        printer: &mut Printer<T, P>,
+        mut millis: impl FnMut() -> u32,
+    ) -> Result<(), PrinterError<T::Error>> {
+        let start = millis();
+        let result = self.submit(printer);
+        self.elapsed_ms = Some(millis().wrapping_sub(start));
+        result
+    }
+
+    /// Send the whole job to `printer`.
+    ///
+    /// The job is sent one command at a time, as by [`Printer::replay`],
+    /// so the printer's hooks, chunking and pacing apply to each of them.
+    /// The job is kept, so it can be submitted again.
+    pub fn submit<T: Write, P: Delay>(
+        &mut self,
+        printer: &mut Printer<T, P>,
+    ) -> Result<(), PrinterError<T::Error>> {
+        self.sent = 0;
+        self.elapsed_ms = None;
+        self.resume(printer)
+    }
+
+    /// Send the whole job to `stations` of a hybrid printer, e.g. the
+    /// journal as well as the receipt.
+    ///
+    /// The stations are selected with [`Printer::select_station`] first
+    /// and stay selected afterwards.
+    pub fn submit_to<T: Write, P: Delay>(
+        &mut self,
+        printer: &mut Printer<T, P>,
+        stations: Stations,
+    ) -> Result<(), PrinterError<T::Error>> {
+        printer.select_station(stations)?;
+        self.submit(printer)
+    }
+
+    /// Send the part of the job not yet written by the last submission.
+    ///
+    /// Does nothing if the job is complete.
+    pub fn resume<T: Write, P: Delay>(
+        &mut self,
+        printer: &mut Printer<T, P>,
+    ) -> Result<(), PrinterError<T::Error>> {
+        let rest = &self.printer.transport.as_bytes()[self.sent..];
+        let mut decoder = Decoder::new(rest);
+        let mut start = 0;
+        while let Some(command) = decoder.next() {
+            let end = decoder.position();
+            printer.send_with(&command, |out| out.write(&rest[start..end]))?;
+            self.sent += end - start;
+            start = end;
+        }
+        Ok(())
+    }
+
+    /// Send the whole job to an async printer, stopping early if `cancel`
+    /// is set.
+    ///
+    /// `cancel` is checked before every command. When it is set, the
+    /// printer's buffers are cleared with the real-time `DLE DC4 8` command,
+    /// so data already sent is discarded too, and
+    /// [`PrinterError::Cancelled`] is returned.
+    #[cfg(feature = "async")]
+    pub async fn submit_async<T: AsyncWrite>(
+        &mut self,
+        printer: &mut AsyncPrinter<T>,
+        cancel: &AtomicBool,
+    ) -> Result<(), PrinterError<T::Error>> {
+        self.sent = 0;
+        self.elapsed_ms = None;
+        self.resume_async(printer, cancel).await
+    }
+
+    /// Async counterpart of [`resume`](Job::resume), with cancellation as in
+    /// [`submit_async`](Job::submit_async).
+    #[cfg(feature = "async")]
+    pub async fn resume_async<T: AsyncWrite>(
+        &mut self,
+        printer: &mut AsyncPrinter<T>,
+        cancel: &AtomicBool,
+    ) -> Result<(), PrinterError<T::Error>> {
+        let rest = &self.printer.transport.as_bytes()[self.sent..];
+        let mut decoder = Decoder::new(rest);
+        let mut start = 0;
+        while let Some(command) = decoder.next() {
+            if cancel.load(Ordering::Acquire) {
+                printer.send(&Command::ClearBuffers).await?;
+                return Err(PrinterError::Cancelled { command: "Job" });
+            }
+            let end = decoder.position();
+            printer
+                .raw(&rest[start..end])
+                .await
+                .map_err(|err| rename(err, command.name()))?;
+            self.sent += end - start;
+            start = end;
+        }
+        Ok(())
+    }
+
+    /// Send the job rotated 180°, for printers facing the customer.
+    ///
+    /// Lines are printed upside down (`ESC {`) in reverse order and raster
+    /// images are rotated, so the receipt reads the right way up as it comes
+    /// out. Everything from the first cut on is sent last, unchanged.
+    ///
+    /// Each line is preceded by `ESC @` and the style commands in effect
+    /// where it was written (alignment, bold, size, code page, ...), so it
+    /// prints as it would have. Settings written as raw bytes aren't
+    /// replayed.
+    ///
+    /// The job can't be [resumed](Job::resume) part-way; after an error,
+    /// submit it again.
+    pub fn submit_upside_down<T: Write, P: Delay>(
+        &mut self,
+        printer: &mut Printer<T, P>,
+    ) -> Result<(), PrinterError<T::Error>> {
+        self.sent = 0;
+        self.elapsed_ms = None;
+        let bytes = self.printer.transport.as_bytes();
+        let body_end = body_end(bytes);
+        let body = &bytes[..body_end];
+        let mut end = body.len();
+        while end > 0 {
+            let (start, styles) = line_start(body, end);
+            printer.send(&Command::Initialize)?;
+            styles.replay(printer)?;
+            printer.send(&Command::SetUpsideDown(true))?;
+            send_rotated_line(printer, &body[start..end])?;
+            end = start;
+        }
+        printer.send(&Command::SetUpsideDown(false))?;
+        send_commands(printer, &bytes[body_end..])?;
+        self.sent = bytes.len();
+        Ok(())
+    }
+
+    /// Send the job once per entry in `copies`, each with its own banner and
+    /// signature line.
+    ///
+    /// The recorded bytes are reused, so the receipt doesn't have to be
+    /// built again. A banner is printed centered and bold above the
+    /// receipt; a signature line is added before the first cut, or at the
+    /// end if the job doesn't cut. The job should end with a cut so the
+    /// copies come out separately.
+    ///
+    /// ```
+    /// # use escpos_embedded::{CutMode, Job, Printer, PrinterError, ReceiptCopy, Write};
+    /// # fn print<T: Write>(printer: &mut Printer<T>) -> Result<(), PrinterError<T::Error>> {
+    /// let mut job = Job::<256>::for_printer(printer);
+    /// job.write_line("Card payment  12.50").unwrap();
+    /// job.cut(CutMode::Partial).unwrap();
+    /// job.submit_copies(printer, &[ReceiptCopy::CUSTOMER, ReceiptCopy::MERCHANT])
+    /// # }
+    /// ```
+    ///
+    /// The job can't be [resumed](Job::resume) part-way; after an error,
+    /// submit it again.
+    pub fn submit_copies<T: Write, P: Delay>(
+        &mut self,
+        printer: &mut Printer<T, P>,
+        copies: &[ReceiptCopy<'_>],
+    ) -> Result<(), PrinterError<T::Error>> {
+        self.sent = 0;
+        self.elapsed_ms = None;
+        let columns = self.printer.columns();
+        let bytes = self.printer.transport.as_bytes();
+        let body_end = body_end(bytes);
+        for copy in copies {
+            if let Some(banner) = copy.banner {
+                printer.set_align(Align::Center)?;
+                printer.set_bold(true)?;
+                printer.write_line(banner)?;
+                printer.set_bold(false)?;
+                printer.set_align(Align::Left)?;
+            }
+            send_commands(printer, &bytes[..body_end])?;
+            if copy.signature {
+                printer.feed(1)?;
+                printer.write("X")?;
+                let mut left = columns.saturating_sub(1);
+                while left > 0 {
+                    let n = left.min(SIGNATURE_RULE.len());
+                    printer.write(&SIGNATURE_RULE[..n])?;
+                    left -= n;
+                }
+                printer.write_line("")?;
+            }
+            send_commands(printer, &bytes[body_end..])?;
+        }
+        self.sent = bytes.len();
+        Ok(())
+    }
+
+    /// Check the job against its profile and a maximum size.
+    ///
+    /// Yields every problem found, in order:
+    ///
+    /// - the job is longer than `max_len` bytes (e.g. the printer's receive
+    ///   buffer);
+    /// - a raster image is wider than the paper;
+    /// - a cut is requested but the profile has no cutter;
+    /// - a size multiplier written as raw bytes is above 7;
+    /// - any other parameter fails [`Command::validate`].
+    pub fn problems(&self, max_len: usize) -> Problems<'_> {
+        let len = self.as_bytes().len();
+        let oversize = (len > max_len).then(|| Problem {
+            offset: 0,
+            error: PrinterError::InvalidParameter {
+                command: "Job",
+                value: saturate(len),
+                range: 0..=saturate(max_len),
+            },
+        });
+        Problems {
+            decoder: Decoder::new(self.as_bytes()),
+            dots: self.printer.paper_width.dots(),
+            has_cutter: self.printer.profile.has_cutter,
+            pending: oversize,
+        }
+    }
+
+    /// Send the job only if [`problems`](Job::problems) finds none.
+    ///
+    /// Nothing is sent on failure; the first problem is returned.
+    pub fn submit_validated<T: Write, P: Delay>(
+        &mut self,
+        printer: &mut Printer<T, P>,
+        max_len: usize,
+    ) -> Result<(), PrinterError<T::Error>> {
+        if let Some(problem) = self.problems(max_len).next() {
+            return Err(problem.error.map_transport(|never| match never {}));
+        }
+        self.submit(printer)
+    }
+
+    /// Check the printer's status, then send the job if it is ready.
+    ///
+    /// Returns [`PrinterError::NotReady`] without sending anything if the
+    /// printer is offline, out of paper or in an error state.
+    pub fn submit_checked<T, P: Delay>(
+        &mut self,
+        printer: &mut Printer<T, P>,
+    ) -> Result<(), PrinterError<<T as Write>::Error>>
+    where
+        T: Write + Read<Error = <T as Write>::Error>,
+    {
+        let status = printer.status()?;
+        if !status.is_ready() {
+            return Err(PrinterError::NotReady {
+                command: "Job",
+                status,
+            });
+        }
+        self.submit(printer)
+    }
+}
+
+/// One copy printed by [`Job::submit_copies`].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct ReceiptCopy<'a> {
+    banner: Option<&'a str>,
+    signature: bool,
+}
+
+impl<'a> ReceiptCopy<'a> {
+    /// The customer's copy: a "CUSTOMER COPY" banner.
+    pub const CUSTOMER: ReceiptCopy<'static> = ReceiptCopy::new().banner("CUSTOMER COPY");
+    /// The merchant's copy: a "MERCHANT COPY" banner and a signature line.
+    pub const MERCHANT: ReceiptCopy<'static> =
+        ReceiptCopy::new().banner("MERCHANT COPY").signature(true);
+
+    /// The receipt exactly as recorded.
+    pub const fn new() -> Self {
+        Self {
+            banner: None,
+            signature: false,
+        }
+    }
+
+    /// Print `text` above the receipt.
+    pub const fn banner(mut self, text: &'a str) -> Self {
+        self.banner = Some(text);
+        self
+    }
+
+    /// Add a line for the customer's signature at the end of the receipt.
+    pub const fn signature(mut self, on: bool) -> Self {
+        self.signature = on;
+        self
+    }
+}
+
+const SIGNATURE_RULE: &str = "________________________________________________________________";
+
+/// Offset of the first cut in `bytes`, or its length if there is none.
+fn body_end(bytes: &[u8]) -> usize {
+    let mut decoder = Decoder::new(bytes);
+    let mut end = 0;
+    while let Some(command) = decoder.next() {
+        if matches!(
+            command,
+            Command::Cut(_) | Command::FeedAndCut { .. } | Command::FeedLabelToCut
+        ) {
+            break;
+        }
+        end = decoder.position();
+    }
+    end
+}
+
+/// Send recorded `bytes` one command at a time.
+fn send_commands<T: Write, P: Delay>(
+    printer: &mut Printer<T, P>,
+    bytes: &[u8],
+) -> Result<(), PrinterError<T::Error>> {
+    let mut decoder = Decoder::new(bytes);
+    let mut start = 0;
+    while let Some(command) = decoder.next() {
+        let end = decoder.position();
+        printer.send_with(&command, |out| out.write(&bytes[start..end]))?;
+        start = end;
+    }
+    Ok(())
+}
+
+/// Attribute a transport error from sending raw job bytes to `command`.
+#[cfg(feature = "async")]
+fn rename<E>(err: PrinterError<E>, command: &'static str) -> PrinterError<E> {
+    match err {
+        PrinterError::Transport { error, .. } => PrinterError::Transport { command, error },
+        other => other,
+    }
+}
+
+/// Commands that end a printed line of a job.
+fn ends_line(command: &Command) -> bool {
+    matches!(
+        command,
+        Command::LineFeed
+            | Command::CarriageReturn
+            | Command::Feed(_)
+            | Command::FeedUnits(_)
+            | Command::PrintAndFeedToMark
+            | Command::RasterImage { .. }
+    )
+}
+
+/// Number of kinds of style command tracked by [`Styles`].
+const STYLES: usize = 11;
+
+/// Slot in [`Styles`] for commands whose effect lasts across lines until
+/// changed or reset. Commands that override each other share a slot.
+fn style_slot(command: &Command) -> Option<usize> {
+    Some(match command {
+        Command::SetBold(_) => 0,
+        Command::SetUnderline(_) => 1,
+        Command::SetAlign(_) | Command::SetJustification(_) => 2,
+        Command::SetFont(_) => 3,
+        Command::SetSize { .. } => 4,
+        Command::SetInvert(_) => 5,
+        Command::SelectCodePage(_) => 6,
+        Command::SetCharset(_) => 7,
+        Command::SetLineSpacing(_) | Command::DefaultLineSpacing => 8,
+        Command::SetDensity(_) => 9,
+        Command::SetPrintSpeed(_) => 10,
+        _ => return None,
+    })
+}
+
+/// The style commands in effect at some point of a job, with their bytes.
+#[derive(Copy, Clone)]
+struct Styles<'a> {
+    latest: [Option<(usize, Command<'a>, &'a [u8])>; STYLES],
+}
+
+impl<'a> Styles<'a> {
+    const NONE: Self = Self {
+        latest: [None; STYLES],
+    };
+
+    /// Track `command`, found at `offset` and encoded as `bytes`.
+    fn apply(&mut self, offset: usize, command: Command<'a>, bytes: &'a [u8]) {
+        if command == Command::Initialize {
+            *self = Self::NONE;
+        } else if let Some(slot) = style_slot(&command) {
+            self.latest[slot] = Some((offset, command, bytes));
+        }
+    }
+
+    /// Resend the styles in the order they were written.
+    fn replay<T: Write, P: Delay>(
+        &self,
+        printer: &mut Printer<T, P>,
+    ) -> Result<(), PrinterError<T::Error>> {
+        let mut latest = self.latest;
+        latest.sort_unstable_by_key(|style| style.map(|(offset, ..)| offset));
+        for (_, command, bytes) in latest.into_iter().flatten() {
+            printer.send_with(&command, |out| out.write(bytes))?;
+        }
+        Ok(())
+    }
+}
+
+/// Start of the line in `body` that ends at `end`, and the styles in effect
+/// there, found in a single pass.
+fn line_start(body: &[u8], end: usize) -> (usize, Styles<'_>) {
+    let mut decoder = Decoder::new(&body[..end]);
+    let mut styles = Styles::NONE;
+    let mut start = (0, styles);
+    loop {
+        let offset = decoder.position();
+        let Some(command) = decoder.next() else {
+            return start;
+        };
+        let next = decoder.position();
+        styles.apply(offset, command, &body[offset..next]);
+        // In a CR LF ending, the LF ends the line.
+        let before_lf = command == Command::CarriageReturn && body.get(next) == Some(&b'\n');
+        if ends_line(&command) && !before_lf && next < end {
+            start = (next, styles);
+        }
+    }
+}
+
+/// Send one line of a job with upside-down printing on, rotating images.
+fn send_rotated_line<T: Write, P: Delay>(
+    printer: &mut Printer<T, P>,
+    line: &[u8],
+) -> Result<(), PrinterError<T::Error>> {
+    let mut decoder = Decoder::new(line);
+    let mut start = 0;
+    while let Some(command) = decoder.next() {
+        let end = decoder.position();
+        match command {
+            Command::RasterImage {
+                width,
+                height,
+                scale,
+                data,
+            } => printer.send_with(&command, |out| {
+                write_rotated_image(out, width, height, scale, data)
+            })?,
+            _ => printer.send_with(&command, |out| out.write(&line[start..end]))?,
+        }
+        if command == Command::Initialize {
+            printer.send(&Command::SetUpsideDown(true))?;
+        }
+        start = end;
+    }
+    Ok(())
+}
+
+/// Write a raster image turned through 180°.
+///
+/// Decoded images are a whole number of bytes wide, so each row is simply
+/// its bytes in reverse order with their bits reversed.
+fn write_rotated_image<W: Write + ?Sized>(
+    out: &mut W,
+    width: u16,
+    height: u16,
+    scale: BitImageScale,
+    data: &[u8],
+) -> Result<(), W::Error> {
+    out.write(&command::raster_header(width, height, scale))?;
+    let mut buf = [0u8; 64];
+    for row in data.chunks(usize::from(width.div_ceil(8))).rev() {
+        for piece in row.rchunks(buf.len()) {
+            for (dst, byte) in buf.iter_mut().zip(piece.iter().rev()) {
+                *dst = byte.reverse_bits();
+            }
+            out.write(&buf[..piece.len()])?;
+        }
+    }
+    Ok(())
+}
+
+/// Statistics for a submitted job, returned by [`Job::stats`].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct JobStats {
+    /// Bytes written to the printer.
+    pub bytes: usize,
+    /// Commands written, counting each run of text as one.
+    pub commands: usize,
+    /// Commands carrying image data: raster and column bit images, stored
+    /// graphics and downloaded images. An image sent as several column
+    /// bands or in two colors counts once per command.
+    pub images: usize,
+    /// Time spent sending, if submitted with
+    /// [`submit_timed`](Job::submit_timed).
+    pub elapsed_ms: Option<u32>,
+}
+
+impl JobStats {
+    /// Throughput in bytes per second, if the job was timed.
+    ///
+    /// A job sent in under a millisecond is reported as taking one.
+    pub fn bytes_per_sec(&self) -> Option<u32> {
+        let ms = u64::from(self.elapsed_ms?.max(1));
+        Some((self.bytes as u64 * 1000 / ms).min(u64::from(u32::MAX)) as u32)
+    }
+}
+
+fn saturate(n: usize) -> i64 {
+    i64::try_from(n).unwrap_or(i64::MAX)
+}
+
+/// A problem found by [`Job::problems`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Problem {
+    /// Offset of the offending command in the job's bytes.
+    pub offset: usize,
+    /// The error submitting the job would cause.
+    pub error: PrinterError<Infallible>,
+}
+
+/// Iterator over the [`Problem`]s in a job, returned by [`Job::problems`].
+pub struct Problems<'a> {
+    decoder: Decoder<'a>,
+    dots: u16,
+    has_cutter: bool,
+    pending: Option<Problem>,
+}
+
+impl Iterator for Problems<'_> {
+    type Item = Problem;
+
+    fn next(&mut self) -> Option<Problem> {
+        if let Some(problem) = self.pending.take() {
+            return Some(problem);
+        }
+        loop {
+            let offset = self.decoder.position();
+            let invalid = |command, value, max| Problem {
+                offset,
+                error: PrinterError::InvalidParameter {
+                    command,
+                    value,
+                    range: 0..=max,
+                },
+            };
+            match self.decoder.next()? {
+                Command::RasterImage { width, scale, .. }
+                    if width.saturating_mul(scale.factors().0) > self.dots =>
+                {
+                    let width = width.saturating_mul(scale.factors().0);
+                    return Some(invalid("RasterImage", width.into(), self.dots.into()));
+                }
+                command @ (Command::Cut(_) | Command::FeedAndCut { .. }) if !self.has_cutter => {
+                    return Some(Problem {
+                        offset,
+                        error: PrinterError::Unsupported {
+                            command: command.name(),
+                        },
+                    });
+                }
+                Command::SetSize { width, height } if width > 7 || height > 7 => {
+                    let mut problems = [(width, "width"), (height, "height")]
+                        .into_iter()
+                        .filter(|&(n, _)| n > 7)
+                        .map(|(n, _)| invalid("SetSize", n.into(), 7));
+                    let first = problems.next();
+                    self.pending = problems.next();
+                    return first;
+                }
+                command => {
+                    if let Err(error) = command.validate() {
+                        return Some(Problem { offset, error });
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<const N: usize> Default for Job<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for Job<N> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<const N: usize> Deref for Job<N> {
+    type Target = Printer<Recorder<N>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.printer
+    }
+}
+
+impl<const N: usize> DerefMut for Job<N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.printer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockTransport, RecordDelay};
+    use crate::{
+        CodePage, CutMode, Justification, Pacing, PaperWidth, Profile, Status, StatusRequest,
+    };
+    use std::vec::Vec;
+
+    #[test]
+    fn test_submit() {
+        let mut printer = Printer::new(MockTransport::new());
+        let mut job = Job::<32>::new();
+        job.write_line("Hi").unwrap();
+        job.cut(CutMode::Full).unwrap();
+        assert!(printer.transport().written().is_empty());
+        job.submit(&mut printer).unwrap();
+        assert_eq!(printer.transport().written(), b"Hi\n\x1D\x56\x00");
+    }
+
+    #[test]
+    fn test_submit_paces_cut() {
+        let mut printer = Printer::builder(MockTransport::new())
+            .pacing(Pacing::new(RecordDelay::default()).after_cut(300))
+            .build()
+            .unwrap();
+        let mut job = Job::<32>::new();
+        job.write_line("Hi").unwrap();
+        job.cut(CutMode::Full).unwrap();
+        job.submit(&mut printer).unwrap();
+        assert_eq!(printer.pacing().unwrap().delay().0, [300]);
+    }
+
+    #[test]
+    fn test_submit_to_station() {
+        let mut printer = Printer::builder(MockTransport::new())
+            .profile(Profile::TM_H6000)
+            .build()
+            .unwrap();
+        let mut job = Job::<16>::for_printer(&printer);
+        job.write_line("Hi").unwrap();
+        job.submit_to(&mut printer, Stations::SLIP).unwrap();
+        assert_eq!(printer.transport().written(), b"\x1Bc0\x04\x1Bc1\x04Hi\n");
+        let err = job.submit_to(&mut printer, Stations::JOURNAL).unwrap_err();
+        assert_eq!(
+            err,
+            PrinterError::Unsupported {
+                command: "SelectPrintStations"
+            }
+        );
+    }
+
+    #[test]
+    fn test_submit_upside_down() {
+        let mut printer = Printer::new(MockTransport::new());
+        let mut job = Job::<64>::new();
+        job.set_bold(true).unwrap();
+        job.write_line("A").unwrap();
+        job.set_bold(false).unwrap();
+        job.write_line("B").unwrap();
+        job.cut(CutMode::Full).unwrap();
+        job.submit_upside_down(&mut printer).unwrap();
+        let expected: &[u8] = b"\x1B@\x1BE\x01\x1B{\x01\x1BE\x00B\n\
+            \x1B@\x1B{\x01\x1BE\x01A\n\
+            \x1B{\x00\x1D\x56\x00";
+        assert_eq!(printer.transport().written(), expected);
+        assert!(job.is_complete());
+    }
+
+    #[test]
+    fn test_upside_down_replays_latest_styles() {
+        let mut printer = Printer::new(MockTransport::new());
+        let mut job = Job::<64>::new();
+        job.set_align(Align::Center).unwrap();
+        job.set_bold(true).unwrap();
+        job.set_bold(false).unwrap();
+        job.set_justification(Justification::Right).unwrap();
+        job.write_line("A").unwrap();
+        job.initialize().unwrap();
+        job.set_bold(true).unwrap();
+        job.write_line("B").unwrap();
+        job.write_line("C").unwrap();
+        job.submit_upside_down(&mut printer).unwrap();
+        let expected: &[u8] = b"\x1B@\x1BE\x01\x1B{\x01C\n\
+            \x1B@\x1BE\x00\x1Ba\x02\x1B{\x01\x1B@\x1B{\x01\x1BE\x01B\n\
+            \x1B@\x1B{\x01\x1Ba\x01\x1BE\x01\x1BE\x00\x1Ba\x02A\n\
+            \x1B{\x00";
+        assert_eq!(printer.transport().written(), expected);
+    }
+
+    #[test]
+    fn test_submit_copies() {
+        let mut printer = Printer::new(MockTransport::new());
+        let mut job = Job::<64>::new();
+        job.printer.paper_width = PaperWidth::Dots(96);
+        job.write_line("12.50").unwrap();
+        job.cut(CutMode::Full).unwrap();
+        let copies = [ReceiptCopy::new().banner("C"), ReceiptCopy::MERCHANT];
+        job.submit_copies(&mut printer, &copies).unwrap();
+        let expected: &[u8] = b"\x1Ba\x01\x1BE\x01C\n\x1BE\x00\x1Ba\x00\
+            12.50\n\x1D\x56\x00\
+            \x1Ba\x01\x1BE\x01MERCHANT COPY\n\x1BE\x00\x1Ba\x00\
+            12.50\n\x1Bd\x01X_______\n\x1D\x56\x00";
+        assert_eq!(printer.transport().written(), expected);
+        assert!(job.is_complete());
+    }
+
+    #[test]
+    fn test_upside_down_rotates_images() {
+        let mut printer = Printer::new(MockTransport::new());
+        let mut job = Job::<32>::new();
+        job.send(&Command::RasterImage {
+            width: 3,
+            height: 2,
+            scale: BitImageScale::Normal,
+            data: &[0b1100_0000, 0b0010_0000],
+        })
+        .unwrap();
+        job.submit_upside_down(&mut printer).unwrap();
+        let written = printer.transport().written();
+        assert_eq!(
+            &written[5..13],
+            command::raster_header(8, 2, BitImageScale::Normal)
+        );
+        // The job only records whole bytes per row, so padding moves left.
+        assert_eq!(&written[13..15], [0x04, 0x03]);
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut printer = Printer::new(MockTransport::new());
+        let mut job = Job::<64>::new();
+        job.write_line("Hi").unwrap();
+        job.send(&Command::RasterImage {
+            width: 8,
+            height: 1,
+            scale: BitImageScale::Normal,
+            data: &[0xFF],
+        })
+        .unwrap();
+        job.send(&Command::BitImage {
+            mode: crate::BitImageMode::SingleDensity8,
+            width: 1,
+            data: &[0xFF],
+        })
+        .unwrap();
+        job.send(&Command::StoreGraphics {
+            color: crate::Color::Black,
+            width: 8,
+            height: 1,
+            data: &[0xFF],
+        })
+        .unwrap();
+        job.send(&Command::DefineDownloadedImage {
+            width: 1,
+            height: 1,
+            data: &[0xFF; 8],
+        })
+        .unwrap();
+        job.cut(CutMode::Full).unwrap();
+        assert_eq!(job.stats(), JobStats::default());
+        let mut now = 1000;
+        job.submit_timed(&mut printer, || {
+            now += 20;
+            now
+        })
+        .unwrap();
+        let stats = job.stats();
+        assert_eq!(stats.bytes, job.byte_len());
+        assert_eq!((stats.commands, stats.images), (7, 4));
+        assert_eq!(stats.elapsed_ms, Some(20));
+        assert_eq!(stats.bytes_per_sec(), Some(job.byte_len() as u32 * 50));
+    }
+
+    #[test]
+    fn test_transmit_ms() {
+        let mut job = Job::<1024>::new();
+        job.raw(&[0; 960]).unwrap();
+        assert_eq!(job.byte_len(), 960);
+        assert_eq!(job.transmit_ms(9600), 1000);
+        assert_eq!(job.transmit_ms(115_200), 84);
+    }
+
+    #[test]
+    fn test_resume_after_error() {
+        use crate::mock::{FaultError, FaultyTransport};
+        let mut printer = Printer::new(FaultyTransport::new(MockTransport::new()).fail_write(2));
+        let mut job = Job::<32>::new();
+        job.write_line("Hi").unwrap();
+        job.cut(CutMode::Full).unwrap();
+        let err = job.submit(&mut printer).unwrap_err();
+        assert_eq!(
+            err,
+            PrinterError::Transport {
+                command: "LineFeed",
+                error: FaultError::Injected,
+            }
+        );
+        assert_eq!(job.sent(), 2);
+        assert!(!job.is_complete());
+        job.resume(&mut printer).unwrap();
+        assert!(job.is_complete());
+        assert_eq!(printer.transport().inner().written(), b"Hi\n\x1D\x56\x00");
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_submit_async_cancel() {
+        use crate::mock::block_on;
+        use crate::AsyncPrinter;
+
+        struct CancelAfterFirst<'a>(MockTransport, &'a AtomicBool);
+        impl AsyncWrite for CancelAfterFirst<'_> {
+            type Error = Infallible;
+
+            async fn write(&mut self, data: &[u8]) -> Result<(), Infallible> {
+                self.1.store(true, Ordering::Release);
+                Write::write(&mut self.0, data)
+            }
+        }
+
+        let cancel = AtomicBool::new(false);
+        let mut printer = AsyncPrinter::new(CancelAfterFirst(MockTransport::new(), &cancel));
+        let mut job = Job::<32>::new();
+        job.write_line("Hi").unwrap();
+        job.cut(CutMode::Full).unwrap();
+        let err = block_on(job.submit_async(&mut printer, &cancel)).unwrap_err();
+        assert_eq!(err, PrinterError::Cancelled { command: "Job" });
+        assert_eq!(job.sent(), 2);
+        let mut expected = b"Hi".to_vec();
+        expected.extend_from_slice(&crate::command::CLEAR_BUFFERS);
+        assert_eq!(printer.transport().0.written(), expected);
+    }
+
+    #[test]
+    fn test_submit_checked_not_ready() {
+        let offline = Status {
+            cover_open: true,
+            offline: true,
+            ..Status::default()
+        };
+        let mut transport = MockTransport::new();
+        for request in [
+            StatusRequest::Printer,
+            StatusRequest::Offline,
+            StatusRequest::Error,
+            StatusRequest::PaperSensor,
+        ] {
+            transport.push_response(&[offline.response(request)]);
+        }
+        let mut printer = Printer::new(transport);
+        let mut job = Job::<32>::for_printer(&printer);
+        job.write("lost").unwrap();
+        let err = job.submit_checked(&mut printer).unwrap_err();
+        assert_eq!(
+            err,
+            PrinterError::NotReady {
+                command: "Job",
+                status: offline,
+            }
+        );
+        // Only the four status queries were sent.
+        assert_eq!(printer.transport().written().len(), 12);
+    }
+
+    #[test]
+    fn test_problems() {
+        let printer = Printer::builder(MockTransport::new())
+            .profile(crate::Profile::GENERIC_58MM)
+            .build()
+            .unwrap();
+        let mut job = Job::<64>::for_printer(&printer);
+        job.write("ok").unwrap();
+        job.raw(&[0x1D, 0x21, 0x89]).unwrap();
+        job.raw(&[0x1D, 0x56, 0x00]).unwrap();
+        let problems: Vec<_> = job.problems(4).collect();
+        assert_eq!(
+            problems,
+            [
+                Problem {
+                    offset: 0,
+                    error: PrinterError::InvalidParameter {
+                        command: "Job",
+                        value: 8,
+                        range: 0..=4,
+                    },
+                },
+                Problem {
+                    offset: 2,
+                    error: PrinterError::InvalidParameter {
+                        command: "SetSize",
+                        value: 8,
+                        range: 0..=7,
+                    },
+                },
+                Problem {
+                    offset: 2,
+                    error: PrinterError::InvalidParameter {
+                        command: "SetSize",
+                        value: 9,
+                        range: 0..=7,
+                    },
+                },
+                Problem {
+                    offset: 5,
+                    error: PrinterError::Unsupported { command: "Cut" },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_submit_validated_sends_nothing() {
+        let mut printer = Printer::new(MockTransport::new());
+        let mut job = Job::<64>::new();
+        job.write("too long").unwrap();
+        assert!(job.submit_validated(&mut printer, 4).is_err());
+        assert!(printer.transport().written().is_empty());
+        job.submit_validated(&mut printer, 64).unwrap();
+        assert_eq!(printer.transport().written(), b"too long");
+    }
+
+    #[test]
+    fn test_for_printer_copies_config() {
+        let printer = Printer::builder(MockTransport::new())
+            .code_page(CodePage::Pc858)
+            .build()
+            .unwrap();
+        let job = Job::<8>::for_printer(&printer);
+        assert_eq!(job.code_page(), Some(CodePage::Pc858));
+        assert!(job.as_bytes().is_empty());
+    }
+}
@@ -0,0 +1,206 @@
+use super::{Command, Delay, Printer, PrinterError, Read, StatusRequest, Write};
+
+/// Bits that are fixed in every `DLE EOT` response: bits 1 and 4 set, bits
+/// 0 and 7 clear.
+const FIXED_MASK: u8 = 0x93;
+const FIXED_BITS: u8 = 0x12;
+
+/// Round-trip times of `DLE EOT` status requests, measured by
+/// [`Printer::measure_status_latency`].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LatencyReport {
+    /// Requests sent.
+    pub sent: u8,
+    /// Requests the printer answered.
+    pub answered: u8,
+    /// Answers whose fixed bits were wrong, as happens with a baud rate
+    /// mismatch or a noisy line.
+    pub garbled: u8,
+    /// Fastest answered round trip in milliseconds.
+    pub min_ms: u32,
+    /// Slowest answered round trip in milliseconds.
+    pub max_ms: u32,
+    /// Sum of all answered round trips in milliseconds.
+    pub total_ms: u32,
+}
+
+impl LatencyReport {
+    /// Mean round trip in milliseconds, or `None` if nothing was answered.
+    pub const fn average_ms(&self) -> Option<u32> {
+        match self.answered {
+            0 => None,
+            n => Some(self.total_ms / n as u32),
+        }
+    }
+
+    /// Classify the link, treating round trips over `slow_ms` as slow.
+    ///
+    /// A healthy printer answers a status request within a few
+    /// milliseconds over USB or Ethernet, and within one character time
+    /// plus a little on a serial line.
+    pub const fn diagnose(&self, slow_ms: u32) -> LinkDiagnosis {
+        if self.answered == 0 {
+            LinkDiagnosis::Silent
+        } else if self.garbled > 0 {
+            LinkDiagnosis::Garbled
+        } else if self.answered < self.sent {
+            LinkDiagnosis::Intermittent
+        } else if self.max_ms > slow_ms {
+            LinkDiagnosis::Slow
+        } else {
+            LinkDiagnosis::Healthy
+        }
+    }
+
+    fn record(&mut self, ms: u32) {
+        if self.answered == 0 || ms < self.min_ms {
+            self.min_ms = ms;
+        }
+        self.max_ms = self.max_ms.max(ms);
+        self.total_ms = self.total_ms.saturating_add(ms);
+        self.answered += 1;
+    }
+}
+
+/// What a [`LatencyReport`] suggests about the link to the printer.
+///
+/// The link is the first thing to check for every result except
+/// [`Healthy`](LinkDiagnosis::Healthy) and [`Slow`](LinkDiagnosis::Slow).
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LinkDiagnosis {
+    /// Every request was answered correctly and in time.
+    Healthy,
+    /// Every request was answered, but some took longer than expected. The
+    /// printer is alive but busy, e.g. buffering a large job, or the
+    /// firmware is struggling.
+    Slow,
+    /// Some requests went unanswered: a loose connector or a flaky
+    /// network.
+    Intermittent,
+    /// Answers arrived corrupted: wrong baud rate or serial settings,
+    /// interference, or a cable that is too long.
+    Garbled,
+    /// No request was answered. The printer is off, its firmware has hung,
+    /// or the receive line is not connected.
+    Silent,
+}
+
+impl<T, P: Delay> Printer<T, P>
+where
+    T: Write + Read<Error = <T as Write>::Error>,
+{
+    /// Send `rounds` printer status requests (`DLE EOT 1`) one after
+    /// another and time each round trip.
+    ///
+    /// `millis` reads a millisecond counter, e.g. a hardware timer; it may
+    /// wrap. A request counts as unanswered when the transport's read
+    /// returns no data, so the transport needs a read timeout. Real-time
+    /// requests are answered even while the printer is busy or offline, so
+    /// silence points at the link or a hung printer rather than at paper
+    /// or cover problems.
+    ///
+    /// ```
+    /// # use escpos_embedded::{LinkDiagnosis, Printer, PrinterError, Read, Write};
+    /// # fn check<T>(printer: &mut Printer<T>, millis: impl FnMut() -> u32) -> Result<(), PrinterError<<T as Write>::Error>>
+    /// # where T: Write + Read<Error = <T as Write>::Error> {
+    /// let report = printer.measure_status_latency(millis, 10)?;
+    /// if report.diagnose(50) != LinkDiagnosis::Healthy {
+    ///     // check the cable before replacing the printer
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// A transport error ends the measurement and is returned.
+    pub fn measure_status_latency(
+        &mut self,
+        mut millis: impl FnMut() -> u32,
+        rounds: u8,
+    ) -> Result<LatencyReport, PrinterError<<T as Write>::Error>> {
+        let command = Command::TransmitStatus(StatusRequest::Printer);
+        let mut report = LatencyReport::default();
+        for _ in 0..rounds {
+            let start = millis();
+            self.send(&command)?;
+            let mut reply = [0u8; 1];
+            let read =
+                self.transport
+                    .read(&mut reply)
+                    .map_err(|error| PrinterError::Transport {
+                        command: command.name(),
+                        error,
+                    })?;
+            let elapsed = millis().wrapping_sub(start);
+            report.sent += 1;
+            if read == 0 {
+                continue;
+            }
+            report.record(elapsed);
+            if reply[0] & FIXED_MASK != FIXED_BITS {
+                report.garbled += 1;
+            }
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockTransport;
+    use core::cell::Cell;
+
+    /// A clock that advances by the next step each time it is read twice.
+    fn clock<'a>(now: &'a Cell<u32>, steps: &'a [u32]) -> impl FnMut() -> u32 + 'a {
+        let mut reads = 0;
+        move || {
+            if reads % 2 == 1 {
+                now.set(now.get().wrapping_add(steps[reads / 2]));
+            }
+            reads += 1;
+            now.get()
+        }
+    }
+
+    #[test]
+    fn test_measure_healthy() {
+        let now = Cell::new(u32::MAX - 1);
+        let mut printer = Printer::new(MockTransport::with_responses(&[0x12, 0x16, 0x12]));
+        let report = printer
+            .measure_status_latency(clock(&now, &[3, 5, 4]), 3)
+            .unwrap();
+        assert_eq!(printer.transport.written(), [0x10, 0x04, 0x01].repeat(3));
+        assert_eq!((report.sent, report.answered), (3, 3));
+        assert_eq!((report.min_ms, report.max_ms), (3, 5));
+        assert_eq!(report.average_ms(), Some(4));
+        assert_eq!(report.diagnose(20), LinkDiagnosis::Healthy);
+        assert_eq!(report.diagnose(4), LinkDiagnosis::Slow);
+    }
+
+    #[test]
+    fn test_diagnose_faults() {
+        let now = Cell::new(0);
+        let mut silent = Printer::new(MockTransport::new());
+        let report = silent
+            .measure_status_latency(clock(&now, &[100, 100]), 2)
+            .unwrap();
+        assert_eq!(report.average_ms(), None);
+        assert_eq!(report.diagnose(20), LinkDiagnosis::Silent);
+
+        let mut flaky = Printer::new(MockTransport::with_responses(&[0x12]));
+        let report = flaky
+            .measure_status_latency(clock(&now, &[1, 100]), 2)
+            .unwrap();
+        assert_eq!((report.sent, report.answered, report.max_ms), (2, 1, 1));
+        assert_eq!(report.diagnose(20), LinkDiagnosis::Intermittent);
+
+        let mut noisy = Printer::new(MockTransport::with_responses(&[0x12, 0xFE]));
+        let report = noisy
+            .measure_status_latency(clock(&now, &[1, 1]), 2)
+            .unwrap();
+        assert_eq!(report.garbled, 1);
+        assert_eq!(report.diagnose(20), LinkDiagnosis::Garbled);
+    }
+}
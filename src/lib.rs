@@ -53,11 +53,183 @@ impl Delay for () {
     fn delay_ms(&mut self, _ms: u32) {}
 }
 
+mod ansi;
+#[cfg(feature = "async")]
+mod asynch;
+mod backend;
+mod backpressure;
+mod batch;
+#[cfg(feature = "std")]
+mod bench;
+mod border;
+mod builder;
+#[cfg(feature = "std")]
+mod capture;
+#[cfg(feature = "cat")]
+mod cat;
+mod chunked;
+mod clock;
+#[cfg(feature = "image")]
+mod column;
+mod command;
+mod decoder;
+mod degrade;
+#[cfg(feature = "json")]
+mod document;
+#[cfg(feature = "std")]
+mod emulator;
+mod error;
+mod failover;
+#[cfg(feature = "image")]
+mod fit;
+#[cfg(any(feature = "std", feature = "cat"))]
+mod font;
+#[cfg(feature = "framed")]
+mod framed;
+#[cfg(feature = "image")]
+mod gray;
+mod hooks;
+mod infallible;
+mod job;
+mod latency;
+#[cfg(feature = "log")]
+mod logger;
+mod markdown;
+mod micr;
+#[cfg(any(test, feature = "test-util"))]
+#[cfg_attr(not(feature = "test-util"), allow(dead_code))]
+mod mock;
+#[cfg(feature = "std")]
+mod net;
+mod pacing;
+#[cfg(feature = "image")]
+mod pattern;
+#[cfg(feature = "std")]
+mod pool;
+mod profile;
+#[cfg(feature = "std")]
+mod raster_emulator;
+mod recorder;
+mod reprint;
+#[cfg(feature = "std")]
+mod simulator;
+mod spool;
+#[cfg(feature = "std")]
+mod spooler;
+#[cfg(feature = "star")]
+mod star;
+mod status;
+#[cfg(feature = "std")]
+mod table;
+#[cfg(feature = "image")]
+mod two_color;
+pub mod vendor;
+mod watch;
+#[cfg(feature = "wasm")]
+mod web_serial;
+#[cfg(all(windows, feature = "std"))]
+mod windows;
+mod wrap;
+mod xon_xoff;
+
+pub use ansi::AnsiWriter;
+#[cfg(feature = "async")]
+pub use asynch::{AsyncPrinter, AsyncWrite};
+pub use backend::Backend;
+pub use backpressure::{Backpressure, BackpressureError};
+#[cfg(feature = "std")]
+pub use bench::{Bench, BenchReport, Workload};
+pub use border::{BorderStyle, BoxChars};
+pub use builder::{PrinterBuilder, Startup};
+#[cfg(feature = "std")]
+pub use capture::Capture;
+#[cfg(feature = "cat")]
+pub use cat::CatPrinter;
+pub use chunked::Chunked;
+pub use clock::{Clock, DateTime, Formatted};
+pub use command::{BufferFull, Command};
+pub use decoder::Decoder;
+pub use degrade::{Degradation, Fallback};
+#[cfg(feature = "json")]
+pub use document::{Document, Element};
+#[cfg(feature = "std")]
+pub use emulator::TextEmulator;
+pub use error::PrinterError;
+pub use failover::{Destination, Failover, FailoverError};
+#[cfg(feature = "framed")]
+pub use framed::{Framed, FramedError, FRAME_MAX_PAYLOAD};
+#[cfg(feature = "image")]
+pub use gray::GrayImage;
+pub use hooks::Hooks;
+pub use infallible::InfalliblePrinter;
+pub use job::{Job, JobStats, Problem, Problems, ReceiptCopy};
+pub use latency::{LatencyReport, LinkDiagnosis};
+#[cfg(feature = "log")]
+pub use logger::PrinterLogger;
+pub use micr::{MicrFields, MicrLine};
+#[cfg(feature = "test-util")]
+pub use mock::{FaultError, FaultyTransport, LimitedMockTransport, MockTransport, RecordDelay};
+#[cfg(feature = "std")]
+pub use net::{discover, DiscoveredPrinter, DiscoverySource, RAW_PRINT_PORT};
+pub use pacing::Pacing;
+#[cfg(feature = "image")]
+pub use pattern::DiagnosticPattern;
+#[cfg(feature = "std")]
+pub use pool::{PoolError, PrinterHealth, PrinterPool};
+pub use profile::{CutStyle, Features, PaperWidth, Profile, SelfTest, Stations};
+#[cfg(feature = "std")]
+pub use raster_emulator::{Bitmap, RasterEmulator};
+pub use recorder::Recorder;
+pub use reprint::Reprint;
+#[cfg(feature = "std")]
+pub use simulator::SimulatedPrinter;
+pub use spool::{Spool, SpoolError};
+#[cfg(feature = "std")]
+pub use spooler::{JobId, Spooler, SpoolerEvent};
+#[cfg(feature = "star")]
+pub use star::StarPrinter;
+pub use status::{AsbFlags, NearEndPolicy, PaperSensors, PrinterIdKind, Status, StatusRequest};
+#[cfg(feature = "std")]
+pub use table::parse_csv;
+#[cfg(feature = "image")]
+pub use two_color::{split_planes, TwoColorImage};
+pub use watch::{StatusEvent, StatusWatcher};
+#[cfg(feature = "wasm")]
+pub use web_serial::WebSerial;
+#[cfg(all(windows, feature = "std"))]
+pub use windows::WindowsPrinter;
+pub use xon_xoff::{XonXoff, XonXoffError, XOFF, XON};
+
 /// A simple ESC/POS printer driver.
-pub struct Printer<T: Write> {
+///
+/// `P` is the [`Delay`] used for [pacing](PrinterBuilder::pacing), and is
+/// only chosen by the builder.
+pub struct Printer<T: Write, P: Delay = ()> {
     transport: T,
+    profile: Profile,
+    paper_width: PaperWidth,
+    code_page: Option<CodePage>,
+    chunk_size: Option<usize>,
+    font: Font,
+    char_width: u8,
+    cut_fallback: Option<u8>,
+    pacing: Option<Pacing<P>>,
+    hooks: Hooks,
+    line_ending: LineEnding,
+    startup: Startup,
+    degradation: Degradation,
+    emulation: degrade::Emulation,
+    /// Start of an Automatic Status Back packet still being received.
+    auto_status: [u8; 4],
+    auto_status_len: usize,
+    #[cfg(feature = "image")]
+    image_fit: ImageFit,
 }
 
+/// Size of the pieces images are sent in when no chunk size is configured.
+#[cfg(feature = "image")]
+const IMAGE_CHUNK: usize = 512;
+
 #[cfg(feature = "image")]
 /// A simple representation of a black & white image.
 ///
@@ -76,6 +248,37 @@ where
     pub data: D,
 }
 
+/// Image data that can be written as a `GS v 0` raster command.
+#[cfg(feature = "image")]
+trait Raster {
+    /// Write the header and bitmap to `out`.
+    fn encode<W: Write>(&self, out: &mut W) -> Result<(), W::Error>;
+
+    /// The command shown to [`Hooks`]. Its data is empty when the bitmap
+    /// is only produced while encoding.
+    fn command(&self) -> Command<'_>;
+}
+
+/// What [`Printer::print_image`] does with images that don't match the
+/// paper's printable width.
+#[cfg(feature = "image")]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ImageFit {
+    /// Refuse images wider than the paper with
+    /// [`PrinterError::InvalidParameter`].
+    #[default]
+    Error,
+    /// Scale wider images down to the paper width, keeping the aspect
+    /// ratio.
+    Scale,
+    /// Cut off the right-hand side of wider images.
+    Crop,
+    /// Center narrower images, and cut wider ones equally on both sides.
+    Center,
+}
+
 /// Model used to estimate how long printing image data will take.
 ///
 /// `line_time_ms` represents the time to process one line with no black pixels.
@@ -88,6 +291,23 @@ pub struct TimingModel {
     pub black_pixel_time_ms: u32,
 }
 
+#[cfg(feature = "image")]
+impl<D: AsRef<[u8]>> Image<D> {
+    /// Return the image data if its length matches the dimensions.
+    fn check_size<E>(&self) -> Result<&[u8], PrinterError<E>> {
+        let data = self.data.as_ref();
+        let expected = command::image_len("RasterImage", self.width, self.height, 8)?;
+        if data.len() != expected {
+            return Err(PrinterError::ImageSizeMismatch {
+                command: "RasterImage",
+                expected,
+                actual: data.len(),
+            });
+        }
+        Ok(data)
+    }
+}
+
 #[cfg(feature = "image")]
 impl TimingModel {
     /// Create a new timing model.
@@ -101,37 +321,55 @@ impl TimingModel {
     /// Estimate the time to print a chunk of bitmap data for an image with the
     /// given width.
     pub fn estimate_image_chunk_ms(&self, width: u16, chunk: &[u8]) -> u32 {
-        let width_bytes = ((width + 7) / 8) as usize;
+        let width_bytes = width.div_ceil(8) as usize;
         if width_bytes == 0 {
             return 0;
         }
-        let lines = (chunk.len() + width_bytes - 1) / width_bytes;
-        let base = self.line_time_ms * lines as u32;
+        let lines = chunk.len().div_ceil(width_bytes);
+        let base = self.line_time_ms.saturating_mul(lines as u32);
         let black: u32 = chunk.iter().map(|b| b.count_ones()).sum();
-        base + black * self.black_pixel_time_ms
+        base.saturating_add(black.saturating_mul(self.black_pixel_time_ms))
     }
 }
 
 /// Paper cutting modes.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CutMode {
     /// Full paper cut.
     Full,
-    /// Partial paper cut.
+    /// Partial paper cut, leaving one point uncut.
     Partial,
+    /// Partial paper cut leaving three points uncut (`ESC m`).
+    ///
+    /// Only supported by some firmwares. It has no `GS V` form, so it is
+    /// sent as-is whatever the profile's [`CutStyle`], and
+    /// [`Printer::cut_with_feed`] falls back to [`CutMode::Partial`].
+    PartialThreePoints,
 }
 
 impl CutMode {
     fn as_byte(self) -> u8 {
         match self {
             CutMode::Full => 0x00,
-            CutMode::Partial => 0x01,
+            CutMode::Partial | CutMode::PartialThreePoints => 0x01,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0x00 => Some(CutMode::Full),
+            0x01 => Some(CutMode::Partial),
+            _ => None,
         }
     }
 }
 
 /// Underline styles.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum UnderlineMode {
     /// No underline.
     None,
@@ -149,10 +387,21 @@ impl UnderlineMode {
             UnderlineMode::Double => 0x02,
         }
     }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0x00 => Some(UnderlineMode::None),
+            0x01 => Some(UnderlineMode::Single),
+            0x02 => Some(UnderlineMode::Double),
+            _ => None,
+        }
+    }
 }
 
 /// Horizontal alignment modes.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Align {
     Left,
     Center,
@@ -167,26 +416,127 @@ impl Align {
             Align::Right => 0x02,
         }
     }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0x00 => Some(Align::Left),
+            0x01 => Some(Align::Center),
+            0x02 => Some(Align::Right),
+            _ => None,
+        }
+    }
 }
 
 /// Font type selection.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Font {
     FontA,
     FontB,
+    /// Third font, condensed or special depending on the model.
+    FontC,
+    /// Any other font number, for models with more fonts than A–C, such as
+    /// special fonts at `0x61` and `0x62`.
+    Custom(u8),
 }
 
 impl Font {
+    /// Width of one character cell in dots.
+    ///
+    /// Fonts other than A are assumed to be as narrow as Font B, which is
+    /// right for most condensed fonts.
+    const fn dot_width(self) -> u16 {
+        match self {
+            Font::FontA => 12,
+            Font::FontB | Font::FontC | Font::Custom(_) => 9,
+        }
+    }
+
     fn as_byte(self) -> u8 {
         match self {
             Font::FontA => 0x00,
             Font::FontB => 0x01,
+            Font::FontC => 0x02,
+            Font::Custom(n) => n,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Self {
+        match byte {
+            0x00 => Font::FontA,
+            0x01 => Font::FontB,
+            0x02 => Font::FontC,
+            n => Font::Custom(n),
+        }
+    }
+}
+
+/// Character size multiplier, from 1x (normal) to 8x.
+///
+/// ```
+/// # use escpos_embedded::Scale;
+/// assert_eq!(Scale::new(3).map(Scale::get), Some(3));
+/// assert_eq!(Scale::new(9), None);
+/// ```
+#[derive(Copy, Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "u8", into = "u8"))]
+pub struct Scale(u8);
+
+impl Scale {
+    /// Normal size.
+    pub const NORMAL: Self = Self(1);
+    /// Twice the normal size.
+    pub const DOUBLE: Self = Self(2);
+    /// Three times the normal size.
+    pub const TRIPLE: Self = Self(3);
+    /// The largest size, eight times normal.
+    pub const MAX: Self = Self(8);
+
+    /// Scale by `factor`, or `None` if it isn't 1–8.
+    pub const fn new(factor: u8) -> Option<Self> {
+        match factor {
+            1..=8 => Some(Self(factor)),
+            _ => None,
         }
     }
+
+    /// The multiplier, 1–8.
+    pub const fn get(self) -> u8 {
+        self.0
+    }
+}
+
+impl Default for Scale {
+    fn default() -> Self {
+        Self::NORMAL
+    }
+}
+
+impl TryFrom<u8> for Scale {
+    type Error = PrinterError<core::convert::Infallible>;
+
+    fn try_from(factor: u8) -> Result<Self, Self::Error> {
+        Self::new(factor).ok_or(PrinterError::InvalidParameter {
+            command: "Scale",
+            value: factor.into(),
+            range: 1..=8,
+        })
+    }
+}
+
+impl From<Scale> for u8 {
+    fn from(scale: Scale) -> Self {
+        scale.0
+    }
 }
 
 /// Text justification.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Justification {
     Left,
     Center,
@@ -203,8 +553,34 @@ impl Justification {
     }
 }
 
+/// Line ending sent by [`Printer::write_line`].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LineEnding {
+    /// `LF`, which prints the line on ESC/POS printers.
+    #[default]
+    Lf,
+    /// `CR`, for printers that only print on a carriage return.
+    Cr,
+    /// `CR LF`, for printers whose behaviour isn't known.
+    CrLf,
+}
+
+impl LineEnding {
+    fn as_bytes(self) -> &'static [u8] {
+        match self {
+            LineEnding::Lf => b"\n",
+            LineEnding::Cr => b"\r",
+            LineEnding::CrLf => b"\r\n",
+        }
+    }
+}
+
 /// Print density levels.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Density {
     Level0,
     Level1,
@@ -231,10 +607,27 @@ impl Density {
             Density::Level8 => 0x08,
         }
     }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0x00 => Some(Density::Level0),
+            0x01 => Some(Density::Level1),
+            0x02 => Some(Density::Level2),
+            0x03 => Some(Density::Level3),
+            0x04 => Some(Density::Level4),
+            0x05 => Some(Density::Level5),
+            0x06 => Some(Density::Level6),
+            0x07 => Some(Density::Level7),
+            0x08 => Some(Density::Level8),
+            _ => None,
+        }
+    }
 }
 
 /// Printer speed options.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PrintSpeed {
     Speed1,
     Speed2,
@@ -251,6 +644,476 @@ impl PrintSpeed {
             PrintSpeed::Speed4 => 0x03,
         }
     }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0x00 => Some(PrintSpeed::Speed1),
+            0x01 => Some(PrintSpeed::Speed2),
+            0x02 => Some(PrintSpeed::Speed3),
+            0x03 => Some(PrintSpeed::Speed4),
+            _ => None,
+        }
+    }
+}
+
+/// Test patterns printed with `GS ( A`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TestPattern {
+    /// Switch to hexadecimal dump mode; see
+    /// [`enter_hex_dump`](Printer::enter_hex_dump).
+    HexDump,
+    /// The printer status page, as printed by the self-test.
+    StatusPrint,
+    /// A rolling pattern of the character set.
+    RollingPattern,
+}
+
+impl TestPattern {
+    fn as_byte(self) -> u8 {
+        match self {
+            TestPattern::HexDump => 0x01,
+            TestPattern::StatusPrint => 0x02,
+            TestPattern::RollingPattern => 0x03,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0x01 | b'1' => Some(TestPattern::HexDump),
+            0x02 | b'2' => Some(TestPattern::StatusPrint),
+            0x03 | b'3' => Some(TestPattern::RollingPattern),
+            _ => None,
+        }
+    }
+}
+
+/// Customized setting values stored in the printer with `GS ( E`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CustomSetting {
+    /// Print density, -6 to 6 stored as a 16-bit two's complement value.
+    Density,
+    /// Print speed level, 1 (slowest) upwards.
+    PrintSpeed,
+}
+
+impl CustomSetting {
+    fn as_byte(self) -> u8 {
+        match self {
+            CustomSetting::Density => 0x05,
+            CustomSetting::PrintSpeed => 0x06,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0x05 => Some(CustomSetting::Density),
+            0x06 => Some(CustomSetting::PrintSpeed),
+            _ => None,
+        }
+    }
+}
+
+/// When a macro executed with `GS ^` runs each repetition.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MacroMode {
+    /// Repeat after the wait time elapses.
+    Continuous,
+    /// Wait for the FEED button before each repetition.
+    WaitForButton,
+}
+
+impl MacroMode {
+    fn as_byte(self) -> u8 {
+        match self {
+            MacroMode::Continuous => 0x00,
+            MacroMode::WaitForButton => 0x01,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0x00 => Some(MacroMode::Continuous),
+            0x01 => Some(MacroMode::WaitForButton),
+            _ => None,
+        }
+    }
+}
+
+/// Print color on two-color printers.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Color {
+    /// The first color, usually black.
+    #[default]
+    Black,
+    /// The second color, usually red.
+    Red,
+}
+
+impl Color {
+    fn as_byte(self) -> u8 {
+        match self {
+            Color::Black => 0x00,
+            Color::Red => 0x01,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0x00 => Some(Color::Black),
+            0x01 => Some(Color::Red),
+            _ => None,
+        }
+    }
+}
+
+/// Dot density of column-format bit images (`ESC * m`).
+///
+/// 8-dot modes print each dot three motion units tall, so an image sent in
+/// them has a third of the vertical resolution; single-density modes print
+/// each column two dots wide. Older printers often support only the 8-dot
+/// modes.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BitImageMode {
+    /// 8-dot single density (`m = 0`).
+    SingleDensity8,
+    /// 8-dot double density (`m = 1`).
+    DoubleDensity8,
+    /// 24-dot single density (`m = 32`).
+    SingleDensity24,
+    /// 24-dot double density (`m = 33`), full resolution.
+    #[default]
+    DoubleDensity24,
+}
+
+impl BitImageMode {
+    fn as_byte(self) -> u8 {
+        match self {
+            BitImageMode::SingleDensity8 => 0,
+            BitImageMode::DoubleDensity8 => 1,
+            BitImageMode::SingleDensity24 => 32,
+            BitImageMode::DoubleDensity24 => 33,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(BitImageMode::SingleDensity8),
+            1 => Some(BitImageMode::DoubleDensity8),
+            32 => Some(BitImageMode::SingleDensity24),
+            33 => Some(BitImageMode::DoubleDensity24),
+            _ => None,
+        }
+    }
+
+    /// Bytes of data in each column: 1 for 8-dot modes, 3 for 24-dot ones.
+    pub fn bytes_per_column(self) -> u16 {
+        match self {
+            BitImageMode::SingleDensity8 | BitImageMode::DoubleDensity8 => 1,
+            BitImageMode::SingleDensity24 | BitImageMode::DoubleDensity24 => 3,
+        }
+    }
+
+    /// Printer dots covered by each column horizontally.
+    pub fn dot_width(self) -> u16 {
+        match self {
+            BitImageMode::SingleDensity8 | BitImageMode::SingleDensity24 => 2,
+            BitImageMode::DoubleDensity8 | BitImageMode::DoubleDensity24 => 1,
+        }
+    }
+
+    /// Vertical motion units covered by each dot.
+    pub fn dot_height(self) -> u16 {
+        match self {
+            BitImageMode::SingleDensity8 | BitImageMode::DoubleDensity8 => 3,
+            BitImageMode::SingleDensity24 | BitImageMode::DoubleDensity24 => 1,
+        }
+    }
+}
+
+/// Scaling applied to bit images by the printer: downloaded images printed
+/// with `GS /`, and raster images (`GS v 0`).
+///
+/// Double width halves the horizontal dot density, for images prepared at
+/// half the printer's resolution. Raster scaling is only sent to printers
+/// whose profile sets [`raster_scaling`](Profile::raster_scaling).
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BitImageScale {
+    /// Actual size.
+    #[default]
+    Normal,
+    /// Twice as wide.
+    DoubleWidth,
+    /// Twice as tall.
+    DoubleHeight,
+    /// Twice as wide and twice as tall.
+    Quadruple,
+}
+
+impl BitImageScale {
+    fn as_byte(self) -> u8 {
+        match self {
+            BitImageScale::Normal => 0x00,
+            BitImageScale::DoubleWidth => 0x01,
+            BitImageScale::DoubleHeight => 0x02,
+            BitImageScale::Quadruple => 0x03,
+        }
+    }
+
+    /// How many dots wide and tall each image pixel prints.
+    pub fn factors(self) -> (u16, u16) {
+        match self {
+            BitImageScale::Normal => (1, 1),
+            BitImageScale::DoubleWidth => (2, 1),
+            BitImageScale::DoubleHeight => (1, 2),
+            BitImageScale::Quadruple => (2, 2),
+        }
+    }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0x00 => Some(BitImageScale::Normal),
+            0x01 => Some(BitImageScale::DoubleWidth),
+            0x02 => Some(BitImageScale::DoubleHeight),
+            0x03 => Some(BitImageScale::Quadruple),
+            _ => None,
+        }
+    }
+}
+
+/// Position adjusted relative to a black mark with `GS ( F`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MarkPosition {
+    /// Where printing starts after feeding to a mark.
+    PrintStart,
+    /// Where the paper is cut.
+    Cut,
+}
+
+impl MarkPosition {
+    fn as_byte(self) -> u8 {
+        match self {
+            MarkPosition::PrintStart => 0x01,
+            MarkPosition::Cut => 0x02,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0x01 | b'1' => Some(MarkPosition::PrintStart),
+            0x02 | b'2' => Some(MarkPosition::Cut),
+            _ => None,
+        }
+    }
+}
+
+/// Paper stock selected with the `FS ( L` paper layout setting.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LabelStock {
+    /// Continuous receipt paper; label positioning is disabled.
+    Continuous,
+    /// Die-cut labels, located by detecting the gap between them.
+    Gap,
+    /// Labels located by black marks on the backing.
+    BlackMark,
+}
+
+impl LabelStock {
+    fn as_byte(self) -> u8 {
+        match self {
+            LabelStock::Continuous => b'0',
+            LabelStock::BlackMark => b'1',
+            LabelStock::Gap => b'2',
+        }
+    }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            b'0' => Some(LabelStock::Continuous),
+            b'1' => Some(LabelStock::BlackMark),
+            b'2' => Some(LabelStock::Gap),
+            _ => None,
+        }
+    }
+}
+
+/// Size of NV user memory (`FS g`), in bytes.
+const USER_MEMORY_SIZE: usize = 1024;
+
+/// Most bytes a single `FS g` command can read or write.
+const USER_MEMORY_CHUNK: usize = 80;
+
+/// Reject accesses that run past the end of NV user memory.
+pub(crate) fn check_user_memory<E>(
+    command: &'static str,
+    address: u32,
+    len: usize,
+) -> Result<(), PrinterError<E>> {
+    let end = (address as usize).saturating_add(len);
+    if end > USER_MEMORY_SIZE {
+        return Err(PrinterError::InvalidParameter {
+            command,
+            value: end.try_into().unwrap_or(i64::MAX),
+            range: 0..=USER_MEMORY_SIZE as i64,
+        });
+    }
+    Ok(())
+}
+
+/// Largest black mark adjustment accepted by `GS ( F`, in motion units.
+const MAX_MARK_OFFSET: i64 = 1700;
+
+/// Character code tables selectable with `ESC t n`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CodePage {
+    /// USA, standard Europe.
+    Pc437,
+    /// Katakana.
+    Katakana,
+    /// Multilingual Latin-1.
+    Pc850,
+    /// Portuguese.
+    Pc860,
+    /// Canadian-French.
+    Pc863,
+    /// Nordic.
+    Pc865,
+    /// Windows Latin-1.
+    Wpc1252,
+    /// Cyrillic #2.
+    Pc866,
+    /// Latin 2.
+    Pc852,
+    /// Multilingual Latin-1 with the euro sign.
+    Pc858,
+}
+
+impl CodePage {
+    fn as_byte(self) -> u8 {
+        match self {
+            CodePage::Pc437 => 0,
+            CodePage::Katakana => 1,
+            CodePage::Pc850 => 2,
+            CodePage::Pc860 => 3,
+            CodePage::Pc863 => 4,
+            CodePage::Pc865 => 5,
+            CodePage::Wpc1252 => 16,
+            CodePage::Pc866 => 17,
+            CodePage::Pc852 => 18,
+            CodePage::Pc858 => 19,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(CodePage::Pc437),
+            1 => Some(CodePage::Katakana),
+            2 => Some(CodePage::Pc850),
+            3 => Some(CodePage::Pc860),
+            4 => Some(CodePage::Pc863),
+            5 => Some(CodePage::Pc865),
+            16 => Some(CodePage::Wpc1252),
+            17 => Some(CodePage::Pc866),
+            18 => Some(CodePage::Pc852),
+            19 => Some(CodePage::Pc858),
+            _ => None,
+        }
+    }
+}
+
+/// International character sets selectable with `ESC R n`.
+///
+/// Each set replaces a handful of ASCII characters (such as `#`, `$`, `@`
+/// and `[`) with national ones, e.g. `£` for the UK.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Charset {
+    /// U.S.A.
+    #[default]
+    Usa,
+    /// France.
+    France,
+    /// Germany.
+    Germany,
+    /// U.K.
+    Uk,
+    /// Denmark I.
+    DenmarkI,
+    /// Sweden.
+    Sweden,
+    /// Italy.
+    Italy,
+    /// Spain I.
+    SpainI,
+    /// Japan.
+    Japan,
+    /// Norway.
+    Norway,
+    /// Denmark II.
+    DenmarkII,
+    /// Spain II.
+    SpainII,
+    /// Latin America.
+    LatinAmerica,
+    /// Korea.
+    Korea,
+    /// Slovenia/Croatia.
+    SloveniaCroatia,
+    /// China.
+    China,
+    /// Vietnam.
+    Vietnam,
+    /// Arabia.
+    Arabia,
+}
+
+impl Charset {
+    const ALL: [Charset; 18] = [
+        Charset::Usa,
+        Charset::France,
+        Charset::Germany,
+        Charset::Uk,
+        Charset::DenmarkI,
+        Charset::Sweden,
+        Charset::Italy,
+        Charset::SpainI,
+        Charset::Japan,
+        Charset::Norway,
+        Charset::DenmarkII,
+        Charset::SpainII,
+        Charset::LatinAmerica,
+        Charset::Korea,
+        Charset::SloveniaCroatia,
+        Charset::China,
+        Charset::Vietnam,
+        Charset::Arabia,
+    ];
+
+    fn as_byte(self) -> u8 {
+        self as u8
+    }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        Self::ALL.get(usize::from(byte)).copied()
+    }
 }
 
 #[cfg(feature = "embedded_io")]
@@ -337,340 +1200,2974 @@ mod embedded_io {
 }
 
 #[cfg(feature = "embedded_io")]
-pub use embedded_io::FromEmbeddedIo;
+pub use embedded_io::{Compat, FromEmbeddedIo};
 
 impl<T: Write> Printer<T> {
     /// Create a new printer from the given transport.
+    ///
+    /// Uses [`Profile::GENERIC`] and sends nothing; see
+    /// [`builder`](Printer::builder) for other configurations.
     pub fn new(transport: T) -> Self {
-        Self { transport }
+        Self {
+            transport,
+            profile: Profile::GENERIC,
+            paper_width: Profile::GENERIC.paper_width,
+            code_page: None,
+            chunk_size: None,
+            font: Font::FontA,
+            char_width: 1,
+            cut_fallback: None,
+            pacing: None,
+            hooks: Hooks::new(),
+            line_ending: LineEnding::Lf,
+            startup: Startup::default(),
+            degradation: Degradation::new(),
+            emulation: degrade::Emulation::new(),
+            auto_status: [0; 4],
+            auto_status_len: 0,
+            #[cfg(feature = "image")]
+            image_fit: ImageFit::Error,
+        }
     }
-}
 
-impl<T> Printer<T>
-where
-    T: Write + Read<Error = <T as Write>::Error>,
-{
-    /// Write raw text to the printer.
-    pub fn write(&mut self, text: &str) -> Result<(), <T as Write>::Error> {
-        self.transport.write(text.as_bytes())
+    /// Create a printer and send `startup` to it.
+    ///
+    /// The sequence is sent again by [`reinitialize`](Printer::reinitialize)
+    /// and [`reconnect`](Printer::reconnect).
+    ///
+    /// ```
+    /// # use escpos_embedded::{Charset, CodePage, Printer, Recorder, Startup};
+    /// let startup = Startup::new()
+    ///     .code_page(CodePage::Pc858)
+    ///     .charset(Charset::Germany)
+    ///     .line_spacing(30);
+    /// let printer = Printer::new_initialized(Recorder::<64>::new(), startup).unwrap();
+    /// assert_eq!(printer.transport().as_bytes(), b"\x1B@\x1Bt\x13\x1BR\x02\x1B3\x1E");
+    /// ```
+    pub fn new_initialized(
+        transport: T,
+        startup: Startup,
+    ) -> Result<Self, PrinterError<<T as Write>::Error>> {
+        let mut printer = Self {
+            startup,
+            ..Self::new(transport)
+        };
+        printer.reinitialize()?;
+        Ok(printer)
     }
 
-    /// Write text followed by a newline.
-    pub fn write_line(&mut self, text: &str) -> Result<(), <T as Write>::Error> {
-        self.write(text)?;
-        self.transport.write(b"\n")
+    /// Configure a printer before creating it.
+    pub fn builder(transport: T) -> PrinterBuilder<T> {
+        PrinterBuilder::new(transport)
     }
 
-    /// Feed the specified number of lines.
-    pub fn feed(&mut self, lines: u8) -> Result<(), <T as Write>::Error> {
-        self.raw(&[0x1B, 0x64, lines])
+    /// Switch to `pacing`, whose delay may have a different type.
+    fn with_pacing<P: Delay>(self, pacing: Option<Pacing<P>>) -> Printer<T, P> {
+        Printer {
+            transport: self.transport,
+            profile: self.profile,
+            paper_width: self.paper_width,
+            code_page: self.code_page,
+            chunk_size: self.chunk_size,
+            font: self.font,
+            char_width: self.char_width,
+            cut_fallback: self.cut_fallback,
+            pacing,
+            hooks: self.hooks,
+            line_ending: self.line_ending,
+            startup: self.startup,
+            degradation: self.degradation,
+            emulation: self.emulation,
+            auto_status: self.auto_status,
+            auto_status_len: self.auto_status_len,
+            #[cfg(feature = "image")]
+            image_fit: self.image_fit,
+        }
     }
+}
 
-    /// Cut the paper using the given mode.
-    pub fn cut(&mut self, mode: CutMode) -> Result<(), <T as Write>::Error> {
-        self.raw(&[0x1D, 0x56, mode.as_byte()])
+impl<T: Write, P: Delay> Printer<T, P> {
+    /// The printer model's profile.
+    pub fn profile(&self) -> &Profile {
+        &self.profile
     }
 
-    /// Enable or disable bold mode.
-    pub fn set_bold(&mut self, on: bool) -> Result<(), <T as Write>::Error> {
-        let flag = if on { 0x01 } else { 0x00 };
-        self.raw(&[0x1B, 0x45, flag])
+    /// Width of the loaded paper.
+    pub fn paper_width(&self) -> PaperWidth {
+        self.paper_width
     }
 
-    /// Set underline mode.
-    pub fn set_underline(&mut self, mode: UnderlineMode) -> Result<(), <T as Write>::Error> {
-        self.raw(&[0x1B, 0x2D, mode.as_byte()])
+    /// Code page last selected through this printer, if any.
+    pub fn code_page(&self) -> Option<CodePage> {
+        self.code_page
     }
 
-    /// Set text alignment.
-    pub fn set_align(&mut self, align: Align) -> Result<(), <T as Write>::Error> {
-        self.raw(&[0x1B, 0x61, align.as_byte()])
+    /// Maximum size of a single transport write, if limited.
+    pub fn chunk_size(&self) -> Option<usize> {
+        self.chunk_size
     }
 
-    /// Select printer font.
-    pub fn set_font(&mut self, font: Font) -> Result<(), <T as Write>::Error> {
-        self.raw(&[0x1B, 0x4D, font.as_byte()])
+    /// Pauses made after slow commands, if configured.
+    pub fn pacing(&self) -> Option<&Pacing<P>> {
+        self.pacing.as_ref()
     }
 
-    /// Set character size using width and height multipliers.
-    pub fn set_size(&mut self, width: u8, height: u8) -> Result<(), <T as Write>::Error> {
-        let width = core::cmp::min(width, 7);
-        let height = core::cmp::min(height, 7);
-        let param = (width << 4) | height;
-        self.raw(&[0x1D, 0x21, param])
+    /// Line ending sent by [`write_line`](Printer::write_line).
+    pub fn line_ending(&self) -> LineEnding {
+        self.line_ending
     }
 
-    /// Enable or disable inverted printing.
-    pub fn set_invert(&mut self, on: bool) -> Result<(), <T as Write>::Error> {
-        let flag = if on { 0x01 } else { 0x00 };
-        self.raw(&[0x1D, 0x42, flag])
+    /// How [`print_image`](Printer::print_image) fits images to the paper.
+    #[cfg(feature = "image")]
+    pub fn image_fit(&self) -> ImageFit {
+        self.image_fit
     }
 
-    /// Set text justification.
-    pub fn set_justification(&mut self, mode: Justification) -> Result<(), <T as Write>::Error> {
-        self.raw(&[0x1B, 0x61, mode.as_byte()])
+    /// Borrow the underlying transport.
+    pub fn transport(&self) -> &T {
+        &self.transport
     }
 
-    /// Set print density level.
-    pub fn set_density(&mut self, level: Density) -> Result<(), <T as Write>::Error> {
-        self.raw(&[0x1D, 0x7C, level.as_byte()])
+    /// Mutably borrow the underlying transport.
+    ///
+    /// Useful for vendor-specific I/O that isn't covered by the printer API.
+    pub fn transport_mut(&mut self) -> &mut T {
+        &mut self.transport
     }
 
-    /// Set print speed.
-    pub fn set_print_speed(&mut self, speed: PrintSpeed) -> Result<(), <T as Write>::Error> {
-        self.raw(&[0x1F, 0x50, speed.as_byte()])
+    /// Consume the printer, returning the underlying transport.
+    pub fn into_inner(self) -> T {
+        self.transport
     }
+}
 
-    /// Set the serial baud rate used by the printer.
+impl<T: Write, P: Delay> Printer<T, P> {
+    /// Encode and send a single command.
     ///
-    /// The baud rate value is encoded little-endian in the command sequence.
-    pub fn set_baud_rate(&mut self, baud: u32) -> Result<(), <T as Write>::Error> {
-        let b = baud.to_le_bytes();
-        self.raw(&[
-            0x1B, 0x23, 0x23, b'S', b'B', b'D', b'R', b[0], b[1], b[2], b[3],
-        ])
+    /// Parameters are checked with [`Command::validate`] first; nothing is
+    /// sent if they are out of range.
+    pub fn send(&mut self, command: &Command) -> Result<(), PrinterError<<T as Write>::Error>> {
+        command.validate()?;
+        self.send_with(command, |mut out| command.encode(&mut out))
     }
 
-    /// Configure the maximum print speed of the printer.
-    pub fn set_max_speed(&mut self, speed: u8) -> Result<(), <T as Write>::Error> {
-        self.raw(&[0x1B, 0x23, 0x23, b'S', b'T', b'S', b'P', speed])
+    /// Send `command` as the bytes produced by `write`, with the same hooks,
+    /// chunking and pacing as [`send`](Printer::send).
+    ///
+    /// For commands already encoded, e.g. recorded in a [`Job`], or
+    /// transformed on the way out.
+    pub(crate) fn send_with(
+        &mut self,
+        command: &Command,
+        write: impl FnOnce(
+            &mut dyn Write<Error = <T as Write>::Error>,
+        ) -> Result<(), <T as Write>::Error>,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.hooks.check(command)?;
+        let mut out = hooks::Hooked::new(&mut self.transport, self.hooks);
+        let result = match self.chunk_size {
+            Some(max) => write(&mut Chunked::new(&mut out, max)),
+            None => write(&mut out),
+        };
+        out.finish(command.name(), result)?;
+        if let Some(pacing) = &mut self.pacing {
+            pacing.pause(command);
+        }
+        Ok(())
     }
 
-    /// Enable or disable software flow control (XON/XOFF).
-    pub fn set_software_flow_control(&mut self, enable: bool) -> Result<(), <T as Write>::Error> {
-        let flag = if enable { 0x01 } else { 0x00 };
-        self.raw(&[0x1B, 0x23, 0x23, b'S', b'F', b'F', b'C', flag])
+    /// Take `other`'s profile, paper width, code page, font and character
+    /// width, cut fallback, degradation policy, line ending and image fit,
+    /// so output built here matches what `other` would send.
+    pub(crate) fn copy_settings<U: Write, Q: Delay>(&mut self, other: &Printer<U, Q>) {
+        self.profile = other.profile;
+        self.paper_width = other.paper_width;
+        self.code_page = other.code_page;
+        self.font = other.font;
+        self.char_width = other.char_width;
+        self.cut_fallback = other.cut_fallback;
+        self.degradation = other.degradation;
+        self.line_ending = other.line_ending;
+        #[cfg(feature = "image")]
+        {
+            self.image_fit = other.image_fit;
+        }
     }
 
-    /// Enable or disable black mark detection.
-    pub fn set_black_mark(&mut self, on: bool) -> Result<(), <T as Write>::Error> {
-        let flag = if on { 0x44 } else { 0x66 };
-        self.raw(&[0x1F, 0x1B, 0x1F, 0x80, 0x04, 0x05, 0x06, flag])
+    /// Characters that fit on one line with the paper width, font and
+    /// character width last set through this printer.
+    pub fn columns(&self) -> usize {
+        usize::from(self.paper_width.columns(self.font) / u16::from(self.char_width))
     }
 
-    /// Query the paper sensor status using `GS r 1`.
+    /// Reset the printer to its power-on settings (`ESC @`).
+    pub fn initialize(&mut self) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::Initialize)?;
+        self.font = Font::FontA;
+        self.char_width = 1;
+        self.emulation = degrade::Emulation::new();
+        Ok(())
+    }
+
+    /// Select the character code table used for bytes 0x80–0xFF.
+    pub fn set_code_page(
+        &mut self,
+        code_page: CodePage,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::SelectCodePage(code_page))?;
+        self.code_page = Some(code_page);
+        Ok(())
+    }
+
+    /// Select the international character set (`ESC R`).
+    pub fn set_charset(
+        &mut self,
+        charset: Charset,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::SetCharset(charset))
+    }
+
+    /// Set the line spacing to `units` motion units (`ESC 3`).
+    pub fn set_line_spacing(&mut self, units: u8) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::SetLineSpacing(units))
+    }
+
+    /// Return to the default line spacing (`ESC 2`).
+    pub fn reset_line_spacing(&mut self) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::DefaultLineSpacing)
+    }
+
+    /// Send the [`Startup`] sequence the printer was created with.
+    ///
+    /// Useful after the printer was power-cycled or reset, to get back to a
+    /// known state.
+    pub fn reinitialize(&mut self) -> Result<(), PrinterError<<T as Write>::Error>> {
+        let startup = self.startup;
+        if startup.initialize {
+            self.initialize()?;
+        }
+        if let Some(code_page) = startup.code_page {
+            self.set_code_page(code_page)?;
+        }
+        if let Some(charset) = startup.charset {
+            self.set_charset(charset)?;
+        }
+        if let Some(level) = startup.density {
+            self.send(&Command::SetDensity(level))?;
+        }
+        if let Some(units) = startup.line_spacing {
+            self.set_line_spacing(units)?;
+        }
+        Ok(())
+    }
+
+    /// Replace the transport, e.g. after the link dropped, and send the
+    /// [`Startup`] sequence over the new one.
+    ///
+    /// The old transport is dropped.
+    pub fn reconnect(&mut self, transport: T) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.transport = transport;
+        self.auto_status_len = 0;
+        self.reinitialize()
+    }
+
+    /// Write raw text to the printer.
+    pub fn write(&mut self, text: &str) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::Text(text))
+    }
+
+    /// Write text followed by the configured [`LineEnding`].
+    ///
+    /// Alignment and underlines that the profile lacks are emulated here
+    /// when the [`Degradation`] policy says so.
+    pub fn write_line(&mut self, text: &str) -> Result<(), PrinterError<<T as Write>::Error>> {
+        let len = text.chars().count();
+        let indent = self.emulated_indent(len);
+        self.write_repeated(b' ', indent)?;
+        self.write(text)?;
+        self.end_line()?;
+        let rule = match self.emulation.underline {
+            UnderlineMode::None => return Ok(()),
+            UnderlineMode::Single => b'-',
+            UnderlineMode::Double => b'=',
+        };
+        self.write_repeated(b' ', indent)?;
+        self.write_repeated(rule, len)?;
+        self.end_line()
+    }
+
+    fn end_line(&mut self) -> Result<(), PrinterError<<T as Write>::Error>> {
+        match self.line_ending {
+            LineEnding::Lf => self.send(&Command::LineFeed),
+            LineEnding::Cr => self.send(&Command::CarriageReturn),
+            LineEnding::CrLf => {
+                self.send(&Command::CarriageReturn)?;
+                self.send(&Command::LineFeed)
+            }
+        }
+    }
+
+    /// Print formatted text without allocating.
+    ///
+    /// ```
+    /// # use escpos_embedded::{Printer, PrinterError, Write};
+    /// # fn print<T: Write>(printer: &mut Printer<T>) -> Result<(), PrinterError<T::Error>> {
+    /// let qty = 3;
+    /// printer.print_fmt(format_args!("Qty: {qty}\n"))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn print_fmt(
+        &mut self,
+        args: core::fmt::Arguments<'_>,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        struct Adapter<'p, T: Write, P: Delay> {
+            printer: &'p mut Printer<T, P>,
+            error: Option<PrinterError<T::Error>>,
+        }
+
+        impl<T: Write, P: Delay> core::fmt::Write for Adapter<'_, T, P> {
+            fn write_str(&mut self, s: &str) -> core::fmt::Result {
+                self.printer.write(s).map_err(|err| {
+                    self.error = Some(err);
+                    core::fmt::Error
+                })
+            }
+        }
+
+        let mut adapter = Adapter {
+            printer: self,
+            error: None,
+        };
+        // A formatting error without a transport error comes from a
+        // `Display` impl.
+        core::fmt::write(&mut adapter, args).map_err(|_| {
+            adapter
+                .error
+                .unwrap_or(PrinterError::Encode { command: "Text" })
+        })
+    }
+
+    /// Print the time read from `clock`, formatted with `pattern` (see
+    /// [`DateTime::format`]), followed by the configured line ending.
+    ///
+    /// ```
+    /// # use escpos_embedded::{Clock, Printer, PrinterError, Write};
+    /// # fn print<T: Write>(printer: &mut Printer<T>, rtc: &mut impl Clock) -> Result<(), PrinterError<T::Error>> {
+    /// printer.print_timestamp(rtc, "%d/%m/%Y %H:%M")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn print_timestamp<C: Clock>(
+        &mut self,
+        clock: &mut C,
+        pattern: &str,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        let time = clock.now();
+        self.print_fmt(format_args!("{}", time.format(pattern)))?;
+        self.write_line("")
+    }
+
+    /// Print each of `lines` followed by the configured [`LineEnding`].
+    ///
+    /// Lines are gathered into 128-byte writes rather than sent one command
+    /// at a time, which suits long reports and logs on links where every
+    /// write has a cost. Alignment and underlines are emulated as by
+    /// [`write_line`](Printer::write_line).
+    pub fn print_lines<'a>(
+        &mut self,
+        lines: impl IntoIterator<Item = &'a str>,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        let ending = self.line_ending.as_bytes();
+        let rule = match self.emulation.underline {
+            UnderlineMode::None => None,
+            UnderlineMode::Single => Some(b'-'),
+            UnderlineMode::Double => Some(b'='),
+        };
+        let mut batch = batch::Batch::new();
+        for line in lines {
+            let len = line.chars().count();
+            let indent = self.emulated_indent(len);
+            self.batch_repeated(&mut batch, b' ', indent)?;
+            self.batch_text(&mut batch, line.as_bytes())?;
+            self.batch_text(&mut batch, ending)?;
+            if let Some(rule) = rule {
+                self.batch_repeated(&mut batch, b' ', indent)?;
+                self.batch_repeated(&mut batch, rule, len)?;
+                self.batch_text(&mut batch, ending)?;
+            }
+        }
+        match batch.rest() {
+            [] => Ok(()),
+            rest => self.write_text_bytes(rest),
+        }
+    }
+
+    /// Add `data` to `batch`, writing it out each time it fills up.
+    fn batch_text(
+        &mut self,
+        batch: &mut batch::Batch,
+        mut data: &[u8],
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        while !data.is_empty() {
+            if let Some(full) = batch.fill(&mut data) {
+                self.write_text_bytes(full)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Add `byte` to `batch` `count` times.
+    fn batch_repeated(
+        &mut self,
+        batch: &mut batch::Batch,
+        byte: u8,
+        mut count: usize,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        let buf = [byte; 32];
+        while count > 0 {
+            let n = count.min(buf.len());
+            self.batch_text(batch, &buf[..n])?;
+            count -= n;
+        }
+        Ok(())
+    }
+
+    fn write_text_bytes(&mut self, data: &[u8]) -> Result<(), PrinterError<<T as Write>::Error>> {
+        let command = match core::str::from_utf8(data) {
+            Ok(text) => Command::Text(text),
+            Err(_) => Command::Raw(data),
+        };
+        self.hooks.check(&command)?;
+        let mut out = hooks::Hooked::new(&mut self.transport, self.hooks);
+        let result = match self.chunk_size {
+            Some(max) => Chunked::new(&mut out, max).write(data),
+            None => out.write(data),
+        };
+        out.finish("Text", result)
+    }
+
+    /// Write text word-wrapped to [`columns`](Printer::columns), ending every
+    /// line with a newline.
+    ///
+    /// Existing line breaks are kept. Words longer than a line are split.
+    pub fn write_wrapped(&mut self, text: &str) -> Result<(), PrinterError<<T as Write>::Error>> {
+        let columns = self.columns().max(1);
+        for paragraph in text.lines() {
+            let mut rest = Some(paragraph);
+            while let Some(text) = rest {
+                let (line, next) = wrap::split_line(text, columns);
+                self.write_line(line)?;
+                rest = next;
+            }
+        }
+        Ok(())
+    }
+
+    /// Discard the text of the current line in page mode (`CAN`).
+    ///
+    /// Ignored in standard mode; use
+    /// [`to_line_start`](Printer::to_line_start) there.
+    pub fn cancel_line(&mut self) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::CancelLine)
+    }
+
+    /// Discard the partly composed line and move back to its start
+    /// (`GS T 0`).
+    ///
+    /// Useful when a layout turns out not to fit after some of a line has
+    /// been sent: the line can be rebuilt without printing the first
+    /// attempt.
+    ///
+    /// ```
+    /// # use escpos_embedded::{Printer, Recorder};
+    /// let mut printer = Printer::new(Recorder::<64>::new());
+    /// printer.write("Coffee 3.50 (large, oat").unwrap();
+    /// printer.to_line_start().unwrap();
+    /// printer.write_line("Coffee 3.50").unwrap();
+    /// assert!(printer.transport().as_bytes().starts_with(b"Coffee 3.50 (large, oat\x1dT\x00"));
+    /// ```
+    pub fn to_line_start(&mut self) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::ToLineStart { print: false })
+    }
+
+    /// Feed the specified number of lines.
+    ///
+    /// Feeds of more than 255 lines are sent as several `ESC d` commands.
+    pub fn feed(&mut self, lines: u16) -> Result<(), PrinterError<<T as Write>::Error>> {
+        let mut left = lines;
+        loop {
+            let n = left.min(255) as u8;
+            self.send(&Command::Feed(n))?;
+            left -= u16::from(n);
+            if left == 0 {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Print the buffer and feed the paper by `mm` millimetres (`ESC J`).
+    ///
+    /// Assumes a vertical motion unit of one dot at 203 dpi (8 dots per mm),
+    /// the default on most 58mm and 80mm printers.
+    pub fn feed_paper_mm(&mut self, mm: u16) -> Result<(), PrinterError<<T as Write>::Error>> {
+        let mut left = u32::from(mm) * 8;
+        while left > 0 {
+            let n = left.min(255) as u8;
+            self.send(&Command::FeedUnits(n))?;
+            left -= u32::from(n);
+        }
+        Ok(())
+    }
+
+    /// Cut the paper using the given mode.
+    ///
+    /// The bytes sent depend on the profile's [`CutStyle`]. If the profile
+    /// has no cutter, this returns [`PrinterError::Unsupported`], or feeds
+    /// instead when a [fallback](PrinterBuilder::cut_fallback) is set.
+    pub fn cut(&mut self, mode: CutMode) -> Result<(), PrinterError<<T as Write>::Error>> {
+        if !self.check_cutter("Cut")? {
+            return Ok(());
+        }
+        match (self.profile.cut_style, mode) {
+            (CutStyle::Binary, _) | (_, CutMode::PartialThreePoints) => {
+                self.send(&Command::Cut(mode))
+            }
+            (CutStyle::Ascii, _) => self.send(&Command::Vendor {
+                name: "Cut",
+                data: &[0x1D, 0x56, b'0' + mode.as_byte()],
+            }),
+            (CutStyle::Feed(feed), _) => self.send(&Command::FeedAndCut { mode, feed }),
+            (CutStyle::Legacy, CutMode::Full) => self.send(&Command::Vendor {
+                name: "Cut",
+                data: &[0x1B, 0x69],
+            }),
+            (CutStyle::Legacy, CutMode::Partial) => {
+                self.send(&Command::Cut(CutMode::PartialThreePoints))
+            }
+        }
+    }
+
+    /// Whether a cut can go ahead, feeding instead if the profile has no
+    /// cutter and a fallback is configured.
+    ///
+    /// Returns `Ok(false)` when the fallback feed was sent.
+    fn check_cutter(
+        &mut self,
+        command: &'static str,
+    ) -> Result<bool, PrinterError<<T as Write>::Error>> {
+        if self.profile.has_cutter {
+            return Ok(true);
+        }
+        match self.cut_fallback {
+            Some(lines) => {
+                self.send(&Command::Feed(lines))?;
+                Ok(false)
+            }
+            None => Err(PrinterError::Unsupported { command }),
+        }
+    }
+
+    /// Cut, then feed backwards by `units` vertical motion units so the next
+    /// receipt starts closer to the top of the paper.
+    ///
+    /// Most printers leave around 12mm of blank paper between the print head
+    /// and the cutter, which is wasted at the top of every receipt. Pulling
+    /// the paper back after the cut reclaims it. Keep `units` below the
+    /// head-to-cutter distance or the paper may jam; values above 48 are
+    /// rejected.
+    ///
+    /// Returns [`PrinterError::Unsupported`] unless the profile sets
+    /// [`reverse_feed`](Profile::reverse_feed).
+    pub fn cut_and_reverse_feed(
+        &mut self,
+        mode: CutMode,
+        units: u8,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        let reverse = Command::ReverseFeed(units);
+        if !self.profile.reverse_feed {
+            return Err(PrinterError::Unsupported {
+                command: reverse.name(),
+            });
+        }
+        reverse.validate()?;
+        self.cut(mode)?;
+        self.send(&reverse)
+    }
+
+    /// Feed to the cutting position, plus `feed` vertical motion units, and
+    /// cut.
+    ///
+    /// Unlike [`cut`](Printer::cut), this makes sure the last printed line
+    /// has cleared the blade before cutting. With [`CutStyle::Legacy`],
+    /// which has no such command, it feeds `feed` units and cuts instead.
+    pub fn cut_with_feed(
+        &mut self,
+        mode: CutMode,
+        feed: u8,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        if !self.check_cutter("FeedAndCut")? {
+            return Ok(());
+        }
+        if self.profile.cut_style == CutStyle::Legacy {
+            self.send(&Command::FeedUnits(feed))?;
+            return self.cut(mode);
+        }
+        self.send(&Command::FeedAndCut { mode, feed })
+    }
+
+    /// Enable or disable bold mode.
+    pub fn set_bold(&mut self, on: bool) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::SetBold(on))
+    }
+
+    /// Set underline mode.
+    ///
+    /// Handled according to the [`Degradation`] policy if the profile
+    /// lacks [`Features::UNDERLINE`].
+    pub fn set_underline(
+        &mut self,
+        mode: UnderlineMode,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        match self.fallback(Features::UNDERLINE, "SetUnderline")? {
+            None => self.send(&Command::SetUnderline(mode)),
+            Some(Fallback::Emulate) => {
+                self.emulation.underline = mode;
+                Ok(())
+            }
+            Some(_) => Ok(()),
+        }
+    }
+
+    /// Set text alignment.
+    ///
+    /// Handled according to the [`Degradation`] policy if the profile
+    /// lacks [`Features::ALIGN`].
+    pub fn set_align(&mut self, align: Align) -> Result<(), PrinterError<<T as Write>::Error>> {
+        match self.fallback(Features::ALIGN, "SetAlign")? {
+            None => self.send(&Command::SetAlign(align)),
+            Some(Fallback::Emulate) => {
+                self.emulation.align = align;
+                Ok(())
+            }
+            Some(_) => Ok(()),
+        }
+    }
+
+    /// Select printer font.
+    pub fn set_font(&mut self, font: Font) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::SetFont(font))?;
+        self.font = font;
+        Ok(())
+    }
+
+    /// Set character size using width and height multipliers.
+    ///
+    /// The values are sent as-is: 0 is normal size and 7 is 8x. Larger
+    /// values return [`PrinterError::InvalidParameter`]. Prefer [`set_scale`](Printer::set_scale),
+    /// which takes the multipliers themselves.
+    pub fn set_size(
+        &mut self,
+        width: u8,
+        height: u8,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::SetSize { width, height })?;
+        self.char_width = width + 1;
+        Ok(())
+    }
+
+    /// Set character size to `width`x by `height`x, each 1–8.
+    ///
+    /// Returns [`PrinterError::InvalidParameter`] for values outside 1–8
+    /// instead of clamping.
+    pub fn try_set_size(
+        &mut self,
+        width: u8,
+        height: u8,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        for value in [width, height] {
+            if !(1..=8).contains(&value) {
+                return Err(PrinterError::InvalidParameter {
+                    command: "SetSize",
+                    value: value.into(),
+                    range: 1..=8,
+                });
+            }
+        }
+        self.set_size(width - 1, height - 1)
+    }
+
+    /// Set character size to `width` by `height` times normal.
+    ///
+    /// ```
+    /// # use escpos_embedded::{Printer, PrinterError, Scale, Write};
+    /// # fn print<T: Write>(printer: &mut Printer<T>) -> Result<(), PrinterError<T::Error>> {
+    /// printer.set_scale(Scale::DOUBLE, Scale::TRIPLE)?;
+    /// printer.write_line("TOTAL 4.20")?;
+    /// printer.set_scale(Scale::NORMAL, Scale::NORMAL)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_scale(
+        &mut self,
+        width: Scale,
+        height: Scale,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.set_size(width.get() - 1, height.get() - 1)
+    }
+
+    /// Print characters twice as wide, at normal height.
+    pub fn set_double_width(&mut self) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.set_size(1, 0)
+    }
+
+    /// Print characters twice as tall, at normal width.
+    pub fn set_double_height(&mut self) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.set_size(0, 1)
+    }
+
+    /// Print characters twice as wide and twice as tall.
+    pub fn set_quad_size(&mut self) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.set_size(1, 1)
+    }
+
+    /// Return to normal character size.
+    pub fn reset_size(&mut self) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.set_size(0, 0)
+    }
+
+    /// Enable or disable inverted printing.
+    pub fn set_invert(&mut self, on: bool) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::SetInvert(on))
+    }
+
+    /// Select the print color on two-color printers (`ESC r`).
+    pub fn set_color(&mut self, color: Color) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::SetColor(color))
+    }
+
+    /// Print the downloaded bit image (`GS /`).
+    ///
+    /// Define it first with `GS *`, e.g. through
+    /// [`define_downloaded_image`](Printer::define_downloaded_image).
+    /// Ignored if no image is defined.
+    pub fn print_downloaded_image(
+        &mut self,
+        scale: BitImageScale,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::PrintDownloadedImage(scale))
+    }
+
+    /// Enable or disable upside-down printing (`ESC {`).
+    ///
+    /// Each line is rotated 180°, but lines still print in the order sent;
+    /// see [`Job::submit_upside_down`] to rotate a whole receipt.
+    pub fn set_upside_down(&mut self, on: bool) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::SetUpsideDown(on))
+    }
+
+    /// Set text justification.
+    pub fn set_justification(
+        &mut self,
+        mode: Justification,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::SetJustification(mode))
+    }
+
+    /// Set print density level.
+    pub fn set_density(&mut self, level: Density) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::SetDensity(level))
+    }
+
+    /// Set print speed.
+    pub fn set_print_speed(
+        &mut self,
+        speed: PrintSpeed,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::SetPrintSpeed(speed))
+    }
+
+    /// Set the serial baud rate used by the printer.
+    ///
+    /// The baud rate value is encoded little-endian in the command sequence.
+    pub fn set_baud_rate(&mut self, baud: u32) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::SetBaudRate(baud))
+    }
+
+    /// Configure the maximum print speed of the printer.
+    pub fn set_max_speed(&mut self, speed: u8) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::SetMaxSpeed(speed))
+    }
+
+    /// Enable or disable software flow control (XON/XOFF).
+    pub fn set_software_flow_control(
+        &mut self,
+        enable: bool,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::SetSoftwareFlowControl(enable))
+    }
+
+    /// Enable or disable black mark detection.
+    pub fn set_black_mark(&mut self, on: bool) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::SetBlackMark(on))
+    }
+
+    /// Feed marked paper to the print start position (`GS FF`).
+    pub fn feed_to_mark(&mut self) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::FeedToMark)
+    }
+
+    /// Print the buffered data and feed to the next mark's print start
+    /// position (`FF`).
+    pub fn print_and_feed_to_mark(&mut self) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::PrintAndFeedToMark)
+    }
+
+    /// Print the model's self-test page, as selected by the profile's
+    /// [`SelfTest`].
+    ///
+    /// Saves holding the feed button while power-cycling the printer.
+    /// Returns [`PrinterError::Unsupported`] for [`SelfTest::Unsupported`].
+    pub fn print_self_test(&mut self) -> Result<(), PrinterError<<T as Write>::Error>> {
+        match self.profile.self_test {
+            SelfTest::TestPrint => self.send(&Command::TestPrint(TestPattern::StatusPrint)),
+            SelfTest::Dc2T => self.send(&Command::Vendor {
+                name: "SelfTest",
+                data: &[0x12, 0x54],
+            }),
+            SelfTest::Unsupported => Err(PrinterError::Unsupported {
+                command: "SelfTest",
+            }),
+        }
+    }
+
+    /// Put the printer into hexadecimal dump mode (`GS ( A` with `m = 1`).
+    ///
+    /// From then on the printer prints every byte it receives as hex and
+    /// ASCII instead of executing it, which shows exactly what arrives when
+    /// a transport mangles data. Commands are no longer interpreted, so the
+    /// mode can't be left from the host: power the printer off, or on most
+    /// models press FEED three times.
+    pub fn enter_hex_dump(&mut self) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.test_print(TestPattern::HexDump)
+    }
+
+    /// Print a test pattern (`GS ( A`).
+    pub fn test_print(
+        &mut self,
+        pattern: TestPattern,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::TestPrint(pattern))
+    }
+
+    /// Store `data` in the printer's 1024-byte NV user memory at `address`
+    /// (`FS g 1`).
+    ///
+    /// Useful for keeping small values such as a terminal ID inside the
+    /// printer. Data longer than 80 bytes is written in several commands.
+    /// NV memory wears out, so avoid writing it on every print.
+    pub fn write_user_memory(
+        &mut self,
+        address: u32,
+        data: &[u8],
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        check_user_memory("WriteUserMemory", address, data.len())?;
+        for (address, data) in (address..)
+            .step_by(USER_MEMORY_CHUNK)
+            .zip(data.chunks(USER_MEMORY_CHUNK))
+        {
+            self.send(&Command::WriteUserMemory { address, data })?;
+        }
+        Ok(())
+    }
+
+    /// Put the printer to sleep after `seconds` of inactivity, or never with
+    /// 0 (`ESC 8 n t1 t2`).
+    ///
+    /// Supported by most battery-powered portable printers; desktop models
+    /// ignore it.
+    pub fn set_sleep_timeout(
+        &mut self,
+        seconds: u16,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::SetSleepTimeout(seconds))
+    }
+
+    /// Wake a sleeping printer by sending a `NUL` byte, then wait `ms`
+    /// milliseconds for it to become ready.
+    ///
+    /// The first bytes received by a sleeping printer may be lost, so call
+    /// this before a job after the printer has been idle.
+    pub fn wake<D: Delay>(
+        &mut self,
+        delay: &mut D,
+        ms: u32,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::Wake)?;
+        delay.delay_ms(ms);
+        Ok(())
+    }
+
+    /// Leave user setting mode (`GS ( E` function 2).
+    ///
+    /// The printer resets to apply the new settings, so wait for it to come
+    /// back before sending more data.
+    pub fn exit_user_setting(&mut self) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::ExitUserSetting)
+    }
+
+    /// Change the bits in `mask` of memory switch `switch` (1–8) to those of
+    /// `value` (`GS ( E` function 3).
+    ///
+    /// Only valid in user setting mode; see
+    /// [`enter_user_setting`](Printer::enter_user_setting). The new value is
+    /// stored in non-volatile memory and takes effect after
+    /// [`exit_user_setting`](Printer::exit_user_setting).
+    pub fn set_memory_switch(
+        &mut self,
+        switch: u8,
+        mask: u8,
+        value: u8,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::SetMemorySwitch {
+            switch,
+            mask,
+            value,
+        })
+    }
+
+    /// Store the default print density, from -6 (lightest) to 6 (darkest),
+    /// in the printer (`GS ( E` function 5).
+    ///
+    /// Unlike [`set_density`](Printer::set_density), the value survives
+    /// power cycles. Only valid in user setting mode.
+    pub fn store_density(&mut self, level: i8) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::SetCustomValue {
+            setting: CustomSetting::Density,
+            value: i16::from(level) as u16,
+        })
+    }
+
+    /// Store the default print speed level, from 1 (slowest) to 13, in the
+    /// printer (`GS ( E` function 5).
+    ///
+    /// Unlike [`set_print_speed`](Printer::set_print_speed), the value
+    /// survives power cycles. Only valid in user setting mode. Most models
+    /// support fewer levels and ignore values above their maximum.
+    pub fn store_print_speed(
+        &mut self,
+        speed: u8,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::SetCustomValue {
+            setting: CustomSetting::PrintSpeed,
+            value: speed.into(),
+        })
+    }
+
+    /// Select the paper sensors whose state is reported as paper end
+    /// (`ESC c 3`).
+    ///
+    /// With only [`PaperSensors::END`] selected, near-end just raises
+    /// [`Status::paper_near_end`] without signalling paper end.
+    pub fn set_paper_signal_sensors(
+        &mut self,
+        sensors: PaperSensors,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::SetPaperSignalSensors(sensors))
+    }
+
+    /// Select the paper sensors that stop printing (`ESC c 4`).
+    ///
+    /// Only [`PaperSensors::NEAR_END`] is meaningful here; the printer always
+    /// stops at paper end. Pass [`PaperSensors::NONE`] to keep printing
+    /// until the roll runs out.
+    pub fn set_paper_stop_sensors(
+        &mut self,
+        sensors: PaperSensors,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::SetPaperStopSensors(sensors))
+    }
+
+    /// Print on `stations` of a hybrid printer, such as the receipt or the
+    /// journal, from now on (`ESC c 0` and `ESC c 1`).
+    ///
+    /// Settings sent afterwards, such as line spacing, also apply to
+    /// `stations`. Returns [`PrinterError::Unsupported`] unless the
+    /// profile's [`stations`](Profile::stations) include them all.
+    ///
+    /// ```
+    /// # use escpos_embedded::{Printer, PrinterError, Profile, Stations, Write};
+    /// # fn print<T: Write>(printer: &mut Printer<T>) -> Result<(), PrinterError<T::Error>> {
+    /// // Keep an audit copy on the journal of a TM-U220.
+    /// printer.select_station(Stations::RECEIPT | Stations::JOURNAL)?;
+    /// printer.write_line("Refund  -12.50")?;
+    /// printer.select_station(Stations::RECEIPT)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn select_station(
+        &mut self,
+        stations: Stations,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        if !self.profile.stations.contains(stations) {
+            return Err(PrinterError::Unsupported {
+                command: "SelectPrintStations",
+            });
+        }
+        self.send(&Command::SelectPrintStations(stations))?;
+        self.send(&Command::SelectCommandStations(stations))
+    }
+
+    /// Set how long the slip station waits for a sheet to be inserted, up
+    /// to 64 minutes, and the delay after insertion before printing starts,
+    /// in units of 100ms up to 64 (`ESC f`).
+    pub fn set_slip_wait(
+        &mut self,
+        minutes: u8,
+        delay: u8,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::SetSlipWait { minutes, delay })
+    }
+
+    /// Release the slip or validation sheet so it can be removed (`ESC q`).
+    pub fn eject_slip(&mut self) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::ReleasePaper)
+    }
+
+    /// Eject the check from the MICR reader (`FS a 2`).
+    pub fn eject_check(&mut self) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::EjectCheck)
+    }
+
+    /// Print what `f` sends on a slip, such as a check endorsement or a
+    /// validation stamp, then eject it.
+    ///
+    /// `station` is [`Stations::SLIP`] or [`Stations::VALIDATION`]; any
+    /// other station returns [`PrinterError::Unsupported`]. Once it is
+    /// selected the printer waits for the sheet to be inserted, for as
+    /// long as configured with [`set_slip_wait`](Printer::set_slip_wait).
+    /// The sheet is ejected and the receipt station selected again even if
+    /// `f` fails.
+    ///
+    /// ```
+    /// # use escpos_embedded::{Printer, PrinterError, Stations, Write};
+    /// # fn endorse<T: Write>(printer: &mut Printer<T>) -> Result<(), PrinterError<T::Error>> {
+    /// printer.set_slip_wait(1, 5)?;
+    /// printer.print_on_slip(Stations::SLIP, |slip| {
+    ///     slip.write_line("FOR DEPOSIT ONLY")?;
+    ///     slip.write_line("Harbour Grill  #004417")
+    /// })
+    /// # }
+    /// ```
+    pub fn print_on_slip<F>(
+        &mut self,
+        station: Stations,
+        f: F,
+    ) -> Result<(), PrinterError<<T as Write>::Error>>
+    where
+        F: FnOnce(&mut Self) -> Result<(), PrinterError<<T as Write>::Error>>,
+    {
+        if station != Stations::SLIP && station != Stations::VALIDATION {
+            return Err(PrinterError::Unsupported {
+                command: "SelectPrintStations",
+            });
+        }
+        self.select_station(station)?;
+        let result = f(self);
+        self.eject_slip()?;
+        self.select_station(Stations::RECEIPT)?;
+        result
+    }
+
+    /// Program the paper sensors for `policy`.
+    ///
+    /// Sends [`set_paper_signal_sensors`](Printer::set_paper_signal_sensors)
+    /// and [`set_paper_stop_sensors`](Printer::set_paper_stop_sensors) with
+    /// the sensors matching the policy. On printers with a Read transport,
+    /// [`paper_near_end`](Printer::paper_near_end) reports near-end in
+    /// either case.
+    pub fn set_near_end_policy(
+        &mut self,
+        policy: NearEndPolicy,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        let (signal, stop) = match policy {
+            NearEndPolicy::Stop => (
+                PaperSensors::NEAR_END | PaperSensors::END,
+                PaperSensors::NEAR_END,
+            ),
+            NearEndPolicy::Continue => (PaperSensors::END, PaperSensors::NONE),
+        };
+        self.set_paper_signal_sensors(signal)?;
+        self.set_paper_stop_sensors(stop)
+    }
+
+    /// Select label stock and the distance from one label to the next
+    /// (`FS ( L`).
+    ///
+    /// `length` is in units of 0.1mm and includes the gap or mark. It is
+    /// ignored for [`LabelStock::Continuous`], which switches label mode
+    /// off.
+    pub fn set_label_layout(
+        &mut self,
+        stock: LabelStock,
+        length: u16,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::SetLabelLayout { stock, length })
+    }
+
+    /// Feed to the print start position of the next label (`FS ( L C`).
+    pub fn feed_to_label(&mut self) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::FeedToLabel)
+    }
+
+    /// Enable or disable the label peeler (`FS ( L D`).
+    ///
+    /// With the peeler on, each label is separated from the backing paper
+    /// as it is printed; use
+    /// [`wait_label_taken`](Printer::wait_label_taken) before printing the
+    /// next one.
+    pub fn set_peeler(&mut self, on: bool) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::SetPeeler(on))
+    }
+
+    /// Feed the current label to the cutting position (`FS ( L B`).
+    pub fn feed_label_to_cut(&mut self) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::FeedLabelToCut)
+    }
+
+    /// Shift the print start or cut position relative to the black mark
+    /// (`GS ( F`).
+    ///
+    /// `offset` is in vertical motion units; negative values move the
+    /// position backwards. Printers accept at most ±1700 units.
+    pub fn set_mark_offset(
+        &mut self,
+        position: MarkPosition,
+        offset: i16,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::SetMarkOffset { position, offset })
+    }
+
+    /// Ask the printer to reply with process ID `id`, 0–9999, once it has
+    /// processed everything sent before (`GS ( H` function 48).
+    ///
+    /// Wait for the reply with
+    /// [`wait_process_id`](Printer::wait_process_id) to know that a batch
+    /// of commands has been handled. Using a different ID for each batch
+    /// keeps a late reply from being taken for the current one.
+    pub fn request_process_id(&mut self, id: u16) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::TransmitProcessId(id))
+    }
+
+    /// Select which status changes the printer reports automatically.
+    ///
+    /// Use [`read_auto_status`](Printer::read_auto_status) to receive the
+    /// reports. Pass [`AsbFlags::NONE`] to disable them.
+    pub fn set_auto_status_back(
+        &mut self,
+        flags: AsbFlags,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::SetAutoStatusBack(flags))
+    }
+
+    #[cfg(feature = "image")]
+    /// Print a black & white image using ESC/POS raster format.
+    ///
+    /// Images that don't match the paper width are handled according to the
+    /// [`ImageFit`] set with [`PrinterBuilder::image_fit`].
+    pub fn print_image<D>(
+        &mut self,
+        image: &Image<D>,
+    ) -> Result<(), PrinterError<<T as Write>::Error>>
+    where
+        D: AsRef<[u8]>,
+    {
+        self.print_image_scaled(image, BitImageScale::Normal)
+    }
+
+    #[cfg(feature = "image")]
+    /// Print a black & white image with the printer scaling it up (`GS v 0`
+    /// with `m` set).
+    ///
+    /// Use [`BitImageScale::DoubleWidth`] for images prepared at half the
+    /// printer's horizontal density, which would otherwise print at half
+    /// width. The [`ImageFit`] applies to the scaled width. Returns
+    /// [`PrinterError::Unsupported`] for any scaling unless the profile sets
+    /// [`raster_scaling`](Profile::raster_scaling).
+    pub fn print_image_scaled<D>(
+        &mut self,
+        image: &Image<D>,
+        scale: BitImageScale,
+    ) -> Result<(), PrinterError<<T as Write>::Error>>
+    where
+        D: AsRef<[u8]>,
+    {
+        if scale != BitImageScale::Normal && !self.profile.raster_scaling {
+            return Err(PrinterError::Unsupported {
+                command: "RasterImage",
+            });
+        }
+        let (x, _) = scale.factors();
+        let fitted = fit::Fitted::new(
+            image.check_size()?,
+            image.width,
+            image.height,
+            self.image_fit,
+            self.paper_width.dots() / x,
+            scale,
+        );
+        self.check_image_width(fitted.width().saturating_mul(x))?;
+        self.send_raster(&fitted, "RasterImage")
+    }
+
+    #[cfg(feature = "image")]
+    /// Print a black & white image as column-format bit images (`ESC *`).
+    ///
+    /// For older printers without `GS v 0`. The image is sent in bands 24
+    /// motion units tall, with the line spacing set to match and restored
+    /// to the default afterwards. Modes with a lower resolution than the
+    /// printer's skip pixels rather than stretching the image, so it prints
+    /// at the same size in every mode.
+    ///
+    /// ```
+    /// # use escpos_embedded::{BitImageMode, Image, Printer, PrinterError, Write};
+    /// # fn print<T: Write>(printer: &mut Printer<T>) -> Result<(), PrinterError<T::Error>> {
+    /// let logo = Image { width: 16, height: 2, data: [0xFF; 4] };
+    /// printer.print_image_columns(&logo, BitImageMode::DoubleDensity8)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn print_image_columns<D>(
+        &mut self,
+        image: &Image<D>,
+        mode: BitImageMode,
+    ) -> Result<(), PrinterError<<T as Write>::Error>>
+    where
+        D: AsRef<[u8]>,
+    {
+        let data = image.check_size()?;
+        self.check_image_width(image.width)?;
+        let columns = column::Columns {
+            data,
+            width: image.width,
+            height: image.height,
+            mode,
+        };
+        self.send_raster(&columns, "BitImage")
+    }
+
+    #[cfg(feature = "image")]
+    /// Print a software test pattern `height` dots tall across the full
+    /// paper width.
+    ///
+    /// ```
+    /// # use escpos_embedded::{DiagnosticPattern, Printer, PrinterError, Write};
+    /// # fn diagnose<T: Write>(printer: &mut Printer<T>) -> Result<(), PrinterError<T::Error>> {
+    /// for pattern in [DiagnosticPattern::Black, DiagnosticPattern::Ladder] {
+    ///     printer.print_test_pattern(pattern, 64)?;
+    ///     printer.feed(1)?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn print_test_pattern(
+        &mut self,
+        pattern: DiagnosticPattern,
+        height: u16,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        let width = self.paper_width.dots();
+        command::image_len("RasterImage", width, height, 8)?;
+        let pattern = pattern::Pattern {
+            kind: pattern,
+            width,
+            height,
+        };
+        self.send_raster(&pattern, "RasterImage")
+    }
+
+    #[cfg(feature = "image")]
+    /// Store `image` as the downloaded bit image in RAM (`GS *`).
+    ///
+    /// For older firmwares without NV graphics. The image is padded to a
+    /// multiple of 8 dots each way and must then fit in the printer's 1536
+    /// bytes of bit image memory, i.e. `width / 8 * height / 8` at most 1536
+    /// and `height` at most 384. Print it with
+    /// [`print_downloaded_image`](Printer::print_downloaded_image); it is
+    /// kept until it is redefined, the printer is reset, or a macro or NV
+    /// image is defined.
+    pub fn define_downloaded_image<D>(
+        &mut self,
+        image: &Image<D>,
+    ) -> Result<(), PrinterError<<T as Write>::Error>>
+    where
+        D: AsRef<[u8]>,
+    {
+        let data = image.check_size()?;
+        self.check_image_width(image.width)?;
+        command::downloaded_image_len(
+            "DefineDownloadedImage",
+            image.width.div_ceil(8),
+            image.height.div_ceil(8),
+        )?;
+        let downloaded = column::Downloaded {
+            data,
+            width: image.width,
+            height: image.height,
+        };
+        self.send_raster(&downloaded, "DefineDownloadedImage")
+    }
+
+    #[cfg(feature = "image")]
+    /// Print a 4-level grayscale image by printing it several times.
+    ///
+    /// Experimental. The image is printed in bands of 24 rows; each band is
+    /// printed once with every pixel of level 1 or more, then fed back with
+    /// `ESC K` and printed again with levels 2 and 3, so darker pixels are
+    /// burnt more often. Results depend heavily on the head and paper, and
+    /// the profile must allow it with both
+    /// [`multi_pass_gray`](Profile::multi_pass_gray) and
+    /// [`reverse_feed`](Profile::reverse_feed); otherwise
+    /// [`PrinterError::Unsupported`] is returned.
+    pub fn print_image_gray<D>(
+        &mut self,
+        image: &GrayImage<D>,
+    ) -> Result<(), PrinterError<<T as Write>::Error>>
+    where
+        D: AsRef<[u8]>,
+    {
+        if !self.profile.multi_pass_gray || !self.profile.reverse_feed {
+            return Err(PrinterError::Unsupported {
+                command: "GrayImage",
+            });
+        }
+        let data = image.check_size()?;
+        self.check_image_width(image.width)?;
+        let mut start = 0;
+        while start < image.height {
+            let end = image.height.min(start + gray::BAND);
+            for level in 1..=3 {
+                if level > 1 {
+                    self.send(&Command::ReverseFeed((end - start) as u8))?;
+                }
+                let pass = gray::Pass {
+                    data,
+                    width: image.width,
+                    rows: start..end,
+                    level,
+                };
+                self.send_raster(&pass, "GrayImage")?;
+            }
+            start = end;
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "image")]
+    /// Print a black and red image on a two-color printer.
+    ///
+    /// Both planes are stored in the print buffer with `GS ( L`, then printed
+    /// together so the colors line up.
+    pub fn print_image_two_color<D>(
+        &mut self,
+        image: &TwoColorImage<D>,
+    ) -> Result<(), PrinterError<<T as Write>::Error>>
+    where
+        D: AsRef<[u8]>,
+    {
+        let (black, red) = image.check_size()?;
+        self.check_image_width(image.width)?;
+        for (color, data) in [(Color::Black, black), (Color::Red, red)] {
+            self.send(&Command::StoreGraphics {
+                color,
+                width: image.width,
+                height: image.height,
+                data,
+            })?;
+        }
+        self.send(&Command::PrintGraphics)
+    }
+
+    #[cfg(feature = "image")]
+    fn send_raster(
+        &mut self,
+        raster: &impl Raster,
+        command: &'static str,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.hooks.check(&raster.command())?;
+        let max = self.chunk_size.unwrap_or(IMAGE_CHUNK);
+        let mut out = hooks::Hooked::new(&mut self.transport, self.hooks);
+        let result = match &mut self.pacing {
+            Some(pacing) => raster.encode(&mut Chunked::new(
+                pacing::PacedWrites {
+                    inner: &mut out,
+                    pacing,
+                },
+                max,
+            )),
+            None => raster.encode(&mut Chunked::new(&mut out, max)),
+        };
+        out.finish(command, result)
+    }
+
+    #[cfg(feature = "image")]
+    /// Print an image while pausing between chunks according to a timing model.
+    pub fn print_image_with_delay<D, Del>(
+        &mut self,
+        image: &Image<D>,
+        model: &TimingModel,
+        delay: &mut Del,
+    ) -> Result<(), PrinterError<<T as Write>::Error>>
+    where
+        D: AsRef<[u8]>,
+        Del: Delay,
+    {
+        let data = image.check_size()?;
+        self.check_image_width(image.width)?;
+        self.raw(&command::raster_header(
+            image.width,
+            image.height,
+            BitImageScale::Normal,
+        ))?;
+        for chunk in data.chunks(self.chunk_size.unwrap_or(IMAGE_CHUNK)) {
+            let mut out = hooks::Hooked::new(&mut self.transport, self.hooks);
+            let result = out.write(chunk);
+            out.finish("RasterImage", result)?;
+            let ms = model.estimate_image_chunk_ms(image.width, chunk);
+            delay.delay_ms(ms);
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "image")]
+    fn check_image_width(&self, width: u16) -> Result<(), PrinterError<<T as Write>::Error>> {
+        let dots = self.paper_width.dots();
+        if width > dots {
+            return Err(PrinterError::InvalidParameter {
+                command: "RasterImage",
+                value: width.into(),
+                range: 0..=dots.into(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Start recording a macro in the printer (`GS :`).
+    ///
+    /// Everything sent until [`end_macro`](Printer::end_macro) is stored as
+    /// well as processed. The macro holds up to 2048 bytes on most models.
+    pub fn start_macro(&mut self) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::ToggleMacroDefinition)
+    }
+
+    /// Stop recording a macro (`GS :`).
+    pub fn end_macro(&mut self) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::ToggleMacroDefinition)
+    }
+
+    /// Record everything `f` sends as the printer's macro.
+    ///
+    /// The definition is ended even if `f` fails, so the printer doesn't keep
+    /// recording.
+    pub fn define_macro<F>(&mut self, f: F) -> Result<(), PrinterError<<T as Write>::Error>>
+    where
+        F: FnOnce(&mut Self) -> Result<(), PrinterError<<T as Write>::Error>>,
+    {
+        self.start_macro()?;
+        let result = f(self);
+        self.end_macro()?;
+        result
+    }
+
+    /// Run the stored macro `times` times (`GS ^ r t m`).
+    ///
+    /// `wait` is the pause between repetitions in units of 100ms.
+    pub fn execute_macro(
+        &mut self,
+        times: u8,
+        wait: u8,
+        mode: MacroMode,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::ExecuteMacro { times, wait, mode })
+    }
+
+    /// Send raw bytes directly to the printer.
+    pub fn raw(&mut self, data: &[u8]) -> Result<(), PrinterError<<T as Write>::Error>> {
+        self.send(&Command::Raw(data))
+    }
+
+    /// Send a captured ESC/POS stream, such as a `.bin` print file.
+    ///
+    /// Unlike [`raw`](Printer::raw), the stream is split into commands, so
+    /// [pacing](PrinterBuilder::pacing) applies after its cuts and feeds, and
+    /// errors name the command that failed. The bytes are sent unchanged,
+    /// in pieces of the configured chunk size.
+    ///
+    /// With `validate`, the whole stream is checked first and
+    /// [`PrinterError::InvalidData`] is returned, before anything is sent, if
+    /// it contains an unrecognised or truncated command.
+    ///
+    /// ```
+    /// # use escpos_embedded::{Printer, PrinterError, Recorder};
+    /// let mut printer = Printer::new(Recorder::<64>::new());
+    /// printer.replay(b"\x1b@Hello\n\x1dV\x00", true).unwrap();
+    /// let err = printer.replay(b"Hello\n\x1dV", true).unwrap_err();
+    /// assert!(matches!(err, PrinterError::InvalidData { offset: 6, .. }));
+    /// ```
+    pub fn replay(
+        &mut self,
+        data: &[u8],
+        validate: bool,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        if validate {
+            if let Some(offset) = decoder::first_invalid(data) {
+                return Err(PrinterError::InvalidData {
+                    command: "Replay",
+                    offset,
+                });
+            }
+        }
+        let mut decoder = Decoder::new(data);
+        let mut start = 0;
+        while let Some(command) = decoder.next() {
+            let bytes = &data[start..decoder.position()];
+            start = decoder.position();
+            self.send_with(&command, |out| out.write(bytes))?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, P: Delay> Printer<T, P>
+where
+    T: Write + Read<Error = <T as Write>::Error>,
+{
+    /// Query the paper sensor status using `GS r 1`.
     ///
     /// Returns the raw status byte reported by the printer.
-    pub fn paper_status(&mut self) -> Result<u8, <T as Write>::Error> {
-        self.raw(&[0x1D, 0x72, 0x01])?;
+    pub fn paper_status(&mut self) -> Result<u8, PrinterError<<T as Write>::Error>> {
+        let command = Command::QueryPaperStatus;
+        self.send(&command)?;
+        self.read_byte(command.name())
+    }
+
+    /// Query a single real-time status byte using `DLE EOT n`.
+    ///
+    /// Returns [`PrinterError::InvalidResponse`] if the printer doesn't
+    /// answer, so a silent printer is never mistaken for a ready one.
+    pub fn transmit_status(
+        &mut self,
+        request: StatusRequest,
+    ) -> Result<u8, PrinterError<<T as Write>::Error>> {
+        let command = Command::TransmitStatus(request);
+        self.send(&command)?;
+        self.read_byte(command.name())
+    }
+
+    /// Whether the roll paper is nearly used up, from the roll paper sensor
+    /// status (`DLE EOT 4`).
+    pub fn paper_near_end(&mut self) -> Result<bool, PrinterError<<T as Write>::Error>> {
+        let paper = self.transmit_status(StatusRequest::PaperSensor)?;
+        Ok(Status::from_responses(0, 0, 0, paper).paper_near_end)
+    }
+
+    /// Query all four real-time status bytes and decode them.
+    pub fn status(&mut self) -> Result<Status, PrinterError<<T as Write>::Error>> {
+        Ok(Status::from_responses(
+            self.transmit_status(StatusRequest::Printer)?,
+            self.transmit_status(StatusRequest::Offline)?,
+            self.transmit_status(StatusRequest::Error)?,
+            self.transmit_status(StatusRequest::PaperSensor)?,
+        ))
+    }
+
+    /// Query a printer ID byte using `GS I n`.
+    pub fn printer_id(
+        &mut self,
+        kind: PrinterIdKind,
+    ) -> Result<u8, PrinterError<<T as Write>::Error>> {
+        let command = Command::TransmitPrinterId(kind);
+        self.send(&command)?;
+        self.read_byte(command.name())
+    }
+
+    /// Identify the printer with `GS I` and switch to a matching profile.
+    ///
+    /// Same as [`detect_profile_with`](Printer::detect_profile_with)
+    /// without any known models, so the profile is always built from the
+    /// type ID.
+    pub fn detect_profile(&mut self) -> Result<Profile, PrinterError<<T as Write>::Error>> {
+        self.detect_profile_with(&[])
+    }
+
+    /// Identify the printer with `GS I` and switch to a matching profile.
+    ///
+    /// The model ID (`GS I 1`) is looked up in `models`. Unknown models get
+    /// a profile built by [`Profile::from_type_id`] from the type ID
+    /// (`GS I 2`), and printers that don't answer get [`Profile::BASIC`].
+    /// Model IDs are vendor-specific, so `models` lists the ones in use:
+    ///
+    /// ```
+    /// # use escpos_embedded::{Printer, PrinterError, Profile, Read, Write};
+    /// # fn connect<T>(printer: &mut Printer<T>) -> Result<(), PrinterError<<T as Write>::Error>>
+    /// # where T: Write + Read<Error = <T as Write>::Error> {
+    /// let profile = printer.detect_profile_with(&[(0x40, Profile::TM_H6000)])?;
+    /// if !profile.has_cutter {
+    ///     // ask the operator to tear the receipt off
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// The paper width is left as configured, since `GS I` doesn't report
+    /// it. Silence is detected by a read returning no data, so the
+    /// transport needs a read timeout.
+    pub fn detect_profile_with(
+        &mut self,
+        models: &[(u8, Profile)],
+    ) -> Result<Profile, PrinterError<<T as Write>::Error>> {
+        let profile = match self.query_id(PrinterIdKind::Model)? {
+            None => Profile::BASIC,
+            Some(model) => match models.iter().find(|(id, _)| *id == model) {
+                Some((_, profile)) => *profile,
+                None => match self.query_id(PrinterIdKind::Type)? {
+                    Some(type_id) => Profile::from_type_id(type_id),
+                    None => Profile::BASIC,
+                },
+            },
+        };
+        self.profile = profile;
+        Ok(profile)
+    }
+
+    /// Like [`printer_id`](Printer::printer_id), but `None` if the printer
+    /// doesn't answer.
+    fn query_id(
+        &mut self,
+        kind: PrinterIdKind,
+    ) -> Result<Option<u8>, PrinterError<<T as Write>::Error>> {
+        let command = Command::TransmitPrinterId(kind);
+        self.send(&command)?;
+        let mut id = [0u8; 1];
+        match self.read_exact(command.name(), &mut id) {
+            Ok(()) => Ok(Some(id[0])),
+            Err(PrinterError::InvalidResponse { .. }) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Whether a peeled label is waiting to be taken (`DLE EOT 8 3`).
+    ///
+    /// Returns [`PrinterError::InvalidResponse`] if the printer doesn't
+    /// answer, rather than reporting the label as taken.
+    pub fn label_waiting(&mut self) -> Result<bool, PrinterError<<T as Write>::Error>> {
+        let command = Command::TransmitPeelerStatus;
+        self.send(&command)?;
+        let mut status = [0u8; 1];
+        self.read_exact(command.name(), &mut status)?;
+        Ok(status[0] & 0x04 != 0)
+    }
+
+    /// Wait until the peeled label has been taken, so the next one isn't
+    /// printed on top of it.
+    ///
+    /// Polls [`label_waiting`](Printer::label_waiting) every `poll_ms`
+    /// milliseconds, at most `max_polls` times. Returns whether the label
+    /// was taken.
+    ///
+    /// ```
+    /// # use escpos_embedded::{Delay, Printer, PrinterError, Read, Write};
+    /// # fn print<T, D: Delay>(printer: &mut Printer<T>, delay: &mut D, labels: &[&str]) -> Result<(), PrinterError<<T as Write>::Error>>
+    /// # where
+    /// #     T: Write + Read<Error = <T as Write>::Error>,
+    /// # {
+    /// printer.set_peeler(true)?;
+    /// for label in labels {
+    ///     while !printer.wait_label_taken(delay, 200, 50)? {
+    ///         // Remind the operator, then keep waiting.
+    ///     }
+    ///     printer.write_line(label)?;
+    ///     printer.feed_to_label()?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn wait_label_taken<D: Delay>(
+        &mut self,
+        delay: &mut D,
+        poll_ms: u32,
+        max_polls: u32,
+    ) -> Result<bool, PrinterError<<T as Write>::Error>> {
+        for poll in 0..max_polls {
+            if !self.label_waiting()? {
+                return Ok(true);
+            }
+            if poll + 1 < max_polls {
+                delay.delay_ms(poll_ms);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Read a pending Automatic Status Back report, if any.
+    ///
+    /// Returns `None` when no complete 4-byte report is available. Bytes of
+    /// a partly received report are kept and completed by the next call.
+    pub fn read_auto_status(
+        &mut self,
+    ) -> Result<Option<Status>, PrinterError<<T as Write>::Error>> {
+        while self.auto_status_len < self.auto_status.len() {
+            let read = self
+                .transport
+                .read(&mut self.auto_status[self.auto_status_len..]);
+            match read.map_err(|error| PrinterError::Transport {
+                command: "AutoStatusBack",
+                error,
+            })? {
+                0 => return Ok(None),
+                n => self.auto_status_len += n,
+            }
+        }
+        self.auto_status_len = 0;
+        Ok(Some(Status::from_asb(self.auto_status)))
+    }
+
+    /// Wait for the printer's reply to
+    /// [`request_process_id`](Printer::request_process_id) with `id`
+    /// (`37 22 d1 d2 d3 d4 00`).
+    ///
+    /// Automatic Status Back packets arriving first are passed to
+    /// `on_status` instead of being mistaken for the reply, and replies for
+    /// other IDs and stray bytes are skipped. Returns
+    /// [`PrinterError::InvalidResponse`] if the transport runs dry first.
+    ///
+    /// ```
+    /// # use escpos_embedded::{Printer, PrinterError, Read, Status, Write};
+    /// # fn print<T>(printer: &mut Printer<T>) -> Result<(), PrinterError<<T as Write>::Error>>
+    /// # where
+    /// #     T: Write + Read<Error = <T as Write>::Error>,
+    /// # {
+    /// let mut last = Status::default();
+    /// printer.write_line("Order 17")?;
+    /// printer.request_process_id(17)?;
+    /// printer.wait_process_id(17, |status| last = status)?;
+    /// // Everything up to the request has been processed.
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn wait_process_id(
+        &mut self,
+        id: u16,
+        mut on_status: impl FnMut(Status),
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        const NAME: &str = "TransmitProcessId";
+        let digits = command::process_id_digits(id);
+        loop {
+            let mut first = [0u8; 1];
+            self.read_exact(NAME, &mut first)?;
+            match first[0] {
+                0x37 => {
+                    let mut kind = [0u8; 1];
+                    self.read_exact(NAME, &mut kind)?;
+                    if kind[0] != 0x22 {
+                        continue;
+                    }
+                    let mut reply = [0u8; 5];
+                    self.read_exact(NAME, &mut reply)?;
+                    if reply[..4] == digits && reply[4] == 0x00 {
+                        return Ok(());
+                    }
+                }
+                // ASB packets start with `0xx1xx00`.
+                byte if byte & 0x93 == 0x10 => {
+                    let mut packet = [byte, 0, 0, 0];
+                    self.read_exact(NAME, &mut packet[1..])?;
+                    on_status(Status::from_asb(packet));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Read the MICR line of the check in the slip station (`FS a 0`), then
+    /// fetch it (`FS b`).
+    ///
+    /// The printer must have a MICR reader, as on the TM-H6000. The check
+    /// stays in the station afterwards, so it can be endorsed with
+    /// [`print_on_slip`](Printer::print_on_slip) or ejected with
+    /// [`eject_check`](Printer::eject_check). The reply is expected as
+    /// `5F <status> <line> 00`; anything else is a
+    /// [`PrinterError::InvalidResponse`].
+    ///
+    /// ```
+    /// # use escpos_embedded::{Printer, PrinterError, Read, Write};
+    /// # fn deposit<T>(printer: &mut Printer<T>) -> Result<(), PrinterError<<T as Write>::Error>>
+    /// # where
+    /// #     T: Write + Read<Error = <T as Write>::Error>,
+    /// # {
+    /// let line = printer.read_check()?;
+    /// if let Some(routing) = line.fields().routing {
+    ///     // look up the bank ...
+    /// }
+    /// printer.eject_check()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_check(&mut self) -> Result<MicrLine, PrinterError<<T as Write>::Error>> {
+        self.send(&Command::ReadMicr)?;
+        let command = Command::TransmitMicr;
+        self.send(&command)?;
+        let invalid = || PrinterError::InvalidResponse {
+            command: command.name(),
+        };
+        let mut header = [0u8; 2];
+        self.read_exact(command.name(), &mut header)?;
+        let [0x5F, status] = header else {
+            return Err(invalid());
+        };
+        let mut line = [0u8; micr::MICR_MAX];
+        let mut len = 0;
+        loop {
+            let mut byte = [0u8; 1];
+            self.read_exact(command.name(), &mut byte)?;
+            match (byte[0], line.get_mut(len)) {
+                (0x00, _) => break,
+                (byte, Some(slot)) => *slot = byte,
+                (_, None) => return Err(invalid()),
+            }
+            len += 1;
+        }
+        MicrLine::new(status, &line[..len]).ok_or_else(invalid)
+    }
+
+    /// Enter user setting mode (`GS ( E` function 1), waiting for the
+    /// printer's acknowledgement.
+    ///
+    /// Printing stops until [`exit_user_setting`](Printer::exit_user_setting)
+    /// is called.
+    pub fn enter_user_setting(&mut self) -> Result<(), PrinterError<<T as Write>::Error>> {
+        let command = Command::EnterUserSetting;
+        self.send(&command)?;
+        let mut reply = [0u8; 3];
+        self.read_exact(command.name(), &mut reply)?;
+        if reply != [0x37, 0x20, 0x00] {
+            return Err(PrinterError::InvalidResponse {
+                command: command.name(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Read memory switch `switch` (1–8) (`GS ( E` function 4).
+    ///
+    /// Only valid in user setting mode. Bit 0 of the result is switch
+    /// `switch`-1, bit 7 is `switch`-8.
+    pub fn memory_switch(&mut self, switch: u8) -> Result<u8, PrinterError<<T as Write>::Error>> {
+        let command = Command::TransmitMemorySwitch(switch);
+        self.send(&command)?;
+        let mut reply = [0u8; 11];
+        self.read_exact(command.name(), &mut reply)?;
+        let invalid = || PrinterError::InvalidResponse {
+            command: command.name(),
+        };
+        let [0x37, 0x21, bits @ .., 0x00] = reply else {
+            return Err(invalid());
+        };
+        bits.iter().try_fold(0u8, |value, &bit| match bit {
+            b'0' | b'1' => Ok(value << 1 | (bit - b'0')),
+            _ => Err(invalid()),
+        })
+    }
+
+    /// Fill `buf` from NV user memory starting at `address` (`FS g 2`).
+    pub fn read_user_memory(
+        &mut self,
+        address: u32,
+        buf: &mut [u8],
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        check_user_memory("ReadUserMemory", address, buf.len())?;
+        for (address, chunk) in (address..)
+            .step_by(USER_MEMORY_CHUNK)
+            .zip(buf.chunks_mut(USER_MEMORY_CHUNK))
+        {
+            let command = Command::ReadUserMemory {
+                address,
+                len: chunk.len() as u16,
+            };
+            self.send(&command)?;
+            // Header `5F`, the data, then `NUL`.
+            let mut reply = [0u8; USER_MEMORY_CHUNK + 2];
+            let reply = &mut reply[..chunk.len() + 2];
+            self.read_exact(command.name(), reply)?;
+            let [0x5F, data @ .., 0x00] = reply else {
+                return Err(PrinterError::InvalidResponse {
+                    command: command.name(),
+                });
+            };
+            chunk.copy_from_slice(data);
+        }
+        Ok(())
+    }
+
+    /// Read the stored print density (`GS ( E` function 6).
+    ///
+    /// Only valid in user setting mode.
+    pub fn stored_density(&mut self) -> Result<i8, PrinterError<<T as Write>::Error>> {
+        let command = Command::TransmitCustomValue(CustomSetting::Density);
+        let value = self.custom_value(&command)? as i16;
+        i8::try_from(value).map_err(|_| PrinterError::InvalidResponse {
+            command: command.name(),
+        })
+    }
+
+    /// Read the stored print speed level (`GS ( E` function 6).
+    ///
+    /// Only valid in user setting mode.
+    pub fn stored_print_speed(&mut self) -> Result<u8, PrinterError<<T as Write>::Error>> {
+        let command = Command::TransmitCustomValue(CustomSetting::PrintSpeed);
+        let value = self.custom_value(&command)?;
+        u8::try_from(value).map_err(|_| PrinterError::InvalidResponse {
+            command: command.name(),
+        })
+    }
+
+    /// Send a `GS ( E` function 6 request and parse the reply,
+    /// `37 27 [a 1F] <decimal> 00`.
+    fn custom_value(
+        &mut self,
+        command: &Command,
+    ) -> Result<u16, PrinterError<<T as Write>::Error>> {
+        let name = command.name();
+        let invalid = || PrinterError::InvalidResponse { command: name };
+        self.send(command)?;
+        let mut reply = [0u8; 12];
+        let mut len = 0;
+        loop {
+            let byte = &mut reply[len..len + 1];
+            self.read_exact(name, byte)?;
+            if byte[0] == 0x00 {
+                break;
+            }
+            len += 1;
+            if len == reply.len() {
+                return Err(invalid());
+            }
+        }
+        let [0x37, 0x27, data @ ..] = &reply[..len] else {
+            return Err(invalid());
+        };
+        let digits = match data.iter().position(|&b| b == 0x1F) {
+            Some(separator) => &data[separator + 1..],
+            None => data,
+        };
+        core::str::from_utf8(digits)
+            .ok()
+            .and_then(|digits| digits.parse().ok())
+            .ok_or_else(invalid)
+    }
+
+    /// Fill `buf` from the transport, failing if the printer stops sending.
+    fn read_exact(
+        &mut self,
+        command: &'static str,
+        buf: &mut [u8],
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        let mut filled = 0;
+        while filled < buf.len() {
+            let read = self.transport.read(&mut buf[filled..]);
+            match read.map_err(|error| PrinterError::Transport { command, error })? {
+                0 => return Err(PrinterError::InvalidResponse { command }),
+                n => filled += n,
+            }
+        }
+        Ok(())
+    }
+
+    /// Read a one-byte reply, failing if the printer doesn't answer.
+    fn read_byte(
+        &mut self,
+        command: &'static str,
+    ) -> Result<u8, PrinterError<<T as Write>::Error>> {
         let mut buf = [0u8; 1];
-        self.transport.read(&mut buf)?;
+        self.read_exact(command, &mut buf)?;
         Ok(buf[0])
     }
+}
+
+#[cfg(any(test, feature = "std", feature = "test-util"))]
+extern crate std;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{LimitedMockTransport, MockTransport};
+    use std::vec;
+    use std::vec::Vec;
+
+    #[cfg(feature = "embedded_io")]
+    #[test]
+    fn test_embedded_io_compat() {
+        use crate::embedded_io::Compat;
+        let mut transport = Compat::new(MockTransport::with_responses(b"Ok"));
+        ::embedded_io::Write::write_all(&mut transport, b"Hi").unwrap();
+        let mut buf = [0u8; 2];
+        ::embedded_io::Read::read_exact(&mut transport, &mut buf).unwrap();
+        assert_eq!(&buf, b"Ok");
+        assert_eq!(transport.into_inner().written(), b"Hi");
+    }
+
+    #[cfg(feature = "embedded_io")]
+    #[test]
+    fn test_from_embedded_io() {
+        use crate::embedded_io::{Compat, FromEmbeddedIo};
+        let mock = MockTransport::with_responses(b"Ok");
+        let mut transport = FromEmbeddedIo(Compat::new(mock));
+        Write::write(&mut transport, b"Hi").unwrap();
+        let mut buf = [0u8; 2];
+        Read::read(&mut transport, &mut buf).unwrap();
+        assert_eq!(&buf, b"Ok");
+        assert_eq!(transport.into_inner().into_inner().written(), b"Hi");
+    }
+
+    #[test]
+    fn test_write_line() {
+        let mut printer = Printer::new(MockTransport::new());
+        printer.write_line("Hello").unwrap();
+
+        assert_eq!(printer.transport.written(), b"Hello\n".to_vec());
+    }
+
+    #[test]
+    fn test_print_fmt() {
+        let mut printer = Printer::new(MockTransport::new());
+        let (qty, price) = (3, 4.5);
+        printer
+            .print_fmt(format_args!("Qty: {qty} @ {price:.2}"))
+            .unwrap();
+        assert_eq!(printer.transport().written(), b"Qty: 3 @ 4.50");
+    }
+
+    #[test]
+    fn test_print_timestamp() {
+        let mut printer = Printer::new(MockTransport::new());
+        let mut clock = || DateTime {
+            year: 2025,
+            month: 6,
+            day: 1,
+            hour: 9,
+            minute: 30,
+            second: 0,
+        };
+        printer
+            .print_timestamp(&mut clock, "%Y-%m-%d %H:%M")
+            .unwrap();
+        assert_eq!(printer.transport().written(), b"2025-06-01 09:30\n");
+    }
+
+    #[test]
+    fn test_print_lines() {
+        let mut printer = Printer::builder(LimitedMockTransport::new(40))
+            .chunk_size(40)
+            .build()
+            .unwrap();
+        let lines = ["0123456789"; 20];
+        printer.print_lines(lines).unwrap();
+        assert_eq!(printer.transport().written(), b"0123456789\n".repeat(20));
+    }
+
+    #[test]
+    fn test_long_feeds_are_split() {
+        let mut printer = Printer::new(MockTransport::new());
+        printer.feed(300).unwrap();
+        printer.feed_paper_mm(40).unwrap();
+        let expected = [
+            0x1B, 0x64, 0xFF, 0x1B, 0x64, 0x2D, 0x1B, 0x4A, 0xFF, 0x1B, 0x4A, 0x41,
+        ];
+        assert_eq!(printer.transport().written(), expected);
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_print_image() {
+        let mut printer = Printer::new(MockTransport::new());
+        let image = Image {
+            width: 8,
+            height: 1,
+            data: &[0xAA],
+        };
+        printer.print_image(&image).unwrap();
+        let expected = [0x1D, 0x76, 0x30, 0x00, 0x01, 0x00, 0x01, 0x00, 0xAA].to_vec();
+        assert_eq!(printer.transport.written(), expected);
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_print_image_chunking() {
+        let mut printer = Printer::new(LimitedMockTransport::new(512));
+        let data = vec![0xFF; 1025];
+        let image = Image {
+            width: 8,
+            height: 1025,
+            data: &data,
+        };
+        printer.print_image(&image).unwrap();
+
+        let expected_header = [0x1D, 0x76, 0x30, 0x00, 0x01, 0x00, 0x01, 0x04];
+        let mut expected = expected_header.to_vec();
+        expected.extend_from_slice(&data);
+        assert_eq!(printer.transport.written(), expected);
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_print_image_with_delay() {
+        let mut printer = Printer::new(MockTransport::new());
+        let image = Image {
+            width: 8,
+            height: 1,
+            data: &[0xFF],
+        };
+        let mut delay = crate::mock::RecordDelay::default();
+        let model = TimingModel::new(10, 1);
+        printer
+            .print_image_with_delay(&image, &model, &mut delay)
+            .unwrap();
+        let expected_delay = model.estimate_image_chunk_ms(8, &[0xFF]);
+        assert_eq!(delay.0, vec![expected_delay]);
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_estimate_widest_image() {
+        let model = TimingModel::new(10, 1);
+        assert_eq!(model.estimate_image_chunk_ms(u16::MAX, &[0x01; 8192]), 8202);
+    }
+
+    #[test]
+    fn test_chunked_raw() {
+        let mut printer = Printer::new(Chunked::new(LimitedMockTransport::new(4), 4));
+        let data = vec![0x55; 10];
+        printer.raw(&data).unwrap();
+        assert_eq!(printer.transport.into_inner().written(), data);
+    }
+
+    #[test]
+    fn test_replay() {
+        let mut printer = Printer::builder(LimitedMockTransport::new(4))
+            .chunk_size(4)
+            .build()
+            .unwrap();
+        let data = b"\x1b@\x1bt\x00caf\x82 ok\n\x1dV\x41\x03";
+        printer.replay(data, true).unwrap();
+        assert_eq!(printer.transport.written(), data);
+
+        let err = printer.replay(b"ok\x1b\x7a\x01", true).unwrap_err();
+        assert_eq!(
+            err,
+            PrinterError::InvalidData {
+                command: "Replay",
+                offset: 2
+            }
+        );
+        printer.replay(b"ok\x1b\x7a\x01", false).unwrap();
+    }
+
+    #[test]
+    fn test_infallible() {
+        let mut transport = MockTransport::new();
+        transport.push_response(&[0x12]);
+        let mut printer = Printer::new(transport);
+        let mut p = printer.infallible();
+        assert_eq!(p.paper_status(), 0x12);
+        p.write_line("Hi");
+        let expected = [0x1D, 0x72, 0x01, b'H', b'i', b'\n'].to_vec();
+        assert_eq!(printer.transport.written(), expected);
+    }
+
+    #[test]
+    fn test_error_command_context() {
+        use crate::mock::{FaultError, FaultyTransport};
+        let mut printer = Printer::new(FaultyTransport::new(MockTransport::new()).fail_write(2));
+        printer.set_bold(true).unwrap();
+        assert_eq!(
+            printer.cut(CutMode::Full),
+            Err(PrinterError::Transport {
+                command: "Cut",
+                error: FaultError::Injected,
+            })
+        );
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_print_image_size_mismatch() {
+        let mut printer = Printer::new(MockTransport::new());
+        let image = Image {
+            width: 16,
+            height: 2,
+            data: &[0xFF; 3],
+        };
+        assert_eq!(
+            printer.print_image(&image),
+            Err(PrinterError::ImageSizeMismatch {
+                command: "RasterImage",
+                expected: 4,
+                actual: 3,
+            })
+        );
+        assert!(printer.transport.written().is_empty());
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_print_image_empty() {
+        let mut printer = Printer::new(MockTransport::new());
+        let image = Image {
+            width: 0,
+            height: 2,
+            data: &[],
+        };
+        assert_eq!(
+            printer.print_image(&image),
+            Err(PrinterError::InvalidParameter {
+                command: "RasterImage",
+                value: 0,
+                range: 1..=65535,
+            })
+        );
+        assert_eq!(
+            printer.send(&Command::RasterImage {
+                width: 8,
+                height: 2,
+                scale: BitImageScale::Normal,
+                data: &[0xFF],
+            }),
+            Err(PrinterError::ImageSizeMismatch {
+                command: "RasterImage",
+                expected: 2,
+                actual: 1,
+            })
+        );
+        assert!(printer.transport.written().is_empty());
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_print_image_fit() {
+        let mut printer = Printer::builder(MockTransport::new())
+            .paper_width(PaperWidth::Dots(16))
+            .image_fit(ImageFit::Crop)
+            .build()
+            .unwrap();
+        let image = Image {
+            width: 24,
+            height: 1,
+            data: &[0x01, 0x02, 0x03],
+        };
+        printer.print_image(&image).unwrap();
+        let expected = [0x1D, 0x76, 0x30, 0x00, 0x02, 0x00, 0x01, 0x00, 0x01, 0x02];
+        assert_eq!(printer.transport.written(), expected);
+    }
 
     #[cfg(feature = "image")]
-    /// Print a black & white image using ESC/POS raster format.
-    pub fn print_image<D>(&mut self, image: &Image<D>) -> Result<(), <T as Write>::Error>
-    where
-        D: AsRef<[u8]>,
-    {
-        let width_bytes = ((image.width + 7) / 8) as u16;
-        let x_l = (width_bytes & 0xFF) as u8;
-        let x_h = (width_bytes >> 8) as u8;
-        let y_l = (image.height & 0xFF) as u8;
-        let y_h = (image.height >> 8) as u8;
-        // GS v 0 - raster bit image, mode 0
-        self.raw(&[0x1D, 0x76, 0x30, 0x00, x_l, x_h, y_l, y_h])?;
-        let data = image.data.as_ref();
-        for chunk in data.chunks(512) {
-            self.transport.write(chunk)?;
-        }
-        Ok(())
+    #[test]
+    fn test_print_image_columns() {
+        let image = Image {
+            width: 3,
+            height: 4,
+            data: &[0xE0, 0x40, 0x40, 0xE0],
+        };
+        let mut printer = Printer::new(MockTransport::new());
+        printer
+            .print_image_columns(&image, BitImageMode::DoubleDensity24)
+            .unwrap();
+        printer
+            .print_image_columns(&image, BitImageMode::SingleDensity8)
+            .unwrap();
+        let expected = [
+            &[0x1B, 0x33, 24, 0x1B, 0x2A, 33, 3, 0][..],
+            &[0x90, 0, 0, 0xF0, 0, 0, 0x90, 0, 0],
+            b"\n\x1b2",
+            &[0x1B, 0x33, 24, 0x1B, 0x2A, 0, 2, 0],
+            &[0xC0, 0xC0],
+            b"\n\x1b2",
+        ]
+        .concat();
+        assert_eq!(printer.transport.written(), expected);
+        let images = Decoder::new(&expected).filter(|command| command.name() == "BitImage");
+        assert_eq!(
+            images.last(),
+            Some(Command::BitImage {
+                mode: BitImageMode::SingleDensity8,
+                width: 2,
+                data: &[0xC0, 0xC0],
+            })
+        );
     }
 
     #[cfg(feature = "image")]
-    /// Print an image while pausing between chunks according to a timing model.
-    pub fn print_image_with_delay<D, Del>(
-        &mut self,
-        image: &Image<D>,
-        model: &TimingModel,
-        delay: &mut Del,
-    ) -> Result<(), <T as Write>::Error>
-    where
-        D: AsRef<[u8]>,
-        Del: Delay,
-    {
-        let width_bytes = ((image.width + 7) / 8) as u16;
-        let x_l = (width_bytes & 0xFF) as u8;
-        let x_h = (width_bytes >> 8) as u8;
-        let y_l = (image.height & 0xFF) as u8;
-        let y_h = (image.height >> 8) as u8;
-        self.raw(&[0x1D, 0x76, 0x30, 0x00, x_l, x_h, y_l, y_h])?;
-        let data = image.data.as_ref();
-        for chunk in data.chunks(512) {
-            self.transport.write(chunk)?;
-            let ms = model.estimate_image_chunk_ms(image.width, chunk);
-            delay.delay_ms(ms);
-        }
-        Ok(())
+    #[test]
+    fn test_print_image_columns_tallest() {
+        let image = Image {
+            width: 1,
+            height: u16::MAX,
+            data: vec![0x80; usize::from(u16::MAX)],
+        };
+        let mut printer = Printer::new(MockTransport::new());
+        printer
+            .print_image_columns(&image, BitImageMode::SingleDensity8)
+            .unwrap();
+        let written = printer.transport.written();
+        // 2731 bands of a 5-byte header, one column and a newline.
+        assert_eq!(written.len(), 3 + 2731 * 7 + 2);
+        // The last band starts at row 65520, so only 5 of its rows exist.
+        assert_eq!(
+            written[written.len() - 5..],
+            [0x00, 0xF8, b'\n', 0x1B, 0x32]
+        );
     }
 
-    /// Send raw bytes directly to the printer.
-    pub fn raw(&mut self, data: &[u8]) -> Result<(), <T as Write>::Error> {
-        self.transport.write(data)
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_downloaded_image() {
+        let mut printer = Printer::new(MockTransport::new());
+        let image = Image {
+            width: 3,
+            height: 2,
+            data: &[0xE0, 0xA0],
+        };
+        printer.define_downloaded_image(&image).unwrap();
+        printer
+            .print_downloaded_image(BitImageScale::DoubleHeight)
+            .unwrap();
+        assert_eq!(
+            printer.transport.written(),
+            [0x1D, 0x2A, 1, 1, 0xC0, 0x80, 0xC0, 0, 0, 0, 0, 0, 0x1D, 0x2F, 2]
+        );
+        let tall = Image {
+            width: 8,
+            height: 400,
+            data: [0; 400],
+        };
+        assert_eq!(
+            printer.define_downloaded_image(&tall),
+            Err(PrinterError::InvalidParameter {
+                command: "DefineDownloadedImage",
+                value: 50,
+                range: 1..=48,
+            })
+        );
     }
-}
 
-#[cfg(test)]
-extern crate std;
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_print_image_scaled() {
+        let image = Image {
+            width: 400,
+            height: 1,
+            data: [0xFF; 50],
+        };
+        let mut printer = Printer::builder(MockTransport::new())
+            .image_fit(ImageFit::Crop)
+            .build()
+            .unwrap();
+        printer
+            .print_image_scaled(&image, BitImageScale::DoubleWidth)
+            .unwrap();
+        assert_eq!(
+            printer.transport.written()[..8],
+            command::raster_header(288, 1, BitImageScale::DoubleWidth)
+        );
+        printer.profile.raster_scaling = false;
+        assert_eq!(
+            printer.print_image_scaled(&image, BitImageScale::Quadruple),
+            Err(PrinterError::Unsupported {
+                command: "RasterImage"
+            })
+        );
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::vec;
-    use std::vec::Vec;
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_print_image_gray() {
+        let image = GrayImage {
+            width: 4,
+            height: 1,
+            data: &[0b00_01_10_11],
+        };
+        let mut printer = Printer::new(MockTransport::new());
+        assert_eq!(
+            printer.print_image_gray(&image),
+            Err(PrinterError::Unsupported {
+                command: "GrayImage"
+            })
+        );
+        let mut printer = Printer::builder(MockTransport::new())
+            .profile(Profile {
+                reverse_feed: false,
+                multi_pass_gray: true,
+                ..Profile::GENERIC
+            })
+            .build()
+            .unwrap();
+        assert_eq!(
+            printer.print_image_gray(&image),
+            Err(PrinterError::Unsupported {
+                command: "GrayImage"
+            })
+        );
+        assert!(printer.transport.written().is_empty());
+        let mut printer = Printer::builder(MockTransport::new())
+            .profile(Profile {
+                reverse_feed: true,
+                multi_pass_gray: true,
+                ..Profile::GENERIC
+            })
+            .build()
+            .unwrap();
+        printer.print_image_gray(&image).unwrap();
+        let header = command::raster_header(4, 1, BitImageScale::Normal);
+        let expected = [
+            &header[..],
+            &[0b0111_0000],
+            &[0x1B, 0x4B, 0x01],
+            &header,
+            &[0b0011_0000],
+            &[0x1B, 0x4B, 0x01],
+            &header,
+            &[0b0001_0000],
+        ]
+        .concat();
+        assert_eq!(printer.transport.written(), expected);
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_print_image_two_color() {
+        let mut printer = Printer::new(MockTransport::new());
+        let image = TwoColorImage {
+            width: 8,
+            height: 1,
+            black: [0xF0],
+            red: [0x0F],
+        };
+        printer.print_image_two_color(&image).unwrap();
+        let expected = [
+            0x1D, 0x28, 0x4C, 0x0B, 0x00, 0x30, 0x70, 0x30, 0x01, 0x01, 0x31, 0x08, 0x00, 0x01,
+            0x00, 0xF0, 0x1D, 0x28, 0x4C, 0x0B, 0x00, 0x30, 0x70, 0x30, 0x01, 0x01, 0x32, 0x08,
+            0x00, 0x01, 0x00, 0x0F, 0x1D, 0x28, 0x4C, 0x02, 0x00, 0x30, 0x32,
+        ];
+        assert_eq!(printer.transport.written(), expected);
+    }
 
-    struct MockTransport {
-        buffer: Vec<u8>,
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_print_image_wider_than_paper() {
+        let mut printer = Printer::builder(MockTransport::new())
+            .paper_width(PaperWidth::Mm58)
+            .build()
+            .unwrap();
+        let image = Image {
+            width: 392,
+            height: 1,
+            data: &[0xFF; 49],
+        };
+        assert_eq!(
+            printer.print_image(&image),
+            Err(PrinterError::InvalidParameter {
+                command: "RasterImage",
+                value: 392,
+                range: 0..=384,
+            })
+        );
+        assert!(printer.transport.written().is_empty());
     }
 
-    impl MockTransport {
-        fn new() -> Self {
-            Self { buffer: Vec::new() }
-        }
+    #[test]
+    fn test_wait_process_id() {
+        let paper_out = Status {
+            paper_end: true,
+            ..Status::default()
+        };
+        let mut transport = MockTransport::new();
+        transport.push_response(&paper_out.to_asb());
+        transport.push_response(b"\x37\x220016\x00");
+        transport.push_response(b"\x37\x220017\x00");
+        let mut printer = Printer::new(transport);
+        printer.request_process_id(17).unwrap();
+        let mut statuses = Vec::new();
+        printer
+            .wait_process_id(17, |status| statuses.push(status))
+            .unwrap();
+        assert_eq!(statuses, [Status::from_asb(paper_out.to_asb())]);
+        assert_eq!(printer.transport.written(), b"\x1d(H\x06\x00000017");
+        assert_eq!(
+            printer.wait_process_id(18, |_| {}),
+            Err(PrinterError::InvalidResponse {
+                command: "TransmitProcessId"
+            })
+        );
+        assert!(printer.request_process_id(10_000).is_err());
     }
 
-    impl Write for MockTransport {
-        type Error = core::convert::Infallible;
+    #[test]
+    fn test_send_validates() {
+        let mut printer = Printer::new(MockTransport::new());
+        assert_eq!(
+            printer.set_size(8, 0),
+            Err(PrinterError::InvalidParameter {
+                command: "SetSize",
+                value: 8,
+                range: 0..=7,
+            })
+        );
+        printer.profile.reverse_feed = true;
+        assert!(printer.cut_and_reverse_feed(CutMode::Full, 49).is_err());
+        assert!(printer.transport.written().is_empty());
+        assert_eq!(printer.columns(), 48);
+    }
 
-        fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
-            self.buffer.extend_from_slice(data);
-            Ok(())
-        }
+    #[test]
+    fn test_print_on_slip() {
+        let mut printer = Printer::new(MockTransport::new());
+        printer.profile = Profile::TM_H6000;
+        printer.set_slip_wait(1, 5).unwrap();
+        let err = printer.print_on_slip(Stations::VALIDATION, |slip| {
+            slip.write_line("PAID")?;
+            slip.set_size(8, 8)
+        });
+        assert!(err.is_err());
+        let expected: &[u8] = b"\x1Bf\x01\x05\x1Bc0\x08\x1Bc1\x08PAID\n\
+            \x1Bq\x1Bc0\x02\x1Bc1\x02";
+        assert_eq!(printer.transport.written(), expected);
+        assert_eq!(
+            printer.print_on_slip(Stations::RECEIPT, |_| Ok(())),
+            Err(PrinterError::Unsupported {
+                command: "SelectPrintStations"
+            })
+        );
+        assert!(printer.set_slip_wait(65, 0).is_err());
+        assert_eq!(printer.transport.written(), expected);
     }
 
-    impl Read for MockTransport {
-        type Error = core::convert::Infallible;
+    #[test]
+    fn test_read_check() {
+        let mut printer = Printer::new(MockTransport::with_responses(
+            b"\x5F\x00A021000021A 123456789C 0042\x00",
+        ));
+        let line = printer.read_check().unwrap();
+        assert_eq!(line.as_str(), "A021000021A 123456789C 0042");
+        assert_eq!(line.fields().check_number, Some("0042"));
+        assert_eq!(
+            printer.transport.written(),
+            [0x1C, 0x61, 0x30, 0x00, 0x1C, 0x62, 0x01]
+        );
 
-        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
-            let len = core::cmp::min(buf.len(), self.buffer.len());
-            buf[..len].copy_from_slice(&self.buffer[..len]);
-            self.buffer.drain(..len);
-            Ok(len)
+        let mut printer = Printer::new(MockTransport::with_responses(b"\x5F\x00A0210"));
+        assert_eq!(
+            printer.read_check(),
+            Err(PrinterError::InvalidResponse {
+                command: "TransmitMicr"
+            })
+        );
+        let mut long = vec![0x5F, 0x00];
+        long.resize(2 + micr::MICR_MAX + 1, b'1');
+        long.push(0x00);
+        let mut printer = Printer::new(MockTransport::with_responses(&long));
+        assert!(printer.read_check().is_err());
+    }
+
+    #[test]
+    fn test_wait_label_taken() {
+        let mut printer = Printer::new(MockTransport::with_responses(&[0x16, 0x16, 0x12]));
+        printer.set_peeler(true).unwrap();
+        assert!(printer.wait_label_taken(&mut (), 100, 5).unwrap());
+        let mut expected = vec![0x1C, 0x28, 0x4C, 0x02, 0x00, 0x44, 0x31];
+        for _ in 0..3 {
+            expected.extend_from_slice(&[0x10, 0x04, 0x08, 0x03]);
         }
+        assert_eq!(printer.transport.written(), expected);
+
+        printer.transport.push_response(&[0x16, 0x16]);
+        assert!(!printer.wait_label_taken(&mut (), 100, 2).unwrap());
+
+        assert_eq!(
+            printer.wait_label_taken(&mut (), 100, 2),
+            Err(PrinterError::InvalidResponse {
+                command: "TransmitPeelerStatus"
+            })
+        );
     }
 
-    struct LimitedMockTransport {
-        buffer: Vec<u8>,
-        max: usize,
+    #[test]
+    fn test_try_set_size() {
+        let mut printer = Printer::new(MockTransport::new());
+        printer.try_set_size(2, 8).unwrap();
+        assert_eq!(printer.columns(), 24);
+        assert_eq!(
+            printer.try_set_size(0, 1),
+            Err(PrinterError::InvalidParameter {
+                command: "SetSize",
+                value: 0,
+                range: 1..=8,
+            })
+        );
+        assert!(printer.try_set_size(1, 9).is_err());
+        assert_eq!(printer.transport.written(), [0x1D, 0x21, 0x17]);
     }
 
-    impl LimitedMockTransport {
-        fn new(max: usize) -> Self {
-            Self {
-                buffer: Vec::new(),
-                max,
+    #[test]
+    fn test_set_scale() {
+        let mut printer = Printer::new(MockTransport::new());
+        printer.set_scale(Scale::MAX, Scale::NORMAL).unwrap();
+        assert_eq!(printer.columns(), 6);
+        printer
+            .set_scale(Scale::new(2).unwrap(), Scale::TRIPLE)
+            .unwrap();
+        assert_eq!(
+            printer.transport.written(),
+            [0x1D, 0x21, 0x70, 0x1D, 0x21, 0x12]
+        );
+    }
+
+    #[test]
+    fn test_size_shortcuts() {
+        let mut printer = Printer::new(MockTransport::new());
+        printer.set_double_width().unwrap();
+        printer.set_double_height().unwrap();
+        printer.set_quad_size().unwrap();
+        assert_eq!(printer.columns(), 24);
+        printer.reset_size().unwrap();
+        assert_eq!(printer.columns(), 48);
+        let expected = [
+            0x1D, 0x21, 0x10, 0x1D, 0x21, 0x01, 0x1D, 0x21, 0x11, 0x1D, 0x21, 0x00,
+        ];
+        assert_eq!(printer.transport.written(), expected);
+    }
+
+    #[test]
+    fn test_write_wrapped() {
+        let mut printer = Printer::builder(MockTransport::new())
+            .paper_width(PaperWidth::Mm58)
+            .build()
+            .unwrap();
+        assert_eq!(printer.columns(), 32);
+        printer.set_size(1, 1).unwrap();
+        assert_eq!(printer.columns(), 16);
+        printer.transport.take_written();
+        printer.write_wrapped("Grilled halloumi wrap\nx2").unwrap();
+        assert_eq!(printer.transport.written(), b"Grilled halloumi\nwrap\nx2\n");
+        printer.set_font(Font::FontB).unwrap();
+        assert_eq!(printer.columns(), 21);
+    }
+
+    #[test]
+    fn test_define_macro() {
+        let mut printer = Printer::new(MockTransport::new());
+        printer.define_macro(|p| p.write_line("ACME Ltd")).unwrap();
+        printer.execute_macro(1, 0, MacroMode::Continuous).unwrap();
+        let mut expected = vec![0x1D, 0x3A];
+        expected.extend_from_slice(b"ACME Ltd\n");
+        expected.extend_from_slice(&[0x1D, 0x3A, 0x1D, 0x5E, 0x01, 0x00, 0x00]);
+        assert_eq!(printer.transport.written(), expected);
+    }
+
+    #[test]
+    fn test_transport_accessors() {
+        let mut printer = Printer::new(MockTransport::new());
+        printer.write("A").unwrap();
+        assert_eq!(printer.transport().written(), b"A");
+        assert_eq!(printer.transport_mut().take_written(), b"A".to_vec());
+        printer.write("B").unwrap();
+        assert_eq!(printer.into_inner().written(), b"B");
+    }
+
+    #[test]
+    fn test_write_only_transport() {
+        struct TxOnly(Vec<u8>);
+        impl Write for TxOnly {
+            type Error = core::convert::Infallible;
+
+            fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+                self.0.extend_from_slice(data);
+                Ok(())
             }
         }
-    }
 
-    impl Write for LimitedMockTransport {
-        type Error = core::convert::Infallible;
+        let mut printer = Printer::new(TxOnly(Vec::new()));
+        printer.set_bold(true).unwrap();
+        printer.write_line("Hi").unwrap();
+        printer.cut(CutMode::Full).unwrap();
+        let expected = [0x1B, 0x45, 0x01, b'H', b'i', b'\n', 0x1D, 0x56, 0x00].to_vec();
+        assert_eq!(printer.into_inner().0, expected);
+    }
 
-        fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
-            assert!(data.len() <= self.max);
-            self.buffer.extend_from_slice(data);
-            Ok(())
+    #[test]
+    fn test_send_commands() {
+        let mut printer = Printer::new(MockTransport::new());
+        let commands = [
+            Command::SetAlign(Align::Center),
+            Command::Text("Hi"),
+            Command::LineFeed,
+            Command::Feed(2),
+        ];
+        for command in &commands {
+            printer.send(command).unwrap();
         }
+        let expected = [0x1B, 0x61, 0x01, b'H', b'i', b'\n', 0x1B, 0x64, 0x02].to_vec();
+        assert_eq!(printer.transport.written(), expected);
     }
 
-    impl Read for LimitedMockTransport {
-        type Error = core::convert::Infallible;
+    #[test]
+    fn test_black_mark_positioning() {
+        let mut printer = Printer::new(MockTransport::new());
+        printer.set_mark_offset(MarkPosition::Cut, -300).unwrap();
+        printer.feed_to_mark().unwrap();
+        printer.print_and_feed_to_mark().unwrap();
+        let expected = [
+            0x1D, 0x28, 0x46, 0x04, 0x00, 0x02, 0x01, 0x2C, 0x01, 0x1D, 0x0C, 0x0C,
+        ];
+        assert_eq!(printer.transport.written(), expected);
+        assert_eq!(
+            printer.set_mark_offset(MarkPosition::PrintStart, 1701),
+            Err(PrinterError::InvalidParameter {
+                command: "SetMarkOffset",
+                value: 1701,
+                range: -1700..=1700,
+            })
+        );
+        assert_eq!(
+            printer.set_mark_offset(MarkPosition::Cut, -1701),
+            Err(PrinterError::InvalidParameter {
+                command: "SetMarkOffset",
+                value: -1701,
+                range: -1700..=1700,
+            })
+        );
+    }
 
-        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
-            let len = core::cmp::min(buf.len(), self.buffer.len());
-            buf[..len].copy_from_slice(&self.buffer[..len]);
-            self.buffer.drain(..len);
-            Ok(len)
-        }
+    #[test]
+    fn test_paper_sensors() {
+        let mut printer = Printer::new(MockTransport::new());
+        printer
+            .set_paper_signal_sensors(PaperSensors::NEAR_END | PaperSensors::END)
+            .unwrap();
+        printer.set_paper_stop_sensors(PaperSensors::NONE).unwrap();
+        let expected = [0x1B, 0x63, 0x33, 0x0F, 0x1B, 0x63, 0x34, 0x00];
+        assert_eq!(printer.transport.written(), expected);
     }
 
-    #[cfg(feature = "embedded_io")]
     #[test]
-    fn test_embedded_io_compat() {
-        use crate::embedded_io::Compat;
-        let mut transport = Compat::new(MockTransport::new());
-        ::embedded_io::Write::write_all(&mut transport, b"Hi").unwrap();
-        let mut buf = [0u8; 2];
-        ::embedded_io::Read::read_exact(&mut transport, &mut buf).unwrap();
-        assert_eq!(&buf, b"Hi");
+    fn test_near_end_policy() {
+        let mut transport = MockTransport::new();
+        transport.push_response(&[0x1E]);
+        let mut printer = Printer::builder(transport)
+            .near_end_policy(NearEndPolicy::Stop)
+            .build()
+            .unwrap();
+        printer
+            .set_near_end_policy(NearEndPolicy::Continue)
+            .unwrap();
+        assert!(printer.paper_near_end().unwrap());
+        let expected = [
+            0x1B, 0x63, 0x33, 0x0F, 0x1B, 0x63, 0x34, 0x03, 0x1B, 0x63, 0x33, 0x0C, 0x1B, 0x63,
+            0x34, 0x00, 0x10, 0x04, 0x04,
+        ];
+        assert_eq!(printer.transport.written(), expected);
     }
 
-    #[cfg(feature = "embedded_io")]
     #[test]
-    fn test_from_embedded_io() {
-        use crate::embedded_io::{Compat, FromEmbeddedIo};
-        let mut transport = FromEmbeddedIo(Compat::new(MockTransport::new()));
-        Write::write(&mut transport, b"Ok").unwrap();
+    fn test_user_memory_bytes() {
+        let mut printer = Printer::new(MockTransport::with_responses(b"\x5FT4\x00"));
+        printer.write_user_memory(0x0102, b"T4").unwrap();
         let mut buf = [0u8; 2];
-        Read::read(&mut transport, &mut buf).unwrap();
-        assert_eq!(&buf, b"Ok");
+        printer.read_user_memory(0x0102, &mut buf).unwrap();
+        assert_eq!(&buf, b"T4");
+        let expected: &[u8] = b"\x1Cg1\x30\x02\x01\x00\x00\x02\x00T4\
+            \x1Cg2\x30\x02\x01\x00\x00\x02\x00";
+        assert_eq!(printer.transport.written(), expected);
     }
 
     #[test]
-    fn test_write_line() {
+    fn test_label_mode() {
         let mut printer = Printer::new(MockTransport::new());
-        printer.write_line("Hello").unwrap();
+        printer.set_label_layout(LabelStock::Gap, 300).unwrap();
+        printer.feed_to_label().unwrap();
+        printer.feed_label_to_cut().unwrap();
+        printer
+            .set_label_layout(LabelStock::Continuous, 300)
+            .unwrap();
+        let mut expected = [0x1C, 0x28, 0x4C, 0x06, 0x00, 0x21, b'2'].to_vec();
+        expected.extend_from_slice(b"300;");
+        expected.extend_from_slice(&[0x1C, 0x28, 0x4C, 0x02, 0x00, 0x43, 0x30]);
+        expected.extend_from_slice(&[0x1C, 0x28, 0x4C, 0x02, 0x00, 0x42, 0x30]);
+        expected.extend_from_slice(&[0x1C, 0x28, 0x4C, 0x02, 0x00, 0x21, b'0']);
+        assert_eq!(printer.transport.written(), expected);
+        let mut printer = Printer::new(MockTransport::new());
+        printer
+            .set_label_layout(LabelStock::BlackMark, 1016)
+            .unwrap();
+        assert_eq!(printer.transport.written(), b"\x1C(L\x07\x00!11016;");
+    }
 
-        assert_eq!(printer.transport.buffer, b"Hello\n".to_vec());
+    #[test]
+    fn test_memory_switch_errors() {
+        let mut printer = Printer::new(MockTransport::with_responses(b"\x37\x21012\x00"));
+        assert_eq!(
+            printer.set_memory_switch(9, 0xFF, 0x00),
+            Err(PrinterError::InvalidParameter {
+                command: "SetMemorySwitch",
+                value: 9,
+                range: 1..=8,
+            })
+        );
+        assert_eq!(
+            printer.memory_switch(1),
+            Err(PrinterError::InvalidResponse {
+                command: "TransmitMemorySwitch"
+            })
+        );
     }
 
-    #[cfg(feature = "image")]
     #[test]
-    fn test_print_image() {
+    fn test_store_density_range() {
         let mut printer = Printer::new(MockTransport::new());
-        let image = Image {
-            width: 8,
-            height: 1,
-            data: &[0xAA],
-        };
-        printer.print_image(&image).unwrap();
-        let expected = [0x1D, 0x76, 0x30, 0x00, 0x01, 0x00, 0x01, 0x00, 0xAA].to_vec();
-        assert_eq!(printer.transport.buffer, expected);
+        printer.store_density(-6).unwrap();
+        assert_eq!(
+            printer.transport.written(),
+            [0x1D, 0x28, 0x45, 0x04, 0x00, 0x05, 0x05, 0xFA, 0xFF]
+        );
+        assert_eq!(
+            printer.store_density(7),
+            Err(PrinterError::InvalidParameter {
+                command: "SetCustomValue",
+                value: 7,
+                range: -6..=6,
+            })
+        );
+        assert_eq!(
+            printer.store_density(-7),
+            Err(PrinterError::InvalidParameter {
+                command: "SetCustomValue",
+                value: -7,
+                range: -6..=6,
+            })
+        );
     }
 
-    #[cfg(feature = "image")]
     #[test]
-    fn test_print_image_chunking() {
-        let mut printer = Printer::new(LimitedMockTransport::new(512));
-        let data = vec![0xFF; 1025];
-        let image = Image {
-            width: 8,
-            height: 1025,
-            data: &data,
-        };
-        printer.print_image(&image).unwrap();
+    fn test_sleep_and_wake() {
+        let mut printer = Printer::new(MockTransport::new());
+        printer.set_sleep_timeout(600).unwrap();
+        printer.set_sleep_timeout(0).unwrap();
+        printer.wake(&mut (), 100).unwrap();
+        let expected = [
+            0x1B, 0x38, 0x01, 0x58, 0x02, 0x1B, 0x38, 0x00, 0x00, 0x00, 0x00,
+        ];
+        assert_eq!(printer.transport.written(), expected);
+    }
 
-        let expected_header = [0x1D, 0x76, 0x30, 0x00, 0x01, 0x00, 0x01, 0x04];
-        let mut expected = expected_header.to_vec();
-        expected.extend_from_slice(&data);
-        assert_eq!(printer.transport.buffer, expected);
+    #[test]
+    fn test_print_self_test() {
+        let mut printer = Printer::new(MockTransport::new());
+        printer.print_self_test().unwrap();
+        printer.profile.self_test = SelfTest::Dc2T;
+        printer.print_self_test().unwrap();
+        let expected = [0x1D, 0x28, 0x41, 0x02, 0x00, 0x00, 0x02, 0x12, 0x54];
+        assert_eq!(printer.transport.take_written(), expected);
+        printer.enter_hex_dump().unwrap();
+        assert_eq!(
+            printer.transport.written(),
+            [0x1D, 0x28, 0x41, 0x02, 0x00, 0x00, 0x01]
+        );
+        printer.profile.self_test = SelfTest::Unsupported;
+        assert_eq!(
+            printer.print_self_test(),
+            Err(PrinterError::Unsupported {
+                command: "SelfTest"
+            })
+        );
     }
 
-    #[cfg(feature = "image")]
     #[test]
-    fn test_print_image_with_delay() {
+    fn test_cut_without_cutter() {
+        let mut printer = Printer::builder(MockTransport::new())
+            .profile(Profile::GENERIC_58MM)
+            .build()
+            .unwrap();
+        assert_eq!(
+            printer.cut(CutMode::Full),
+            Err(PrinterError::Unsupported { command: "Cut" })
+        );
+        assert_eq!(
+            printer.cut_with_feed(CutMode::Full, 8),
+            Err(PrinterError::Unsupported {
+                command: "FeedAndCut"
+            })
+        );
+        assert!(printer.transport.written().is_empty());
+
+        let mut printer = Printer::builder(MockTransport::new())
+            .profile(Profile::GENERIC_58MM)
+            .cut_fallback(4)
+            .build()
+            .unwrap();
+        printer.cut(CutMode::Partial).unwrap();
+        assert_eq!(printer.transport.written(), [0x1B, 0x64, 0x04]);
+    }
+
+    #[test]
+    fn test_cut_style() {
         let mut printer = Printer::new(MockTransport::new());
-        let image = Image {
-            width: 8,
-            height: 1,
-            data: &[0xFF],
-        };
-        struct RecordDelay {
-            calls: Vec<u32>,
-        }
-        impl Delay for RecordDelay {
-            fn delay_ms(&mut self, ms: u32) {
-                self.calls.push(ms);
-            }
-        }
-        let mut delay = RecordDelay { calls: Vec::new() };
-        let model = TimingModel::new(10, 1);
-        printer
-            .print_image_with_delay(&image, &model, &mut delay)
+        printer.profile.cut_style = CutStyle::Ascii;
+        printer.cut(CutMode::Partial).unwrap();
+        printer.profile.cut_style = CutStyle::Feed(3);
+        printer.cut(CutMode::Full).unwrap();
+        printer.cut(CutMode::PartialThreePoints).unwrap();
+        let expected = [0x1D, 0x56, 0x31, 0x1D, 0x56, 0x41, 0x03, 0x1B, 0x6D];
+        assert_eq!(printer.transport.written(), expected);
+    }
+
+    #[test]
+    fn test_legacy_cut_style() {
+        let mut printer = Printer::builder(MockTransport::new())
+            .profile(Profile {
+                cut_style: CutStyle::Legacy,
+                ..Profile::GENERIC
+            })
+            .build()
             .unwrap();
-        let expected_delay = model.estimate_image_chunk_ms(8, &[0xFF]);
-        assert_eq!(delay.calls, vec![expected_delay]);
+        printer.cut(CutMode::Full).unwrap();
+        printer.cut(CutMode::Partial).unwrap();
+        printer.cut_with_feed(CutMode::Full, 24).unwrap();
+        let expected = b"\x1bi\x1bm\x1bJ\x18\x1bi";
+        assert_eq!(printer.transport.written(), expected);
+        let decoded: Vec<_> = Decoder::new(expected).collect();
+        assert_eq!(decoded[0], Command::Cut(CutMode::Full));
+    }
+
+    #[test]
+    fn test_cut_and_reverse_feed() {
+        let mut printer = Printer::new(MockTransport::new());
+        assert_eq!(
+            printer.cut_and_reverse_feed(CutMode::Full, 40),
+            Err(PrinterError::Unsupported {
+                command: "ReverseFeed"
+            })
+        );
+        assert!(printer.transport.written().is_empty());
+        printer.profile.reverse_feed = true;
+        printer.cut_and_reverse_feed(CutMode::Full, 40).unwrap();
+        assert_eq!(
+            printer.transport.written(),
+            [0x1D, 0x56, 0x00, 0x1B, 0x4B, 0x28]
+        );
+    }
+
+    #[test]
+    fn test_cut_with_feed() {
+        let mut printer = Printer::new(MockTransport::new());
+        printer.cut_with_feed(CutMode::Partial, 16).unwrap();
+        assert_eq!(printer.transport.written(), [0x1D, 0x56, 0x42, 0x10]);
     }
 
     #[test]
@@ -681,7 +4178,7 @@ mod tests {
             0x1B, 0x23, 0x23, b'S', b'B', b'D', b'R', 0x80, 0x25, 0x00, 0x00,
         ]
         .to_vec();
-        assert_eq!(printer.transport.buffer, expected);
+        assert_eq!(printer.transport.written(), expected);
     }
 
     #[test]
@@ -689,7 +4186,7 @@ mod tests {
         let mut printer = Printer::new(MockTransport::new());
         printer.set_max_speed(30).unwrap();
         let expected = [0x1B, 0x23, 0x23, b'S', b'T', b'S', b'P', 0x1E].to_vec();
-        assert_eq!(printer.transport.buffer, expected);
+        assert_eq!(printer.transport.written(), expected);
     }
 
     #[test]
@@ -697,7 +4194,7 @@ mod tests {
         let mut printer = Printer::new(MockTransport::new());
         printer.set_software_flow_control(true).unwrap();
         let expected = [0x1B, 0x23, 0x23, b'S', b'F', b'F', b'C', 0x01].to_vec();
-        assert_eq!(printer.transport.buffer, expected);
+        assert_eq!(printer.transport.written(), expected);
     }
 
     #[test]
@@ -705,17 +4202,70 @@ mod tests {
         let mut printer = Printer::new(MockTransport::new());
         printer.set_black_mark(true).unwrap();
         let expected = [0x1F, 0x1B, 0x1F, 0x80, 0x04, 0x05, 0x06, 0x44].to_vec();
-        assert_eq!(printer.transport.buffer, expected);
+        assert_eq!(printer.transport.written(), expected);
     }
 
     #[test]
     fn test_paper_status() {
         let mut transport = MockTransport::new();
-        transport.buffer.push(0x12);
+        transport.push_response(&[0x12]);
         let mut printer = Printer::new(transport);
         let status = printer.paper_status().unwrap();
         assert_eq!(status, 0x12);
         let expected = [0x1D, 0x72, 0x01].to_vec();
-        assert_eq!(printer.transport.buffer, expected);
+        assert_eq!(printer.transport.written(), expected);
+    }
+
+    #[test]
+    fn test_transmit_status() {
+        let mut transport = MockTransport::new();
+        transport.push_response(&[0x16]);
+        let mut printer = Printer::new(transport);
+        let status = printer.transmit_status(StatusRequest::Offline).unwrap();
+        assert_eq!(status, 0x16);
+        assert_eq!(printer.transport.written(), [0x10, 0x04, 0x02].to_vec());
+    }
+
+    #[test]
+    fn test_silent_printer_is_not_ready() {
+        let mut printer = Printer::new(MockTransport::new());
+        assert_eq!(
+            printer.status(),
+            Err(PrinterError::InvalidResponse {
+                command: "TransmitStatus"
+            })
+        );
+        assert_eq!(
+            printer.paper_status(),
+            Err(PrinterError::InvalidResponse {
+                command: "QueryPaperStatus"
+            })
+        );
+        assert!(printer.printer_id(PrinterIdKind::Model).is_err());
+    }
+
+    #[test]
+    fn test_detect_profile() {
+        let known = [(0x40, Profile::TM_H6000)];
+        let mut printer = Printer::new(MockTransport::with_responses(&[0x40]));
+        assert_eq!(printer.detect_profile_with(&known), Ok(Profile::TM_H6000));
+        assert_eq!(printer.transport.written(), [0x1D, 0x49, 0x01]);
+        assert_eq!(printer.profile(), &Profile::TM_H6000);
+
+        let mut printer = Printer::new(MockTransport::with_responses(&[0x20, 0x00]));
+        let profile = printer.detect_profile_with(&known).unwrap();
+        assert_eq!(profile.name, "detected");
+        assert!(!profile.has_cutter);
+        assert_eq!(
+            printer.transport.written(),
+            [0x1D, 0x49, 0x01, 0x1D, 0x49, 0x02]
+        );
+
+        let mut printer = Printer::new(MockTransport::with_responses(&[0x20, 0x02]));
+        assert!(printer.detect_profile().unwrap().has_cutter);
+
+        let mut printer = Printer::new(MockTransport::new());
+        assert_eq!(printer.detect_profile(), Ok(Profile::BASIC));
+        assert_eq!(printer.profile(), &Profile::BASIC);
     }
 }
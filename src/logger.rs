@@ -0,0 +1,95 @@
+use std::sync::{Mutex, PoisonError};
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+use super::{Delay, Printer, Write};
+
+/// [`log`] backend printing each record as a line on the printer.
+///
+/// Turns a receipt printer into a cheap hardcopy console. Records are
+/// printed as `LEVEL target: message`; messages longer than a line wrap on
+/// the printer. Printing errors are ignored, since a logger has nowhere to
+/// report them.
+///
+/// ```no_run
+/// # use escpos_embedded::{Printer, PrinterLogger};
+/// # let port = std::net::TcpStream::connect("192.168.1.50:9100").unwrap();
+/// let logger = PrinterLogger::new(Printer::new(port)).level(log::LevelFilter::Warn);
+/// log::set_logger(Box::leak(Box::new(logger))).unwrap();
+/// log::set_max_level(log::LevelFilter::Warn);
+/// log::warn!("conveyor 3 jammed");
+/// ```
+pub struct PrinterLogger<T: Write, P: Delay = ()> {
+    printer: Mutex<Printer<T, P>>,
+    level: LevelFilter,
+}
+
+impl<T: Write, P: Delay> PrinterLogger<T, P> {
+    /// Log every record to `printer`.
+    pub fn new(printer: Printer<T, P>) -> Self {
+        Self {
+            printer: Mutex::new(printer),
+            level: LevelFilter::Trace,
+        }
+    }
+
+    /// Only print records at `level` or more severe.
+    pub fn level(mut self, level: LevelFilter) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Consume the logger, returning the printer.
+    pub fn into_inner(self) -> Printer<T, P> {
+        self.printer
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T: Write + Send, P: Delay + Send> Log for PrinterLogger<T, P> {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let mut printer = self.printer.lock().unwrap_or_else(PoisonError::into_inner);
+        let _ = printer
+            .print_fmt(format_args!(
+                "{:<5} {}: {}",
+                record.level(),
+                record.target(),
+                record.args()
+            ))
+            .and_then(|()| printer.write_line(""));
+    }
+
+    fn flush(&self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockTransport;
+    use log::Level;
+
+    #[test]
+    fn test_log_records() {
+        let logger =
+            PrinterLogger::new(Printer::new(MockTransport::new())).level(LevelFilter::Info);
+        for level in [Level::Debug, Level::Warn] {
+            logger.log(
+                &Record::builder()
+                    .level(level)
+                    .target("belt")
+                    .args(format_args!("speed {}", 3))
+                    .build(),
+            );
+        }
+        let printer = logger.into_inner();
+        assert_eq!(printer.transport().written(), b"WARN  belt: speed 3\n");
+    }
+}
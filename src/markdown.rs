@@ -0,0 +1,247 @@
+use super::{Delay, Font, Printer, PrinterError, UnderlineMode, Write};
+
+/// Dashes written at a time for horizontal rules.
+const RULE: &str = "--------------------------------";
+
+/// One line of Markdown, classified.
+#[derive(Debug, Eq, PartialEq)]
+enum Block<'a> {
+    /// `#` to `######` followed by the heading text.
+    Heading(usize, &'a str),
+    /// `- item`, `* item` or `+ item`, with the indentation before it.
+    Item(&'a str, &'a str),
+    /// `---`, `***` or `___`.
+    Rule,
+    /// A ```` ``` ```` line opening or closing a code block.
+    Fence,
+    /// Anything else, including blank lines.
+    Text(&'a str),
+}
+
+fn block(line: &str) -> Block<'_> {
+    let trimmed = line.trim();
+    if trimmed.starts_with("```") {
+        return Block::Fence;
+    }
+    if trimmed.len() >= 3
+        && ['-', '*', '_']
+            .iter()
+            .any(|&c| trimmed.chars().all(|t| t == c || t == ' '))
+    {
+        return Block::Rule;
+    }
+    let level = line.bytes().take_while(|&b| b == b'#').count();
+    if (1..=6).contains(&level) && line[level..].starts_with(' ') {
+        return Block::Heading(level, line[level..].trim());
+    }
+    let body = line.trim_start();
+    let indent = &line[..line.len() - body.len()];
+    match body.as_bytes() {
+        [b'-' | b'*' | b'+', b' ', ..] => Block::Item(indent, &body[2..]),
+        _ => Block::Text(line),
+    }
+}
+
+/// Inline text and style toggles.
+#[derive(Debug, Eq, PartialEq)]
+enum Span<'a> {
+    Text(&'a str),
+    Bold,
+    Underline,
+}
+
+/// Splits a line into [`Span`]s at `**`, `_` and backslash escapes.
+///
+/// `_` inside a word, as in `snake_case`, is kept as text.
+struct Spans<'a> {
+    rest: &'a str,
+    prev: Option<char>,
+}
+
+impl<'a> Spans<'a> {
+    fn new(line: &'a str) -> Self {
+        Self {
+            rest: line,
+            prev: None,
+        }
+    }
+
+    fn advance(&mut self, len: usize) -> &'a str {
+        let (taken, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        self.prev = taken.chars().next_back().or(self.prev);
+        taken
+    }
+}
+
+impl<'a> Iterator for Spans<'a> {
+    type Item = Span<'a>;
+
+    fn next(&mut self) -> Option<Span<'a>> {
+        let mut chars = self.rest.chars();
+        let first = chars.next()?;
+        let next = chars.next();
+        if first == '\\' && matches!(next, Some('*' | '_' | '\\' | '#' | '-')) {
+            self.advance(1);
+            return Some(Span::Text(self.advance(1)));
+        }
+        if self.rest.starts_with("**") {
+            self.advance(2);
+            return Some(Span::Bold);
+        }
+        let alphanumeric = |c: Option<char>| c.is_some_and(char::is_alphanumeric);
+        if first == '_' && !(alphanumeric(self.prev) && alphanumeric(next)) {
+            self.advance(1);
+            return Some(Span::Underline);
+        }
+        let start = first.len_utf8();
+        let len = self.rest[start..]
+            .find(['*', '_', '\\'])
+            .map_or(self.rest.len(), |i| i + start);
+        Some(Span::Text(self.advance(len)))
+    }
+}
+
+impl<T: Write, P: Delay> Printer<T, P> {
+    /// Print a small subset of Markdown with printer styling.
+    ///
+    /// Supported:
+    ///
+    /// - `#` headings in double size, `##` in double height and deeper
+    ///   levels in bold;
+    /// - `**bold**` and `_underline_`, which end at the end of the line;
+    /// - `-`, `*` and `+` list items, printed with a `-` bullet;
+    /// - `---` horizontal rules across the line;
+    /// - ```` ``` ```` code blocks in Font B, printed as they are.
+    ///
+    /// Everything else is printed as text, one printed line per input line.
+    ///
+    /// ```
+    /// # use escpos_embedded::{Printer, Recorder};
+    /// let mut printer = Printer::new(Recorder::<256>::new());
+    /// printer.print_markdown("# Order 42\n- **2x** coffee\n---").unwrap();
+    /// ```
+    pub fn print_markdown(&mut self, text: &str) -> Result<(), PrinterError<<T as Write>::Error>> {
+        let font = self.font;
+        let mut code = false;
+        for line in text.lines() {
+            match block(line) {
+                Block::Fence => {
+                    code = !code;
+                    self.set_font(if code { Font::FontB } else { font })?;
+                }
+                _ if code => self.write_line(line)?,
+                Block::Heading(level, text) => {
+                    self.set_bold(true)?;
+                    match level {
+                        1 => self.set_size(1, 1)?,
+                        2 => self.set_size(0, 1)?,
+                        _ => {}
+                    }
+                    self.write_markdown_line(text)?;
+                    if level <= 2 {
+                        self.set_size(0, 0)?;
+                    }
+                    self.set_bold(false)?;
+                }
+                Block::Item(indent, text) => {
+                    self.write(indent)?;
+                    self.write("- ")?;
+                    self.write_markdown_line(text)?;
+                }
+                Block::Rule => {
+                    let mut left = self.columns();
+                    while left > 0 {
+                        let n = left.min(RULE.len());
+                        self.write(&RULE[..n])?;
+                        left -= n;
+                    }
+                    self.write_line("")?;
+                }
+                Block::Text(text) => self.write_markdown_line(text)?,
+            }
+        }
+        if code {
+            self.set_font(font)?;
+        }
+        Ok(())
+    }
+
+    /// Write one line of inline Markdown, switching off any style it leaves
+    /// on.
+    fn write_markdown_line(&mut self, line: &str) -> Result<(), PrinterError<<T as Write>::Error>> {
+        let mut bold = false;
+        let mut underline = false;
+        for span in Spans::new(line) {
+            match span {
+                Span::Text(text) => self.write(text)?,
+                Span::Bold => {
+                    bold = !bold;
+                    self.set_bold(bold)?;
+                }
+                Span::Underline => {
+                    underline = !underline;
+                    self.set_underline(if underline {
+                        UnderlineMode::Single
+                    } else {
+                        UnderlineMode::None
+                    })?;
+                }
+            }
+        }
+        if bold {
+            self.set_bold(false)?;
+        }
+        if underline {
+            self.set_underline(UnderlineMode::None)?;
+        }
+        self.write_line("")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockTransport;
+    use std::vec::Vec;
+
+    #[test]
+    fn test_blocks() {
+        assert_eq!(block("## Totals"), Block::Heading(2, "Totals"));
+        assert_eq!(block("#hashtag"), Block::Text("#hashtag"));
+        assert_eq!(block("  * two"), Block::Item("  ", "two"));
+        assert_eq!(block("- - -"), Block::Rule);
+        assert_eq!(block("```rust"), Block::Fence);
+    }
+
+    #[test]
+    fn test_spans() {
+        let spans: Vec<_> = Spans::new(r"**a** _b_ snake_case \*€").collect();
+        assert_eq!(
+            spans,
+            [
+                Span::Bold,
+                Span::Text("a"),
+                Span::Bold,
+                Span::Text(" "),
+                Span::Underline,
+                Span::Text("b"),
+                Span::Underline,
+                Span::Text(" snake"),
+                Span::Text("_case "),
+                Span::Text("*"),
+                Span::Text("€"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_print_markdown() {
+        let mut printer = Printer::new(MockTransport::new());
+        printer.print_markdown("# Hi\n**x\n```\n**y\n```").unwrap();
+        let expected: &[u8] = b"\x1BE\x01\x1D!\x11Hi\n\x1D!\x00\x1BE\x00\
+            \x1BE\x01x\x1BE\x00\n\
+            \x1BM\x01**y\n\x1BM\x00";
+        assert_eq!(printer.transport().written(), expected);
+    }
+}
@@ -0,0 +1,183 @@
+use core::fmt;
+
+/// Longest MICR line accepted from the printer, in characters.
+pub(crate) const MICR_MAX: usize = 80;
+
+/// E13B control symbols, as the printer transmits them.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Symbol {
+    Transit,
+    OnUs,
+    Amount,
+}
+
+/// Transit is sent as `A` or `t`, amount as `B` or `a` and on-us as `C` or
+/// `o`, depending on the model. Dashes (`D` or `-`) stay part of a field.
+fn symbol(c: char) -> Option<Symbol> {
+    match c {
+        'A' | 't' => Some(Symbol::Transit),
+        'B' | 'a' => Some(Symbol::Amount),
+        'C' | 'o' => Some(Symbol::OnUs),
+        _ => None,
+    }
+}
+
+/// Fields of an E13B check line, split by [`MicrFields::parse`].
+///
+/// Each field is the raw text between the control symbols with spaces
+/// trimmed, so it may contain `?` for characters the reader couldn't
+/// recognise. Missing or empty fields are `None`.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct MicrFields<'a> {
+    /// Bank routing (transit) number, between the two transit symbols.
+    pub routing: Option<&'a str>,
+    /// Account number, between the routing number and the on-us symbol.
+    pub account: Option<&'a str>,
+    /// Check serial number: the auxiliary on-us field on the left of
+    /// business checks, otherwise the field after the account number.
+    pub check_number: Option<&'a str>,
+    /// Amount encoded by the bank of first deposit, between the amount
+    /// symbols.
+    pub amount: Option<&'a str>,
+}
+
+impl<'a> MicrFields<'a> {
+    /// Split a MICR line such as `A021000021A 123456789C 0042` (transit,
+    /// routing, transit, account, on-us, check number).
+    ///
+    /// ```
+    /// # use escpos_embedded::MicrFields;
+    /// let fields = MicrFields::parse("C001234C A021000021A 1234-5678C");
+    /// assert_eq!(fields.routing, Some("021000021"));
+    /// assert_eq!(fields.account, Some("1234-5678"));
+    /// assert_eq!(fields.check_number, Some("001234"));
+    /// ```
+    pub fn parse(line: &'a str) -> Self {
+        let mut fields = Self::default();
+        let transits: [Option<usize>; 2] = {
+            let mut found = line
+                .char_indices()
+                .filter(|&(_, c)| symbol(c) == Some(Symbol::Transit))
+                .map(|(i, _)| i);
+            [found.next(), found.next()]
+        };
+        let [Some(first), Some(second)] = transits else {
+            return fields;
+        };
+        fields.routing = field(&line[first + 1..second]);
+
+        // Business checks put the serial number in an on-us field on the
+        // left of the routing number.
+        let aux = &line[..first];
+        let mut parts = aux.split(|c| symbol(c) == Some(Symbol::OnUs));
+        let aux_number = match (parts.next(), parts.next()) {
+            (Some(_), Some(number)) => field(number),
+            _ => None,
+        };
+
+        let mut rest = &line[second + 1..];
+        if let Some(start) = rest.find(|c| symbol(c) == Some(Symbol::Amount)) {
+            let amount = &rest[start + 1..];
+            let end = amount
+                .find(|c| symbol(c) == Some(Symbol::Amount))
+                .unwrap_or(amount.len());
+            fields.amount = field(&amount[..end]);
+            rest = &rest[..start];
+        }
+        match rest.find(|c| symbol(c) == Some(Symbol::OnUs)) {
+            Some(on_us) => {
+                fields.account = field(&rest[..on_us]);
+                let serial = &rest[on_us + 1..];
+                let end = serial.find(|c| symbol(c).is_some()).unwrap_or(serial.len());
+                fields.check_number = aux_number.or(field(&serial[..end]));
+            }
+            None => {
+                fields.account = field(rest);
+                fields.check_number = aux_number;
+            }
+        }
+        fields
+    }
+}
+
+fn field(text: &str) -> Option<&str> {
+    Some(text.trim()).filter(|text| !text.is_empty())
+}
+
+/// A MICR line read from a check by
+/// [`Printer::read_check`](crate::Printer::read_check).
+#[derive(Clone, Eq, PartialEq)]
+pub struct MicrLine {
+    status: u8,
+    data: [u8; MICR_MAX],
+    len: usize,
+}
+
+impl MicrLine {
+    pub(crate) fn new(status: u8, text: &[u8]) -> Option<Self> {
+        if text.len() > MICR_MAX || !text.is_ascii() {
+            return None;
+        }
+        let mut data = [0u8; MICR_MAX];
+        data[..text.len()].copy_from_slice(text);
+        Some(Self {
+            status,
+            data,
+            len: text.len(),
+        })
+    }
+
+    /// The status byte sent with the line; its bits are model-specific.
+    pub fn status(&self) -> u8 {
+        self.status
+    }
+
+    /// The line as read, with the control symbols as transmitted.
+    pub fn as_str(&self) -> &str {
+        // Checked to be ASCII in `new`.
+        core::str::from_utf8(&self.data[..self.len]).unwrap_or_default()
+    }
+
+    /// Split the line into routing, account and check numbers.
+    pub fn fields(&self) -> MicrFields<'_> {
+        MicrFields::parse(self.as_str())
+    }
+}
+
+impl fmt::Debug for MicrLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MicrLine")
+            .field("status", &self.status)
+            .field("line", &self.as_str())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_personal_check() {
+        let fields = MicrFields::parse("A021000021A 123456789C 0042");
+        assert_eq!(fields.routing, Some("021000021"));
+        assert_eq!(fields.account, Some("123456789"));
+        assert_eq!(fields.check_number, Some("0042"));
+        assert_eq!(fields.amount, None);
+    }
+
+    #[test]
+    fn test_parse_lowercase_symbols_and_amount() {
+        let fields = MicrFields::parse("o000517o t011000015t 55-1234o a0000012500a");
+        assert_eq!(fields.routing, Some("011000015"));
+        assert_eq!(fields.account, Some("55-1234"));
+        assert_eq!(fields.check_number, Some("000517"));
+        assert_eq!(fields.amount, Some("0000012500"));
+    }
+
+    #[test]
+    fn test_parse_without_routing() {
+        assert_eq!(MicrFields::parse("12?45"), MicrFields::default());
+        assert_eq!(MicrLine::new(0, &[0xC0]), None);
+    }
+}
@@ -0,0 +1,305 @@
+use std::collections::VecDeque;
+use std::vec::Vec;
+
+use super::{Delay, Read, Write};
+
+/// In-memory transport that records written bytes and replays queued
+/// responses.
+///
+/// Intended for unit-testing receipt generation without a printer:
+///
+/// ```
+/// # use escpos_embedded::{MockTransport, Printer};
+/// let mut printer = Printer::new(MockTransport::new());
+/// printer.write_line("Hi").unwrap();
+/// assert_eq!(printer.transport().written(), b"Hi\n");
+/// ```
+#[derive(Debug, Default)]
+pub struct MockTransport {
+    written: Vec<u8>,
+    responses: VecDeque<u8>,
+}
+
+impl MockTransport {
+    /// Create an empty transport.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a transport that will answer reads with `responses`.
+    pub fn with_responses(responses: &[u8]) -> Self {
+        let mut transport = Self::new();
+        transport.push_response(responses);
+        transport
+    }
+
+    /// Queue bytes to be returned by subsequent reads.
+    pub fn push_response(&mut self, data: &[u8]) {
+        self.responses.extend(data);
+    }
+
+    /// All bytes written so far.
+    pub fn written(&self) -> &[u8] {
+        &self.written
+    }
+
+    /// Take the bytes written so far, leaving the record empty.
+    pub fn take_written(&mut self) -> Vec<u8> {
+        core::mem::take(&mut self.written)
+    }
+}
+
+impl Write for MockTransport {
+    type Error = core::convert::Infallible;
+
+    fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        self.written.extend_from_slice(data);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "async")]
+impl crate::AsyncWrite for MockTransport {
+    type Error = core::convert::Infallible;
+
+    async fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        Write::write(self, data)
+    }
+}
+
+impl Read for MockTransport {
+    type Error = core::convert::Infallible;
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let len = core::cmp::min(buf.len(), self.responses.len());
+        for (slot, byte) in buf.iter_mut().zip(self.responses.drain(..len)) {
+            *slot = byte;
+        }
+        Ok(len)
+    }
+}
+
+/// [`Delay`] that records the requested pauses instead of waiting.
+#[derive(Debug, Default)]
+pub struct RecordDelay(pub Vec<u32>);
+
+impl Delay for RecordDelay {
+    fn delay_ms(&mut self, ms: u32) {
+        self.0.push(ms);
+    }
+}
+
+/// [`MockTransport`] that panics if a single write exceeds a maximum size.
+///
+/// Useful for checking that output is chunked for MTU-limited links.
+#[derive(Debug)]
+pub struct LimitedMockTransport {
+    inner: MockTransport,
+    max: usize,
+}
+
+impl LimitedMockTransport {
+    /// Create a transport accepting writes of at most `max` bytes.
+    pub fn new(max: usize) -> Self {
+        Self {
+            inner: MockTransport::new(),
+            max,
+        }
+    }
+
+    /// Queue bytes to be returned by subsequent reads.
+    pub fn push_response(&mut self, data: &[u8]) {
+        self.inner.push_response(data);
+    }
+
+    /// All bytes written so far.
+    pub fn written(&self) -> &[u8] {
+        self.inner.written()
+    }
+}
+
+impl Write for LimitedMockTransport {
+    type Error = core::convert::Infallible;
+
+    fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        assert!(
+            data.len() <= self.max,
+            "write of {} bytes exceeds limit of {}",
+            data.len(),
+            self.max
+        );
+        self.inner.write(data)
+    }
+}
+
+impl Read for LimitedMockTransport {
+    type Error = core::convert::Infallible;
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        self.inner.read(buf)
+    }
+}
+
+/// Error produced by a [`FaultyTransport`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FaultError<E> {
+    /// A failure injected by the transport's configuration.
+    Injected,
+    /// The wrapped transport failed.
+    Transport(E),
+}
+
+/// Transport wrapper that injects faults, for testing recovery logic.
+///
+/// ```
+/// # use escpos_embedded::{FaultError, FaultyTransport, MockTransport, Printer};
+/// let mut printer = Printer::new(FaultyTransport::new(MockTransport::new()).fail_write(2));
+/// printer.write("ok").unwrap();
+/// let err = printer.write("lost").unwrap_err();
+/// assert_eq!(err.transport_error(), Some(&FaultError::Injected));
+/// printer.write("ok again").unwrap();
+/// ```
+#[derive(Debug)]
+pub struct FaultyTransport<T> {
+    inner: T,
+    writes: usize,
+    fail_write: Option<usize>,
+    max_read: Option<usize>,
+    garbage: VecDeque<u8>,
+}
+
+impl<T> FaultyTransport<T> {
+    /// Wrap `inner` without any faults configured.
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            writes: 0,
+            fail_write: None,
+            max_read: None,
+            garbage: VecDeque::new(),
+        }
+    }
+
+    /// Fail the `n`th write (counting from 1) with [`FaultError::Injected`].
+    pub fn fail_write(mut self, n: usize) -> Self {
+        self.fail_write = Some(n);
+        self
+    }
+
+    /// Return at most `max` bytes from each read.
+    pub fn short_reads(mut self, max: usize) -> Self {
+        self.max_read = Some(max);
+        self
+    }
+
+    /// Return `bytes` from the next reads before any real data.
+    pub fn inject_garbage(mut self, bytes: &[u8]) -> Self {
+        self.garbage.extend(bytes);
+        self
+    }
+
+    /// Number of writes attempted so far, including failed ones.
+    pub fn writes(&self) -> usize {
+        self.writes
+    }
+
+    /// Borrow the wrapped transport.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Consume the wrapper, returning the wrapped transport.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: Write> Write for FaultyTransport<T> {
+    type Error = FaultError<T::Error>;
+
+    fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        self.writes += 1;
+        if self.fail_write == Some(self.writes) {
+            return Err(FaultError::Injected);
+        }
+        self.inner.write(data).map_err(FaultError::Transport)
+    }
+}
+
+impl<T: Read> Read for FaultyTransport<T> {
+    type Error = FaultError<T::Error>;
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let len = match self.max_read {
+            Some(max) => buf.len().min(max),
+            None => buf.len(),
+        };
+        let buf = &mut buf[..len];
+        if !self.garbage.is_empty() {
+            let n = len.min(self.garbage.len());
+            for (slot, byte) in buf.iter_mut().zip(self.garbage.drain(..n)) {
+                *slot = byte;
+            }
+            return Ok(n);
+        }
+        self.inner.read(buf).map_err(FaultError::Transport)
+    }
+}
+
+/// Poll a future that never waits to completion.
+#[cfg(all(test, feature = "async"))]
+pub(crate) fn block_on<F: core::future::Future>(future: F) -> F::Output {
+    let mut future = core::pin::pin!(future);
+    let mut cx = core::task::Context::from_waker(core::task::Waker::noop());
+    loop {
+        if let core::task::Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Printer, Status};
+
+    #[test]
+    fn test_fail_nth_write() {
+        let mut transport = FaultyTransport::new(MockTransport::new()).fail_write(2);
+        transport.write(b"a").unwrap();
+        assert_eq!(transport.write(b"b"), Err(FaultError::Injected));
+        transport.write(b"c").unwrap();
+        assert_eq!(transport.writes(), 3);
+        assert_eq!(transport.inner().written(), b"ac");
+    }
+
+    #[test]
+    fn test_auto_status_survives_short_reads() {
+        let mock = MockTransport::with_responses(&[0x18, 0x00, 0x0F, 0x00]);
+        let mut printer = Printer::new(FaultyTransport::new(mock).short_reads(1));
+        let status = printer.read_auto_status().unwrap().unwrap();
+        assert_eq!(status, Status::from_asb([0x18, 0x00, 0x0F, 0x00]));
+    }
+
+    #[test]
+    fn test_auto_status_across_reads() {
+        let mut printer = Printer::new(MockTransport::with_responses(&[0x18, 0x00]));
+        assert_eq!(printer.read_auto_status().unwrap(), None);
+        printer.transport_mut().push_response(&[0x0F, 0x00]);
+        let status = printer.read_auto_status().unwrap().unwrap();
+        assert_eq!(status, Status::from_asb([0x18, 0x00, 0x0F, 0x00]));
+        assert_eq!(printer.read_auto_status().unwrap(), None);
+    }
+
+    #[test]
+    fn test_inject_garbage() {
+        let mock = MockTransport::with_responses(&[0x12]);
+        let mut transport = FaultyTransport::new(mock).inject_garbage(&[0xEE, 0xEF]);
+        let mut buf = [0u8; 4];
+        assert_eq!(transport.read(&mut buf).unwrap(), 2);
+        assert_eq!(&buf[..2], &[0xEE, 0xEF]);
+        assert_eq!(transport.read(&mut buf).unwrap(), 1);
+        assert_eq!(buf[0], 0x12);
+    }
+}
@@ -0,0 +1,171 @@
+use std::io::{self, ErrorKind};
+use std::net::{Ipv4Addr, SocketAddr, TcpStream, UdpSocket};
+use std::time::{Duration, Instant};
+use std::vec::Vec;
+
+use super::{Read, Write};
+
+/// TCP port of the raw print service (a.k.a. JetDirect / `pdl-datastream`).
+pub const RAW_PRINT_PORT: u16 = 9100;
+
+const EPSON_PORT: u16 = 3289;
+const MDNS_PORT: u16 = 5353;
+const MDNS_GROUP: Ipv4Addr = Ipv4Addr::new(224, 0, 0, 251);
+const MDNS_SERVICE: &[&str] = &["_pdl-datastream", "_tcp", "local"];
+
+impl Write for TcpStream {
+    type Error = io::Error;
+
+    fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        io::Write::write_all(self, data)
+    }
+}
+
+/// Reads time out according to
+/// [`TcpStream::set_read_timeout`]; a timeout is reported as `Ok(0)`.
+impl Read for TcpStream {
+    type Error = io::Error;
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        match io::Read::read(self, buf) {
+            Err(err) if matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => Ok(0),
+            result => result,
+        }
+    }
+}
+
+/// How a printer found by [`discover`] answered.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum DiscoverySource {
+    /// Epson's ENPC broadcast on UDP port 3289.
+    Epson,
+    /// An mDNS `_pdl-datastream._tcp` announcement.
+    Mdns,
+}
+
+/// A networked printer found by [`discover`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct DiscoveredPrinter {
+    /// Address of the raw print service, ready for [`TcpStream::connect`].
+    ///
+    /// The port is always [`RAW_PRINT_PORT`]; neither protocol reliably
+    /// reports another one.
+    pub addr: SocketAddr,
+    /// Protocol the printer answered.
+    pub source: DiscoverySource,
+}
+
+/// Look for networked ESC/POS printers on the local subnet.
+///
+/// Broadcasts an Epson ENPC query and an mDNS query for
+/// `_pdl-datastream._tcp.local`, then collects answers until `timeout`
+/// elapses. Each printer is reported once, by the first protocol it
+/// answered:
+///
+/// ```no_run
+/// use std::net::TcpStream;
+/// use std::time::Duration;
+/// use escpos_embedded::{discover, Printer};
+///
+/// let found = discover(Duration::from_secs(2))?;
+/// if let Some(candidate) = found.first() {
+///     let mut printer = Printer::new(TcpStream::connect(candidate.addr)?);
+///     printer.write_line("Hello")?;
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn discover(timeout: Duration) -> io::Result<Vec<DiscoveredPrinter>> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+    socket.set_broadcast(true)?;
+    socket.send_to(&epson_query(), (Ipv4Addr::BROADCAST, EPSON_PORT))?;
+    socket.send_to(&mdns_query(), (MDNS_GROUP, MDNS_PORT))?;
+
+    let deadline = Instant::now() + timeout;
+    let mut found: Vec<DiscoveredPrinter> = Vec::new();
+    let mut buf = [0u8; 1500];
+    loop {
+        let left = deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return Ok(found);
+        }
+        socket.set_read_timeout(Some(left))?;
+        let (len, from) = match socket.recv_from(&mut buf) {
+            Ok(reply) => reply,
+            Err(err) if matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                return Ok(found)
+            }
+            Err(err) => return Err(err),
+        };
+        if let Some(printer) = classify(from, &buf[..len]) {
+            if !found.iter().any(|p| p.addr == printer.addr) {
+                found.push(printer);
+            }
+        }
+    }
+}
+
+/// ENPC query: `"EPSON" 'Q'`, device type 3, then a zero function code,
+/// result and parameter length.
+fn epson_query() -> [u8; 14] {
+    *b"EPSONQ\x03\x00\x00\x00\x00\x00\x00\x00"
+}
+
+/// mDNS PTR query for the raw print service, asking for a unicast reply.
+fn mdns_query() -> Vec<u8> {
+    // ID 0, no flags, one question.
+    let mut packet = std::vec![0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0];
+    for label in MDNS_SERVICE {
+        packet.push(label.len() as u8);
+        packet.extend_from_slice(label.as_bytes());
+    }
+    // Root label, type PTR, class IN with the unicast-response bit.
+    packet.extend_from_slice(&[0, 0x00, 0x0C, 0x80, 0x01]);
+    packet
+}
+
+fn classify(from: SocketAddr, payload: &[u8]) -> Option<DiscoveredPrinter> {
+    let source = match from.port() {
+        // Replies use a lowercase packet type.
+        EPSON_PORT if payload.starts_with(b"EPSONq") => DiscoverySource::Epson,
+        // Any response (QR bit set) to our single question.
+        MDNS_PORT if payload.len() >= 12 && payload[2] & 0x80 != 0 => DiscoverySource::Mdns,
+        _ => return None,
+    };
+    Some(DiscoveredPrinter {
+        addr: SocketAddr::new(from.ip(), RAW_PRINT_PORT),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mdns_query() {
+        let packet = mdns_query();
+        assert_eq!(&packet[12..28], b"\x0f_pdl-datastream");
+        assert_eq!(
+            &packet[packet.len() - 11..],
+            b"\x05local\x00\x00\x0c\x80\x01"
+        );
+    }
+
+    #[test]
+    fn test_classify() {
+        let epson: SocketAddr = "192.168.1.20:3289".parse().unwrap();
+        let reply = classify(epson, b"EPSONq\x03\x00\x00\x00\x00\x00\x00\x00").unwrap();
+        assert_eq!(reply.addr, "192.168.1.20:9100".parse().unwrap());
+        assert_eq!(reply.source, DiscoverySource::Epson);
+        // Our own query echoed back by the broadcast isn't a printer.
+        assert_eq!(classify(epson, &epson_query()), None);
+        let mdns: SocketAddr = "192.168.1.21:5353".parse().unwrap();
+        assert_eq!(classify(mdns, &mdns_query()), None);
+        let mut answer = mdns_query();
+        answer[2] = 0x84;
+        assert_eq!(
+            classify(mdns, &answer).unwrap().source,
+            DiscoverySource::Mdns
+        );
+    }
+}
@@ -0,0 +1,124 @@
+#[cfg(feature = "image")]
+use super::Write;
+use super::{Command, Delay};
+
+/// Pauses inserted after slow mechanical commands.
+///
+/// Some clone printers drop the first bytes of the next command while the
+/// cutter or feed motor is running. With pacing configured through
+/// [`PrinterBuilder::pacing`](crate::PrinterBuilder::pacing), the printer
+/// waits on `delay` after each such command:
+///
+/// ```
+/// # use escpos_embedded::{Delay, Pacing, Printer, Write};
+/// # fn setup<T: Write, D: Delay>(uart: T, hal_delay: D) -> Result<(), escpos_embedded::PrinterError<T::Error>> {
+/// let printer = Printer::builder(uart)
+///     .pacing(Pacing::new(hal_delay).after_cut(300).after_image_chunk(20))
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Pacing<D> {
+    delay: D,
+    cut_ms: u32,
+    feed_ms: u32,
+    image_ms: u32,
+}
+
+impl<D: Delay> Pacing<D> {
+    /// Pace with `delay`. No pauses are configured yet.
+    pub const fn new(delay: D) -> Self {
+        Self {
+            delay,
+            cut_ms: 0,
+            feed_ms: 0,
+            image_ms: 0,
+        }
+    }
+
+    /// Wait `ms` after every cut.
+    pub const fn after_cut(mut self, ms: u32) -> Self {
+        self.cut_ms = ms;
+        self
+    }
+
+    /// Wait `ms` after every paper feed command.
+    pub const fn after_feed(mut self, ms: u32) -> Self {
+        self.feed_ms = ms;
+        self
+    }
+
+    /// Wait `ms` after every piece of image data.
+    ///
+    /// Images are sent in pieces of the configured chunk size (512 bytes by
+    /// default).
+    pub const fn after_image_chunk(mut self, ms: u32) -> Self {
+        self.image_ms = ms;
+        self
+    }
+
+    /// The delay pauses are made with.
+    pub fn delay(&self) -> &D {
+        &self.delay
+    }
+
+    /// Pause after `command` if it is one of the paced kinds.
+    pub(crate) fn pause(&mut self, command: &Command) {
+        // Matched by name so vendor-encoded cuts are paced too.
+        let ms = match command.name() {
+            "Cut" | "FeedAndCut" | "FeedLabelToCut" => self.cut_ms,
+            "Feed" | "FeedUnits" | "ReverseFeed" | "FeedToMark" | "PrintAndFeedToMark"
+            | "FeedToLabel" => self.feed_ms,
+            "RasterImage" => self.image_ms,
+            _ => 0,
+        };
+        self.wait(ms);
+    }
+
+    fn wait(&mut self, ms: u32) {
+        if ms > 0 {
+            self.delay.delay_ms(ms);
+        }
+    }
+}
+
+/// Writer that pauses after every write, for image chunks.
+#[cfg(feature = "image")]
+pub(crate) struct PacedWrites<'a, W, D> {
+    pub(crate) inner: W,
+    pub(crate) pacing: &'a mut Pacing<D>,
+}
+
+#[cfg(feature = "image")]
+impl<W: Write, D: Delay> Write for PacedWrites<'_, W, D> {
+    type Error = W::Error;
+
+    fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        self.inner.write(data)?;
+        self.pacing.wait(self.pacing.image_ms);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockTransport, RecordDelay};
+    use crate::{CutMode, Printer};
+
+    #[test]
+    fn test_pauses_after_heavy_commands() {
+        let pacing = Pacing::new(RecordDelay::default())
+            .after_cut(300)
+            .after_feed(20);
+        let mut printer = Printer::builder(MockTransport::new())
+            .pacing(pacing)
+            .build()
+            .unwrap();
+        printer.write_line("abc").unwrap();
+        printer.feed(2).unwrap();
+        printer.cut(CutMode::Full).unwrap();
+        assert_eq!(printer.pacing().unwrap().delay().0, [20, 300]);
+    }
+}
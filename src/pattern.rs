@@ -0,0 +1,152 @@
+use super::batch::Batch;
+use super::{command, BitImageScale, Command, Raster, Write};
+
+/// Software test patterns printed by
+/// [`Printer::print_test_pattern`](crate::Printer::print_test_pattern) to
+/// check the print head in the field.
+///
+/// Unlike the printer's own [self-test](crate::Printer::print_self_test),
+/// they are plain raster images, so they work on any model and exercise
+/// the whole path from the host.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DiagnosticPattern {
+    /// A solid black bar. Every head element fires on every row, so dead
+    /// elements show up as thin white streaks.
+    Black,
+    /// 8×8 dot squares, for checking that dots line up across the width.
+    Checkerboard,
+    /// One-dot vertical lines in eight steps, each step firing every eighth
+    /// element one dot further along. A dead element leaves a gap in
+    /// exactly one step, so it can be located to the dot.
+    Ladder,
+    /// White to black from left to right, dithered, for calibrating the
+    /// print density.
+    Gradient,
+}
+
+/// 4×4 ordered dither thresholds.
+const BAYER: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// A [`DiagnosticPattern`] rendered row by row as a raster image.
+pub(crate) struct Pattern {
+    pub(crate) kind: DiagnosticPattern,
+    pub(crate) width: u16,
+    pub(crate) height: u16,
+}
+
+impl Pattern {
+    fn pixel(&self, x: u16, y: u16) -> bool {
+        match self.kind {
+            DiagnosticPattern::Black => true,
+            DiagnosticPattern::Checkerboard => (x / 8 + y / 8).is_multiple_of(2),
+            DiagnosticPattern::Ladder => {
+                let step = u32::from(y) * 8 / u32::from(self.height);
+                u32::from(x % 8) == step
+            }
+            DiagnosticPattern::Gradient => {
+                let level = (u32::from(x) + 1) * 16 / u32::from(self.width);
+                level > u32::from(BAYER[usize::from(y % 4)][usize::from(x % 4)])
+            }
+        }
+    }
+
+    fn byte(&self, column: u16, y: u16) -> u8 {
+        (0..8)
+            .filter(|bit| {
+                let x = column * 8 + bit;
+                x < self.width && self.pixel(x, y)
+            })
+            .fold(0, |byte, bit| byte | 0x80 >> bit)
+    }
+}
+
+impl Raster for Pattern {
+    fn command(&self) -> Command<'_> {
+        Command::RasterImage {
+            width: self.width,
+            height: self.height,
+            scale: BitImageScale::Normal,
+            data: &[],
+        }
+    }
+
+    fn encode<W: Write>(&self, out: &mut W) -> Result<(), W::Error> {
+        out.write(&command::raster_header(
+            self.width,
+            self.height,
+            BitImageScale::Normal,
+        ))?;
+        let mut batch = Batch::new();
+        for y in 0..self.height {
+            for column in 0..self.width.div_ceil(8) {
+                let byte = [self.byte(column, y)];
+                if let Some(full) = batch.fill(&mut &byte[..]) {
+                    out.write(full)?;
+                }
+            }
+        }
+        match batch.rest() {
+            [] => Ok(()),
+            rest => out.write(rest),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockTransport;
+    use std::vec::Vec;
+
+    fn rows(kind: DiagnosticPattern, width: u16, height: u16) -> Vec<Vec<u8>> {
+        let mut out = MockTransport::new();
+        Pattern {
+            kind,
+            width,
+            height,
+        }
+        .encode(&mut out)
+        .unwrap();
+        let data = out.take_written().split_off(8);
+        data.chunks(usize::from(width.div_ceil(8)))
+            .map(<[u8]>::to_vec)
+            .collect()
+    }
+
+    #[test]
+    fn test_black_and_checkerboard() {
+        assert!(rows(DiagnosticPattern::Black, 20, 2)
+            .iter()
+            .all(|row| row == &[0xFF, 0xFF, 0xF0]));
+        let board = rows(DiagnosticPattern::Checkerboard, 24, 16);
+        assert_eq!(board[0], [0xFF, 0x00, 0xFF]);
+        assert_eq!(board[8], [0x00, 0xFF, 0x00]);
+    }
+
+    #[test]
+    fn test_ladder_fires_each_element_once() {
+        let ladder = rows(DiagnosticPattern::Ladder, 16, 16);
+        assert_eq!(ladder[0], [0x80, 0x80]);
+        assert_eq!(ladder[15], [0x01, 0x01]);
+        let steps: Vec<_> = (0..8).map(|step| ladder[step * 2][0]).collect();
+        assert!(steps.iter().all(|byte| byte.count_ones() == 1));
+        assert_eq!(steps.iter().fold(0, |all, byte| all | byte), 0xFF);
+    }
+
+    #[test]
+    fn test_gradient_darkens() {
+        let gradient = rows(DiagnosticPattern::Gradient, 64, 4);
+        let ink = |column: usize| {
+            gradient
+                .iter()
+                .map(|row| row[column].count_ones())
+                .sum::<u32>()
+        };
+        assert!(ink(0) < 4);
+        assert!((1..8).all(|column| ink(column) >= ink(column - 1)));
+        assert!(ink(7) > 28);
+        assert!(gradient.iter().all(|row| row[7] & 0x01 != 0));
+    }
+}
@@ -0,0 +1,306 @@
+use core::fmt;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::vec::Vec;
+
+use super::{Delay, Job, Printer, PrinterError, Read, Status, Write};
+
+/// Health of one printer in a [`PrinterPool`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PrinterHealth {
+    /// The printer hasn't been polled yet.
+    Unknown,
+    /// The last poll found the printer ready to print.
+    Ready(Status),
+    /// The printer answered the last poll but can't print, e.g. because it
+    /// is out of paper or its cover is open.
+    NotReady(Status),
+    /// The last poll or job failed at the transport.
+    Unreachable,
+}
+
+impl PrinterHealth {
+    /// Whether the last poll found the printer ready.
+    pub const fn is_ready(&self) -> bool {
+        matches!(self, PrinterHealth::Ready(_))
+    }
+}
+
+/// Errors returned by [`PrinterPool`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PoolError<E> {
+    /// No printer was added for the destination.
+    UnknownDestination,
+    /// The destination's printer failed.
+    Printer(PrinterError<E>),
+}
+
+impl<E> From<PrinterError<E>> for PoolError<E> {
+    fn from(err: PrinterError<E>) -> Self {
+        PoolError::Printer(err)
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for PoolError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PoolError::UnknownDestination => f.write_str("no printer for destination"),
+            PoolError::Printer(err) => err.fmt(f),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> core::error::Error for PoolError<E> {}
+
+struct Slot<T: Write, P: Delay> {
+    printer: Printer<T, P>,
+    health: PrinterHealth,
+}
+
+/// A set of printers addressed by destination, such as the kitchen, the bar
+/// and the front desk.
+///
+/// Each printer sits behind its own lock, so the pool can be shared between
+/// threads (e.g. in an `Arc`): jobs for the same printer are sent one after
+/// another, while different printers print in parallel.
+///
+/// ```
+/// # use escpos_embedded::{Job, Printer, PrinterPool, SimulatedPrinter};
+/// let pool = PrinterPool::new()
+///     .add("kitchen", Printer::new(SimulatedPrinter::new()))
+///     .add("bar", Printer::new(SimulatedPrinter::new()));
+///
+/// let mut ticket = Job::<256>::new();
+/// ticket.write_line("2x Margherita").unwrap();
+/// pool.submit(&"kitchen", &mut ticket).unwrap();
+///
+/// for (destination, health) in pool.poll() {
+///     assert!(health.is_ready(), "{destination} needs attention");
+/// }
+/// ```
+///
+/// All printers share one transport type; wrap mixed links in an enum that
+/// implements [`Write`] and [`Read`].
+pub struct PrinterPool<K, T: Write, P: Delay = ()> {
+    printers: Vec<(K, Mutex<Slot<T, P>>)>,
+}
+
+impl<K: PartialEq, T: Write, P: Delay> PrinterPool<K, T, P> {
+    /// Create an empty pool.
+    pub fn new() -> Self {
+        Self {
+            printers: Vec::new(),
+        }
+    }
+
+    /// Add `printer` for `destination`, replacing any printer already there.
+    pub fn add(mut self, destination: K, printer: Printer<T, P>) -> Self {
+        self.printers.retain(|(key, _)| *key != destination);
+        let slot = Slot {
+            printer,
+            health: PrinterHealth::Unknown,
+        };
+        self.printers.push((destination, Mutex::new(slot)));
+        self
+    }
+
+    /// Destinations in the order they were added.
+    pub fn destinations(&self) -> impl Iterator<Item = &K> {
+        self.printers.iter().map(|(key, _)| key)
+    }
+
+    /// Run `f` with exclusive access to the printer for `destination`.
+    ///
+    /// Other callers for the same printer wait until `f` returns. A
+    /// transport failure marks the printer
+    /// [`Unreachable`](PrinterHealth::Unreachable) until the next
+    /// [`poll`](PrinterPool::poll).
+    pub fn with<R>(
+        &self,
+        destination: &K,
+        f: impl FnOnce(&mut Printer<T, P>) -> Result<R, PrinterError<T::Error>>,
+    ) -> Result<R, PoolError<T::Error>> {
+        let mut slot = self.lock(destination)?;
+        let result = f(&mut slot.printer);
+        if let Err(PrinterError::Transport { .. }) = result {
+            slot.health = PrinterHealth::Unreachable;
+        }
+        Ok(result?)
+    }
+
+    /// Send `job` to the printer for `destination`.
+    pub fn submit<const N: usize>(
+        &self,
+        destination: &K,
+        job: &mut Job<N>,
+    ) -> Result<(), PoolError<T::Error>> {
+        self.with(destination, |printer| job.submit(printer))
+    }
+
+    /// Health of the printer for `destination`, or `None` if there is none.
+    pub fn health(&self, destination: &K) -> Option<PrinterHealth> {
+        self.lock(destination).ok().map(|slot| slot.health)
+    }
+
+    /// Take the printers back out of the pool.
+    pub fn into_printers(self) -> Vec<(K, Printer<T, P>)> {
+        self.printers
+            .into_iter()
+            .map(|(key, slot)| {
+                let slot = slot.into_inner().unwrap_or_else(PoisonError::into_inner);
+                (key, slot.printer)
+            })
+            .collect()
+    }
+
+    fn lock(&self, destination: &K) -> Result<MutexGuard<'_, Slot<T, P>>, PoolError<T::Error>> {
+        let (_, slot) = self
+            .printers
+            .iter()
+            .find(|(key, _)| key == destination)
+            .ok_or(PoolError::UnknownDestination)?;
+        // A panic while printing leaves the printer in a usable state, so
+        // a poisoned lock is not an error.
+        Ok(slot.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+impl<K, T, P: Delay> PrinterPool<K, T, P>
+where
+    K: PartialEq + Clone,
+    T: Write + Read<Error = <T as Write>::Error>,
+{
+    /// Query the status of every printer and return its health.
+    ///
+    /// Printers are polled one at a time, each waiting for any job in
+    /// progress on it to finish.
+    pub fn poll(&self) -> Vec<(K, PrinterHealth)> {
+        self.printers
+            .iter()
+            .map(|(key, slot)| {
+                let mut slot = slot.lock().unwrap_or_else(PoisonError::into_inner);
+                slot.health = match slot.printer.status() {
+                    Ok(status) if status.is_ready() => PrinterHealth::Ready(status),
+                    Ok(status) => PrinterHealth::NotReady(status),
+                    Err(_) => PrinterHealth::Unreachable,
+                };
+                (key.clone(), slot.health)
+            })
+            .collect()
+    }
+}
+
+impl<K: PartialEq, T: Write, P: Delay> Default for PrinterPool<K, T, P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{FaultError, FaultyTransport};
+    use crate::SimulatedPrinter;
+    use std::sync::Arc;
+    use std::thread;
+
+    type Link = FaultyTransport<SimulatedPrinter>;
+
+    fn printer(sim: SimulatedPrinter) -> Printer<Link> {
+        Printer::new(FaultyTransport::new(sim))
+    }
+
+    #[test]
+    fn test_routes_by_destination() {
+        let pool = PrinterPool::new()
+            .add("kitchen", printer(SimulatedPrinter::new()))
+            .add("bar", printer(SimulatedPrinter::new()));
+        let mut job = Job::<64>::new();
+        job.write_line("Soup").unwrap();
+        pool.submit(&"kitchen", &mut job).unwrap();
+        assert_eq!(
+            pool.submit(&"patio", &mut job),
+            Err(PoolError::UnknownDestination)
+        );
+
+        let printers = pool.into_printers();
+        assert_eq!(printers[0].0, "kitchen");
+        assert_eq!(printers[0].1.transport().inner().received(), b"Soup\n");
+        assert!(printers[1].1.transport().inner().received().is_empty());
+    }
+
+    #[test]
+    fn test_serializes_jobs_per_printer() {
+        let pool = Arc::new(PrinterPool::new().add(0, printer(SimulatedPrinter::new())));
+        let threads: Vec<_> = ["aaaa", "bbbb", "cccc"]
+            .into_iter()
+            .map(|line| {
+                let pool = Arc::clone(&pool);
+                thread::spawn(move || {
+                    pool.with(&0, |printer| {
+                        for c in line.chars() {
+                            printer.write(c.encode_utf8(&mut [0; 4]))?;
+                        }
+                        printer.write("\n")
+                    })
+                    .unwrap();
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        let pool = Arc::into_inner(pool).unwrap();
+        let (_, printer) = pool.into_printers().pop().unwrap();
+        let mut lines: Vec<_> = printer
+            .transport()
+            .inner()
+            .received()
+            .split(|&b| b == b'\n')
+            .filter(|line| !line.is_empty())
+            .collect();
+        lines.sort();
+        assert_eq!(lines, [&b"aaaa"[..], b"bbbb", b"cccc"]);
+    }
+
+    #[test]
+    fn test_poll_health() {
+        let pool = PrinterPool::new()
+            .add("kitchen", printer(SimulatedPrinter::new()))
+            .add("bar", printer(SimulatedPrinter::new().paper_out_at(0)))
+            .add(
+                "desk",
+                Printer::new(FaultyTransport::new(SimulatedPrinter::new()).fail_write(1)),
+            );
+        assert_eq!(pool.health(&"kitchen"), Some(PrinterHealth::Unknown));
+        assert_eq!(pool.health(&"patio"), None);
+
+        let mut job = Job::<64>::new();
+        job.write_line("x").unwrap();
+        pool.submit(&"bar", &mut job).unwrap();
+        let health = pool.poll();
+        assert!(health[0].1.is_ready());
+        assert!(matches!(health[1].1, PrinterHealth::NotReady(s) if s.paper_end));
+        assert_eq!(health[2], ("desk", PrinterHealth::Unreachable));
+        assert_eq!(pool.health(&"desk"), Some(PrinterHealth::Unreachable));
+    }
+
+    #[test]
+    fn test_transport_failure_marks_unreachable() {
+        let pool = PrinterPool::new().add(
+            "kitchen",
+            Printer::new(FaultyTransport::new(SimulatedPrinter::new()).fail_write(1)),
+        );
+        let err = pool.with(&"kitchen", |printer| printer.write("x"));
+        assert!(matches!(
+            err,
+            Err(PoolError::Printer(PrinterError::Transport {
+                error: FaultError::Injected,
+                ..
+            }))
+        ));
+        assert_eq!(pool.health(&"kitchen"), Some(PrinterHealth::Unreachable));
+        assert!(pool.poll()[0].1.is_ready());
+    }
+}
@@ -0,0 +1,293 @@
+use super::Font;
+
+/// Width of the paper roll.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PaperWidth {
+    /// 58mm paper, 384 printable dots.
+    Mm58,
+    /// 80mm paper, 576 printable dots.
+    Mm80,
+    /// Any other printable width, in dots.
+    Dots(u16),
+}
+
+impl PaperWidth {
+    /// Printable width in dots at 203 dpi.
+    pub const fn dots(self) -> u16 {
+        match self {
+            PaperWidth::Mm58 => 384,
+            PaperWidth::Mm80 => 576,
+            PaperWidth::Dots(dots) => dots,
+        }
+    }
+
+    /// Characters per line in `font` at normal width, e.g. 48 for Font A on
+    /// 80mm paper.
+    pub const fn columns(self, font: Font) -> u16 {
+        self.dots() / font.dot_width()
+    }
+}
+
+/// Print stations of hybrid printers, selected with `ESC c 0` and `ESC c 1`.
+///
+/// Receipt-only printers have just [`RECEIPT`](Stations::RECEIPT); models
+/// such as the TM-U220 add a journal and the TM-H6000 a slip station.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Stations(pub u8);
+
+impl Stations {
+    /// Journal paper.
+    pub const JOURNAL: Self = Self(0x01);
+    /// Receipt paper.
+    pub const RECEIPT: Self = Self(0x02);
+    /// Slip paper, e.g. checks and forms.
+    pub const SLIP: Self = Self(0x04);
+    /// Validation paper.
+    pub const VALIDATION: Self = Self(0x08);
+
+    /// Whether all stations in `other` are included.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl core::ops::BitOr for Stations {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl Default for Stations {
+    fn default() -> Self {
+        Self::RECEIPT
+    }
+}
+
+/// Optional text commands that not every model understands.
+///
+/// Models without one of them are handled according to the printer's
+/// [`Degradation`](crate::Degradation) policy.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Features(pub u8);
+
+impl Features {
+    /// Text alignment (`ESC a`).
+    pub const ALIGN: Self = Self(0x01);
+    /// Underlined text (`ESC -`).
+    pub const UNDERLINE: Self = Self(0x02);
+    /// Every optional command.
+    pub const ALL: Self = Self(0x03);
+    /// None of the optional commands.
+    pub const NONE: Self = Self(0x00);
+
+    /// Whether all features in `other` are included.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl core::ops::BitOr for Features {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl Default for Features {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+/// How a model's firmware expects [`Printer::cut`](crate::Printer::cut) to
+/// be encoded.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CutStyle {
+    /// `GS V 0` / `GS V 1`.
+    #[default]
+    Binary,
+    /// `GS V 48` / `GS V 49`, for firmwares that only accept ASCII digits.
+    Ascii,
+    /// `GS V 65 n` / `GS V 66 n`, feeding to the cutter plus `n` motion
+    /// units before cutting.
+    Feed(u8),
+    /// `ESC i` for full cuts and `ESC m` for partial ones, for early
+    /// printers and clones without `GS V`.
+    Legacy,
+}
+
+/// How a model starts its self-test page.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SelfTest {
+    /// The ESC/POS test print command, `GS ( A`.
+    #[default]
+    TestPrint,
+    /// `DC2 T`, used by many low-cost and portable printers.
+    Dc2T,
+    /// The model has no self-test command.
+    Unsupported,
+}
+
+/// What a printer model supports.
+///
+/// Profiles are plain data; start from one of the constants and override
+/// fields for models that differ:
+///
+/// ```
+/// # use escpos_embedded::{PaperWidth, Profile};
+/// let kiosk = Profile {
+///     name: "kiosk",
+///     paper_width: PaperWidth::Dots(512),
+///     ..Profile::GENERIC
+/// };
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Profile {
+    /// Human-readable model name.
+    ///
+    /// Not deserialized, since it can't borrow from the input; profiles
+    /// loaded with serde are named `"custom"`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_deserializing, default = "Profile::custom_name")
+    )]
+    pub name: &'static str,
+    /// Paper the model is usually fitted with.
+    pub paper_width: PaperWidth,
+    /// Whether the model has an autocutter.
+    pub has_cutter: bool,
+    /// Encoding used for cuts.
+    pub cut_style: CutStyle,
+    /// Whether the model can feed paper backwards (`ESC K`).
+    pub reverse_feed: bool,
+    /// Command that prints the self-test page.
+    pub self_test: SelfTest,
+    /// Whether the head can print the same rows repeatedly for
+    /// [`print_image_gray`](crate::Printer::print_image_gray).
+    /// Experimental; also needs [`reverse_feed`](Profile::reverse_feed).
+    pub multi_pass_gray: bool,
+    /// Whether the model honours the scaling modes of raster images
+    /// (`GS v 0` with `m` 1–3); see
+    /// [`print_image_scaled`](crate::Printer::print_image_scaled).
+    pub raster_scaling: bool,
+    /// Print stations the model has; see
+    /// [`select_station`](crate::Printer::select_station).
+    pub stations: Stations,
+    /// Optional text commands the model understands.
+    pub features: Features,
+}
+
+impl Profile {
+    /// A typical 80mm ESC/POS receipt printer.
+    pub const GENERIC: Self = Self {
+        name: "generic",
+        paper_width: PaperWidth::Mm80,
+        has_cutter: true,
+        cut_style: CutStyle::Binary,
+        reverse_feed: false,
+        self_test: SelfTest::TestPrint,
+        multi_pass_gray: false,
+        raster_scaling: true,
+        stations: Stations::RECEIPT,
+        features: Features::ALL,
+    };
+
+    /// A typical 58mm ESC/POS receipt printer.
+    pub const GENERIC_58MM: Self = Self {
+        name: "generic-58mm",
+        paper_width: PaperWidth::Mm58,
+        has_cutter: false,
+        cut_style: CutStyle::Binary,
+        reverse_feed: false,
+        self_test: SelfTest::TestPrint,
+        multi_pass_gray: false,
+        raster_scaling: true,
+        stations: Stations::RECEIPT,
+        features: Features::ALL,
+    };
+}
+
+impl Profile {
+    /// Bit of the `GS I 2` type ID set when an autocutter is fitted.
+    const TYPE_AUTOCUTTER: u8 = 0x02;
+
+    /// A conservative profile for printers that couldn't be identified:
+    /// 80mm paper, but no cutter and no printer-side image scaling.
+    ///
+    /// Used by [`Printer::detect_profile`](crate::Printer::detect_profile)
+    /// when the printer doesn't answer.
+    pub const BASIC: Self = Self {
+        name: "basic",
+        has_cutter: false,
+        raster_scaling: false,
+        ..Self::GENERIC
+    };
+
+    /// Profile for an unknown model from its `GS I 2` type ID: the
+    /// generic 80mm profile, with the cutter as reported.
+    pub const fn from_type_id(type_id: u8) -> Self {
+        Self {
+            name: "detected",
+            has_cutter: type_id & Self::TYPE_AUTOCUTTER != 0,
+            ..Self::GENERIC
+        }
+    }
+}
+
+impl Profile {
+    /// Epson TM-H6000 hybrid printer: an 80mm receipt station plus a slip
+    /// station for checks and validation.
+    pub const TM_H6000: Self = Self {
+        name: "TM-H6000",
+        stations: Stations(Stations::RECEIPT.0 | Stations::SLIP.0 | Stations::VALIDATION.0),
+        ..Self::GENERIC
+    };
+}
+
+#[cfg(feature = "serde")]
+impl Profile {
+    fn custom_name() -> &'static str {
+        "custom"
+    }
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self::GENERIC
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    fn assert_owned<T: serde::de::DeserializeOwned + serde::Serialize>() {}
+
+    #[test]
+    fn test_serde_owned() {
+        assert_owned::<Profile>();
+        assert_owned::<PaperWidth>();
+        assert_owned::<CutStyle>();
+        assert_owned::<SelfTest>();
+        assert_owned::<Stations>();
+        assert_owned::<Features>();
+        assert_owned::<crate::CodePage>();
+        assert_owned::<crate::Scale>();
+    }
+}
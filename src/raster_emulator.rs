@@ -0,0 +1,536 @@
+use std::vec;
+use std::vec::Vec;
+
+use super::font;
+use super::{
+    Align, Command, CutMode, Decoder, Font, Justification, PaperWidth, UnderlineMode, Write,
+};
+
+const GS: u8 = 0x1D;
+
+/// Default line spacing in dots (1/6 inch at 180 dpi-ish, the ESC/POS default).
+const LINE_SPACING: usize = 30;
+
+/// Font B cell size; glyphs are resampled from the Font A bitmaps.
+const FONT_B_WIDTH: usize = 9;
+const FONT_B_HEIGHT: usize = 17;
+
+/// A rendered monochrome receipt.
+pub struct Bitmap {
+    width: usize,
+    height: usize,
+    pixels: Vec<bool>,
+}
+
+impl Bitmap {
+    /// Width in dots.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Height in dots.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether the dot at (`x`, `y`) is printed (black).
+    ///
+    /// Dots outside the bitmap are reported as unprinted.
+    pub fn pixel(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.pixels[y * self.width + x]
+    }
+
+    /// Encode the bitmap as a 1-bit grayscale PNG.
+    pub fn to_png(&self) -> Vec<u8> {
+        let height = self.height.max(1);
+        let row_bytes = self.width.div_ceil(8);
+        let mut raw = Vec::with_capacity(height * (row_bytes + 1));
+        for y in 0..height {
+            raw.push(0); // filter: none
+            for byte in 0..row_bytes {
+                let mut bits = 0xFFu8;
+                for bit in 0..8 {
+                    if self.pixel(byte * 8 + bit, y) {
+                        bits &= !(0x80 >> bit);
+                    }
+                }
+                raw.push(bits);
+            }
+        }
+
+        let mut png = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+        let mut ihdr = Vec::new();
+        ihdr.extend_from_slice(&(self.width as u32).to_be_bytes());
+        ihdr.extend_from_slice(&(height as u32).to_be_bytes());
+        ihdr.extend_from_slice(&[1, 0, 0, 0, 0]); // 1-bit grayscale
+        png_chunk(&mut png, b"IHDR", &ihdr);
+        png_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+        png_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+}
+
+/// Transport that renders the received commands into a [`Bitmap`].
+///
+/// Text is drawn with a built-in 12x24 bitmap font (Font B is resampled to
+/// 9x17) honouring alignment, bold, underline, inversion and size
+/// multipliers. Lines wrap at the paper width, raster images are drawn in
+/// place, and cuts are shown as a dashed (full) or dotted (partial) line.
+/// Commands the [`Decoder`] doesn't recognise are ignored.
+///
+/// Barcodes (`GS k`) and 2D symbols (`GS ( k`) are not rendered: their data
+/// is skipped, leaving neither marks nor blank space where they would be
+/// printed, so previews of receipts with barcodes are shorter than the
+/// real thing.
+pub struct RasterEmulator {
+    dots: usize,
+    bytes: Vec<u8>,
+}
+
+impl RasterEmulator {
+    /// Create an emulator for paper `dots` wide (e.g. 384 for 58mm, 576 for
+    /// 80mm).
+    pub fn new(dots: usize) -> Self {
+        Self {
+            dots,
+            bytes: Vec::new(),
+        }
+    }
+
+    /// Create an emulator as wide as `paper`.
+    pub fn for_paper(paper: PaperWidth) -> Self {
+        Self::new(paper.dots().into())
+    }
+
+    /// Render everything received so far.
+    pub fn render(&self) -> Bitmap {
+        let mut canvas = Canvas::new(self.dots);
+        let mut decoder = Decoder::new(&self.bytes);
+        while let Some(command) = decoder.next() {
+            if let Command::Unknown([GS, f]) = command {
+                // The decoder only consumed the prefix; skip the symbol data
+                // rather than drawing it as text.
+                let rest = decoder.remaining();
+                decoder = Decoder::new(&rest[symbol_len(*f, rest)..]);
+                continue;
+            }
+            canvas.apply(&command);
+        }
+        canvas.finish()
+    }
+
+    /// Render everything received so far and encode it as a PNG.
+    pub fn render_png(&self) -> Vec<u8> {
+        self.render().to_png()
+    }
+}
+
+impl Write for RasterEmulator {
+    type Error = core::convert::Infallible;
+
+    fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        self.bytes.extend_from_slice(data);
+        Ok(())
+    }
+}
+
+#[derive(Copy, Clone)]
+struct Glyph {
+    ch: char,
+    font: Font,
+    x_scale: usize,
+    y_scale: usize,
+    bold: bool,
+    underline: usize,
+    invert: bool,
+}
+
+impl Glyph {
+    const DEFAULT: Self = Self {
+        ch: ' ',
+        font: Font::FontA,
+        x_scale: 1,
+        y_scale: 1,
+        bold: false,
+        underline: 0,
+        invert: false,
+    };
+
+    fn cell(&self) -> (usize, usize) {
+        let (w, h) = match self.font {
+            Font::FontA => (font::WIDTH, font::HEIGHT),
+            // Other fonts vary by model; draw them like Font B.
+            Font::FontB | Font::FontC | Font::Custom(_) => (FONT_B_WIDTH, FONT_B_HEIGHT),
+        };
+        (w * self.x_scale, h * self.y_scale)
+    }
+}
+
+struct Canvas {
+    width: usize,
+    pixels: Vec<bool>,
+    y: usize,
+    line: Vec<Glyph>,
+    line_width: usize,
+    line_align: Align,
+    align: Align,
+    style: Glyph,
+}
+
+impl Canvas {
+    fn new(width: usize) -> Self {
+        Self {
+            width,
+            pixels: Vec::new(),
+            y: 0,
+            line: Vec::new(),
+            line_width: 0,
+            line_align: Align::Left,
+            align: Align::Left,
+            style: Glyph::DEFAULT,
+        }
+    }
+
+    fn apply(&mut self, command: &Command) {
+        match *command {
+            Command::Text(text) => text.chars().for_each(|ch| self.push(ch)),
+            Command::LineFeed | Command::PrintAndFeedToMark => self.flush(),
+            Command::Feed(lines) => (0..lines).for_each(|_| self.flush()),
+            Command::ToLineStart { print: false } => {
+                self.line.clear();
+                self.line_width = 0;
+            }
+            Command::FeedUnits(units) => {
+                self.flush_pending();
+                self.grow(self.y + usize::from(units));
+                self.y += usize::from(units);
+            }
+            Command::Cut(mode) | Command::FeedAndCut { mode, .. } => {
+                self.flush_pending();
+                let (on, off) = match mode {
+                    CutMode::Full => (6, 4),
+                    CutMode::Partial | CutMode::PartialThreePoints => (2, 4),
+                };
+                self.grow(self.y + 9);
+                for x in (0..self.width).filter(|x| x % (on + off) < on) {
+                    self.set(x, self.y + 4);
+                }
+                self.y += 9;
+            }
+            Command::Initialize => {
+                self.style = Glyph::DEFAULT;
+                self.set_align(Align::Left);
+            }
+            Command::SetBold(on) => self.style.bold = on,
+            Command::SetUnderline(mode) => {
+                self.style.underline = match mode {
+                    UnderlineMode::None => 0,
+                    UnderlineMode::Single => 1,
+                    UnderlineMode::Double => 2,
+                }
+            }
+            Command::SetAlign(align) => self.set_align(align),
+            Command::SetJustification(mode) => self.set_align(match mode {
+                Justification::Left => Align::Left,
+                Justification::Center => Align::Center,
+                Justification::Right => Align::Right,
+            }),
+            Command::SetFont(font) => self.style.font = font,
+            Command::SetSize { width, height } => {
+                self.style.x_scale = width.min(7) as usize + 1;
+                self.style.y_scale = height.min(7) as usize + 1;
+            }
+            Command::SetInvert(on) => self.style.invert = on,
+            Command::RasterImage {
+                width,
+                height,
+                scale,
+                data,
+            } => {
+                self.flush_pending();
+                let (sx, sy) = scale.factors();
+                let (sx, sy) = (usize::from(sx), usize::from(sy));
+                let width_bytes = width.div_ceil(8) as usize;
+                let x0 = self.indent(width as usize * sx);
+                let (y0, height) = (self.y, height as usize);
+                self.grow(y0 + height * sy);
+                for row in 0..height * sy {
+                    for col in 0..width as usize * sx {
+                        let (x, y) = (col / sx, row / sy);
+                        let byte = data.get(y * width_bytes + x / 8).copied();
+                        if byte.unwrap_or(0) & (0x80 >> (x % 8)) != 0 {
+                            self.set(x0 + col, y0 + row);
+                        }
+                    }
+                }
+                self.y += height * sy;
+            }
+            _ => {}
+        }
+    }
+
+    fn set_align(&mut self, align: Align) {
+        self.align = align;
+        // ESC a only takes effect at the beginning of a line.
+        if self.line.is_empty() {
+            self.line_align = align;
+        }
+    }
+
+    fn indent(&self, used: usize) -> usize {
+        let pad = self.width.saturating_sub(used);
+        match self.line_align {
+            Align::Left => 0,
+            Align::Center => pad / 2,
+            Align::Right => pad,
+        }
+    }
+
+    fn push(&mut self, ch: char) {
+        let glyph = Glyph { ch, ..self.style };
+        let (w, _) = glyph.cell();
+        if self.line_width + w > self.width && !self.line.is_empty() {
+            self.flush();
+        }
+        self.line.push(glyph);
+        self.line_width += w;
+    }
+
+    fn flush_pending(&mut self) {
+        if !self.line.is_empty() {
+            self.flush();
+        }
+    }
+
+    fn flush(&mut self) {
+        let line_height = self.line.iter().map(|g| g.cell().1).max().unwrap_or(0);
+        self.grow(self.y + line_height.max(LINE_SPACING));
+        let mut x = self.indent(self.line_width);
+        let line = core::mem::take(&mut self.line);
+        for glyph in &line {
+            let (w, h) = glyph.cell();
+            self.draw(glyph, x, self.y + line_height - h);
+            x += w;
+        }
+        self.y += line_height.max(LINE_SPACING);
+        self.line_width = 0;
+        self.line_align = self.align;
+    }
+
+    fn draw(&mut self, glyph: &Glyph, x0: usize, y0: usize) {
+        let bitmap = font::glyph(glyph.ch);
+        let (w, h) = glyph.cell();
+        let (cols, rows) = (w / glyph.x_scale, h / glyph.y_scale);
+        for row in 0..rows {
+            let src_row = bitmap[row * font::HEIGHT / rows];
+            for col in 0..cols {
+                let src_col = col * font::WIDTH / cols;
+                let mut on = src_row & (0x8000 >> src_col) != 0;
+                if glyph.bold && col > 0 {
+                    let prev = (col - 1) * font::WIDTH / cols;
+                    on |= src_row & (0x8000 >> prev) != 0;
+                }
+                on |= glyph.underline > 0 && row >= rows - glyph.underline;
+                on ^= glyph.invert;
+                if on {
+                    for dy in 0..glyph.y_scale {
+                        for dx in 0..glyph.x_scale {
+                            let x = x0 + col * glyph.x_scale + dx;
+                            self.set(x, y0 + row * glyph.y_scale + dy);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn grow(&mut self, height: usize) {
+        if self.pixels.len() < height * self.width {
+            self.pixels.resize(height * self.width, false);
+        }
+    }
+
+    fn set(&mut self, x: usize, y: usize) {
+        if x < self.width {
+            self.pixels[y * self.width + x] = true;
+        }
+    }
+
+    fn finish(mut self) -> Bitmap {
+        self.flush_pending();
+        self.grow(self.y);
+        Bitmap {
+            width: self.width,
+            height: self.y,
+            pixels: self.pixels,
+        }
+    }
+}
+
+/// Length of the parameters of a barcode (`GS k`) or 2D symbol (`GS ( k`)
+/// at the start of `rest`, the bytes following `GS f`. Zero for other
+/// commands.
+fn symbol_len(f: u8, rest: &[u8]) -> usize {
+    let len = match (f, rest) {
+        (b'k', [0..=6, data @ ..]) => {
+            1 + data
+                .iter()
+                .position(|&b| b == 0)
+                .map_or(data.len(), |n| n + 1)
+        }
+        (b'k', [65..=79, n, ..]) => 2 + usize::from(*n),
+        (b'(', [b'k', p_l, p_h, ..]) => 3 + usize::from(u16::from_le_bytes([*p_l, *p_h])),
+        _ => 0,
+    };
+    len.min(rest.len())
+}
+
+fn png_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    let crc = crc32(crc32(0xFFFF_FFFF, kind), data) ^ 0xFFFF_FFFF;
+    out.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Wrap `data` in a zlib stream using uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(0xFFFF).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[0x01, 0x00, 0x00, 0xFF, 0xFF]);
+    }
+    while let Some(block) = blocks.next() {
+        out.push(blocks.peek().is_none() as u8);
+        let len = block.len() as u16;
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    out.extend_from_slice(&((b << 16) | a).to_be_bytes());
+    out
+}
+
+fn crc32(mut crc: u32, data: &[u8]) -> u32 {
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BitImageScale, Printer};
+
+    fn count(bitmap: &Bitmap, xs: core::ops::Range<usize>, ys: core::ops::Range<usize>) -> usize {
+        ys.flat_map(|y| xs.clone().map(move |x| (x, y)))
+            .filter(|&(x, y)| bitmap.pixel(x, y))
+            .count()
+    }
+
+    #[test]
+    fn test_render_text_alignment() {
+        let mut printer = Printer::new(RasterEmulator::new(96));
+        printer.set_align(Align::Right).unwrap();
+        printer.write_line("A").unwrap();
+        let bitmap = printer.into_inner().render();
+        assert_eq!(bitmap.width(), 96);
+        assert_eq!(bitmap.height(), LINE_SPACING);
+        assert_eq!(count(&bitmap, 0..84, 0..30), 0);
+        assert!(count(&bitmap, 84..96, 0..24) > 0);
+    }
+
+    #[test]
+    fn test_render_wraps_at_paper_width() {
+        let mut printer = Printer::new(RasterEmulator::new(24));
+        printer.write_line("AAA").unwrap();
+        assert_eq!(printer.into_inner().render().height(), 2 * LINE_SPACING);
+    }
+
+    #[test]
+    fn test_render_double_height() {
+        let mut printer = Printer::new(RasterEmulator::new(96));
+        printer.set_size(0, 1).unwrap();
+        printer.write_line("A").unwrap();
+        assert_eq!(printer.into_inner().render().height(), 48);
+    }
+
+    #[test]
+    fn test_render_image_and_cut() {
+        let mut printer = Printer::new(RasterEmulator::new(16));
+        printer
+            .send(&Command::RasterImage {
+                width: 16,
+                height: 2,
+                scale: BitImageScale::Normal,
+                data: &[0xFF, 0x00, 0x00, 0x01],
+            })
+            .unwrap();
+        printer.cut(CutMode::Full).unwrap();
+        let bitmap = printer.into_inner().render();
+        assert_eq!(count(&bitmap, 0..16, 0..1), 8);
+        assert!(bitmap.pixel(15, 1));
+        assert_eq!(count(&bitmap, 0..16, 6..7), 12);
+        assert_eq!(bitmap.height(), 11);
+    }
+
+    #[test]
+    fn test_render_scaled_image() {
+        let mut printer = Printer::new(RasterEmulator::new(16));
+        printer
+            .send(&Command::RasterImage {
+                width: 8,
+                height: 1,
+                scale: BitImageScale::Quadruple,
+                data: &[0xF0],
+            })
+            .unwrap();
+        let bitmap = printer.into_inner().render();
+        assert_eq!(count(&bitmap, 0..16, 0..2), 16);
+        assert_eq!(bitmap.height(), 2);
+    }
+
+    #[test]
+    fn test_barcodes_are_skipped() {
+        let mut printer = Printer::new(RasterEmulator::new(96));
+        printer.write_line("A").unwrap();
+        let plain = printer.transport().render();
+        printer.raw(b"\x1Dk\x04CODE39\x00").unwrap();
+        printer.raw(b"\x1Dk\x49\x05{B123").unwrap();
+        printer
+            .raw(b"\x1D(k\x08\x001P0HELLO\x1D(k\x03\x001Q0")
+            .unwrap();
+        let bitmap = printer.into_inner().render();
+        assert_eq!(bitmap.height(), plain.height());
+        assert_eq!(count(&bitmap, 0..96, 0..30), count(&plain, 0..96, 0..30));
+    }
+
+    #[test]
+    fn test_png_encoding() {
+        let mut printer = Printer::new(RasterEmulator::new(20));
+        printer.write_line("x").unwrap();
+        let png = printer.into_inner().render_png();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..24], &[0, 0, 0, 20, 0, 0, 0, 30]);
+        assert_eq!(&png[png.len() - 8..png.len() - 4], b"IEND");
+    }
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(0xFFFF_FFFF, b"123456789") ^ 0xFFFF_FFFF, 0xCBF4_3926);
+    }
+}
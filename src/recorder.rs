@@ -0,0 +1,139 @@
+use super::{BufferFull, Decoder, Write};
+
+/// Fixed-capacity transport that stores output for sending later.
+///
+/// Build a receipt into a `Recorder` while the printer is busy or offline,
+/// then [`replay`](Recorder::replay) it in one go. No allocation is needed;
+/// the capacity `N` is part of the type. A write that doesn't fit fails with
+/// [`BufferFull`] and leaves the recorded bytes unchanged.
+///
+/// ```
+/// # use escpos_embedded::{CutMode, Printer, Recorder};
+/// let mut printer = Printer::new(Recorder::<256>::new());
+/// printer.write_line("Order #42").unwrap();
+/// printer.cut(CutMode::Partial).unwrap();
+/// let receipt = printer.into_inner();
+/// assert_eq!(receipt.commands().count(), 3);
+/// // later: receipt.replay(&mut uart)?;
+/// ```
+#[derive(Clone, Debug)]
+pub struct Recorder<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> Recorder<N> {
+    /// Create an empty recorder.
+    pub const fn new() -> Self {
+        Self {
+            buf: [0; N],
+            len: 0,
+        }
+    }
+
+    /// The recorded bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    /// Number of bytes recorded.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether nothing has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of bytes that can still be recorded.
+    pub fn remaining(&self) -> usize {
+        N - self.len
+    }
+
+    /// Discard the recorded bytes.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Discard the first `n` recorded bytes, keeping the rest.
+    pub(crate) fn drain_front(&mut self, n: usize) {
+        self.buf.copy_within(n..self.len, 0);
+        self.len -= n;
+    }
+
+    /// Decode the recorded commands.
+    pub fn commands(&self) -> Decoder<'_> {
+        Decoder::new(self.as_bytes())
+    }
+
+    /// Write the recorded bytes to `out` in a single write.
+    ///
+    /// The recording is kept, so the same receipt can be replayed again.
+    pub fn replay<W: Write>(&self, out: &mut W) -> Result<(), W::Error> {
+        if self.is_empty() {
+            return Ok(());
+        }
+        out.write(self.as_bytes())
+    }
+}
+
+impl<const N: usize> Default for Recorder<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for Recorder<N> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<const N: usize> Write for Recorder<N> {
+    type Error = BufferFull;
+
+    fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        if data.len() > self.remaining() {
+            return Err(BufferFull);
+        }
+        self.buf[self.len..self.len + data.len()].copy_from_slice(data);
+        self.len += data.len();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockTransport;
+    use crate::{Printer, PrinterError};
+
+    #[test]
+    fn test_record_and_replay() {
+        let mut printer = Printer::new(Recorder::<16>::new());
+        printer.set_bold(true).unwrap();
+        printer.write_line("Hi").unwrap();
+        let recorder = printer.into_inner();
+        let mut transport = MockTransport::new();
+        recorder.replay(&mut transport).unwrap();
+        recorder.replay(&mut transport).unwrap();
+        let expected = [0x1B, 0x45, 0x01, b'H', b'i', b'\n'].repeat(2);
+        assert_eq!(transport.written(), expected);
+    }
+
+    #[test]
+    fn test_overflow_keeps_recording() {
+        let mut printer = Printer::new(Recorder::<4>::new());
+        printer.write("abc").unwrap();
+        assert_eq!(
+            printer.cut(crate::CutMode::Full),
+            Err(PrinterError::Transport {
+                command: "Cut",
+                error: BufferFull,
+            })
+        );
+        assert_eq!(printer.transport().as_bytes(), b"abc");
+        assert_eq!(printer.transport().remaining(), 1);
+    }
+}
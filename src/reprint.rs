@@ -0,0 +1,212 @@
+use super::hooks::Hooked;
+use super::{Chunked, Command, Decoder, Delay, Printer, PrinterError, Recorder, Write};
+
+/// Transport wrapper that keeps a copy of the last receipt for reprinting.
+///
+/// Everything written is passed through and also recorded; a receipt ends
+/// with a cut (or an explicit [`end_receipt`](Reprint::end_receipt) on
+/// printers without a cutter). The last complete receipt, up to `N` bytes,
+/// can be sent again with [`Printer::reprint_last`] when a customer asks for
+/// a copy:
+///
+/// ```
+/// # use escpos_embedded::{CutMode, Printer, Recorder, Reprint};
+/// let mut printer = Printer::new(Reprint::<_, 512>::new(Recorder::<1024>::new()));
+/// printer.write_line("Order #42").unwrap();
+/// printer.cut(CutMode::Partial).unwrap();
+/// assert!(printer.reprint_last().unwrap());
+/// ```
+///
+/// Receipts longer than `N` bytes aren't kept; reprinting after one sends
+/// nothing.
+pub struct Reprint<T, const N: usize> {
+    inner: T,
+    last: Recorder<N>,
+    has_last: bool,
+    current: Recorder<N>,
+    scanned: usize,
+    overflow: bool,
+}
+
+impl<T: Write, const N: usize> Reprint<T, N> {
+    /// Wrap `inner`, with nothing recorded yet.
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            last: Recorder::new(),
+            has_last: false,
+            current: Recorder::new(),
+            scanned: 0,
+            overflow: false,
+        }
+    }
+
+    /// The last complete receipt, if it fitted.
+    pub fn last_receipt(&self) -> Option<&[u8]> {
+        self.has_last.then(|| self.last.as_bytes())
+    }
+
+    /// Treat everything written since the last receipt as a complete
+    /// receipt.
+    pub fn end_receipt(&mut self) {
+        self.finish(self.current.len());
+    }
+
+    /// Borrow the wrapped transport.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Mutably borrow the wrapped transport.
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consume the wrapper, returning the wrapped transport.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    fn finish(&mut self, end: usize) {
+        self.has_last = !self.overflow;
+        self.last.clear();
+        // Can't fail: both recorders have the same capacity.
+        let _ = self.last.write(&self.current.as_bytes()[..end]);
+        self.current.drain_front(end);
+        self.scanned = 0;
+        self.overflow = false;
+    }
+
+    /// Look for a cut in the newly recorded bytes.
+    fn scan(&mut self) {
+        let mut decoder = Decoder::new(&self.current.as_bytes()[self.scanned..]);
+        while let Some(command) = decoder.next() {
+            // An unknown sequence at the very end may be a command split
+            // across writes.
+            if matches!(command, Command::Unknown(_)) && decoder.remaining().is_empty() {
+                return;
+            }
+            let end = self.scanned + decoder.position();
+            if matches!(
+                command,
+                Command::Cut(_) | Command::FeedAndCut { .. } | Command::FeedLabelToCut
+            ) {
+                self.finish(end);
+                return self.scan();
+            }
+            if decoder.remaining().is_empty() {
+                self.scanned = end;
+            }
+        }
+    }
+}
+
+impl<T: Write, const N: usize> Write for Reprint<T, N> {
+    type Error = T::Error;
+
+    fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        self.inner.write(data)?;
+        if self.current.write(data).is_err() {
+            // Too long to keep; still watch for the cut that ends it.
+            self.overflow = true;
+            self.current.clear();
+            self.scanned = 0;
+            let _ = self.current.write(&data[data.len().saturating_sub(N)..]);
+        }
+        self.scan();
+        Ok(())
+    }
+}
+
+impl<T: Write, P: Delay, const N: usize> Printer<Reprint<T, N>, P> {
+    /// Send the last complete receipt again.
+    ///
+    /// Returns `false`, sending nothing, if no receipt has been completed or
+    /// the last one was too long to keep.
+    ///
+    /// The receipt is sent one command at a time, as by
+    /// [`Printer::replay`], but without being recorded again.
+    pub fn reprint_last(&mut self) -> Result<bool, PrinterError<T::Error>> {
+        let reprint = &mut self.transport;
+        if !reprint.has_last {
+            return Ok(false);
+        }
+        let last = reprint.last.as_bytes();
+        let mut decoder = Decoder::new(last);
+        let mut start = 0;
+        while let Some(command) = decoder.next() {
+            let bytes = &last[start..decoder.position()];
+            start = decoder.position();
+            self.hooks.check(&command)?;
+            let mut out = Hooked::new(&mut reprint.inner, self.hooks);
+            let result = match self.chunk_size {
+                Some(max) => Chunked::new(&mut out, max).write(bytes),
+                None => out.write(bytes),
+            };
+            out.finish(command.name(), result)?;
+            if let Some(pacing) = &mut self.pacing {
+                pacing.pause(&command);
+            }
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{LimitedMockTransport, MockTransport, RecordDelay};
+    use crate::{CutMode, Pacing};
+
+    #[test]
+    fn test_reprint_last() {
+        let mut printer = Printer::builder(Reprint::<_, 32>::new(LimitedMockTransport::new(2)))
+            .chunk_size(2)
+            .build()
+            .unwrap();
+        assert!(!printer.reprint_last().unwrap());
+        printer.write_line("One").unwrap();
+        printer.cut(CutMode::Full).unwrap();
+        printer.write_line("Two").unwrap();
+        printer.cut(CutMode::Partial).unwrap();
+        printer.write_line("Three").unwrap();
+        assert_eq!(
+            printer.transport().last_receipt(),
+            Some(&b"Two\n\x1D\x56\x01"[..])
+        );
+        assert!(printer.reprint_last().unwrap());
+        assert!(printer
+            .transport()
+            .inner()
+            .written()
+            .ends_with(b"Three\nTwo\n\x1D\x56\x01"));
+    }
+
+    #[test]
+    fn test_reprint_last_paced() {
+        let pacing = Pacing::new(RecordDelay::default()).after_cut(300);
+        let mut printer = Printer::builder(Reprint::<_, 32>::new(MockTransport::new()))
+            .pacing(pacing)
+            .build()
+            .unwrap();
+        printer.write_line("One").unwrap();
+        printer.cut(CutMode::Full).unwrap();
+        assert!(printer.reprint_last().unwrap());
+        assert_eq!(
+            printer.transport().inner().written(),
+            b"One\n\x1D\x56\x00One\n\x1D\x56\x00"
+        );
+        assert_eq!(printer.pacing().unwrap().delay().0, [300, 300]);
+    }
+
+    #[test]
+    fn test_long_receipt_is_not_kept() {
+        let mut printer = Printer::new(Reprint::<_, 8>::new(MockTransport::new()));
+        printer.write_line("Short").unwrap();
+        printer.cut(CutMode::Full).unwrap();
+        printer.write_line("Much too long").unwrap();
+        printer.cut(CutMode::Full).unwrap();
+        assert_eq!(printer.transport().last_receipt(), None);
+        assert!(!printer.reprint_last().unwrap());
+    }
+}
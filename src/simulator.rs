@@ -0,0 +1,337 @@
+use std::collections::VecDeque;
+use std::vec::Vec;
+
+use super::{
+    AsbFlags, Command, CustomSetting, Decoder, PrinterIdKind, Read, Status, Write, USER_MEMORY_SIZE,
+};
+
+/// Transport simulating a printer that answers status queries.
+///
+/// The simulator decodes everything written to it and responds to
+/// `DLE EOT n`, `GS I n` and `GS r 1` queries from a scriptable [`Status`].
+/// Memory switches and customized values written in user setting mode
+/// (`GS ( E`), and NV user memory (`FS g`), are remembered and can be read
+/// back.
+/// When Automatic Status Back is enabled with `GS a n`, it queues a report
+/// immediately and again whenever an enabled part of the status changes.
+///
+/// Scripted events make it possible to exercise error handling without
+/// hardware:
+///
+/// ```
+/// # use escpos_embedded::{Printer, SimulatedPrinter};
+/// let sim = SimulatedPrinter::new().paper_out_at(10).cover_opens_after_cut();
+/// let mut printer = Printer::new(sim);
+/// printer.write_line("0123456789ABC").unwrap();
+/// assert!(printer.status().unwrap().paper_end);
+/// ```
+#[derive(Default)]
+pub struct SimulatedPrinter {
+    received: Vec<u8>,
+    pending: Vec<u8>,
+    responses: VecDeque<u8>,
+    status: Status,
+    printed: usize,
+    paper_out_at: Option<usize>,
+    near_end_at: Option<usize>,
+    cover_opens_after_cut: bool,
+    asb: AsbFlags,
+    ids: [u8; 3],
+    memory_switches: [u8; 8],
+    density: u16,
+    print_speed: u16,
+    user_memory: Vec<u8>,
+}
+
+impl SimulatedPrinter {
+    /// Create an online printer with paper loaded.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run out of paper once `bytes` bytes of print data have been received.
+    pub fn paper_out_at(mut self, bytes: usize) -> Self {
+        self.paper_out_at = Some(bytes);
+        self
+    }
+
+    /// Report paper near end once `bytes` bytes of print data have been
+    /// received.
+    pub fn paper_near_end_at(mut self, bytes: usize) -> Self {
+        self.near_end_at = Some(bytes);
+        self
+    }
+
+    /// Open the cover (taking the printer offline) after every cut.
+    pub fn cover_opens_after_cut(mut self) -> Self {
+        self.cover_opens_after_cut = true;
+        self
+    }
+
+    /// Set the values returned for `GS I 1`, `2` and `3`.
+    pub fn with_printer_ids(mut self, model: u8, type_id: u8, firmware: u8) -> Self {
+        self.ids = [model, type_id, firmware];
+        self
+    }
+
+    /// Current simulated status.
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    /// Replace the simulated status, sending an ASB report if enabled.
+    pub fn set_status(&mut self, status: Status) {
+        let old = core::mem::replace(&mut self.status, status);
+        let changed = |f: fn(&Status) -> bool| f(&old) != f(&status);
+        let report = (self.asb.contains(AsbFlags::DRAWER) && changed(|s| s.drawer_open))
+            || (self.asb.contains(AsbFlags::ONLINE)
+                && (changed(|s| s.offline) || changed(|s| s.cover_open)))
+            || (self.asb.contains(AsbFlags::ERROR)
+                && (changed(|s| s.cutter_error)
+                    || changed(|s| s.unrecoverable_error)
+                    || changed(|s| s.auto_recoverable_error)))
+            || (self.asb.contains(AsbFlags::PAPER)
+                && (changed(|s| s.paper_near_end) || changed(|s| s.paper_end)));
+        if report {
+            self.responses.extend(status.to_asb());
+        }
+    }
+
+    /// Every byte received so far.
+    pub fn received(&self) -> &[u8] {
+        &self.received
+    }
+
+    /// Number of print data bytes accepted before the paper ran out.
+    pub fn printed(&self) -> usize {
+        self.printed
+    }
+
+    fn print(&mut self, bytes: usize) {
+        if self.status.paper_end {
+            return;
+        }
+        self.printed += bytes;
+        let mut status = self.status;
+        if self.near_end_at.is_some_and(|n| self.printed >= n) {
+            status.paper_near_end = true;
+        }
+        if let Some(limit) = self.paper_out_at.filter(|&n| self.printed >= n) {
+            self.printed = limit;
+            status.paper_near_end = true;
+            status.paper_end = true;
+            status.paper_end_stop = true;
+            status.offline = true;
+        }
+        self.set_status(status);
+    }
+
+    fn handle(&mut self, command: &Command) {
+        match *command {
+            Command::Text(text) => self.print(text.len()),
+            Command::LineFeed => self.print(1),
+            Command::RasterImage { data, .. } => self.print(data.len()),
+            Command::Cut(_) | Command::FeedAndCut { .. } if self.cover_opens_after_cut => {
+                let status = Status {
+                    cover_open: true,
+                    offline: true,
+                    ..self.status
+                };
+                self.set_status(status);
+            }
+            Command::TransmitStatus(request) => {
+                self.responses.push_back(self.status.response(request));
+            }
+            Command::TransmitPrinterId(kind) => {
+                let id = match kind {
+                    PrinterIdKind::Model => self.ids[0],
+                    PrinterIdKind::Type => self.ids[1],
+                    PrinterIdKind::Firmware => self.ids[2],
+                };
+                self.responses.push_back(id);
+            }
+            Command::QueryPaperStatus => {
+                let mut byte = 0x00;
+                if self.status.paper_near_end {
+                    byte |= 0x03;
+                }
+                if self.status.paper_end {
+                    byte |= 0x0C;
+                }
+                self.responses.push_back(byte);
+            }
+            Command::EnterUserSetting => self.responses.extend([0x37, 0x20, 0x00]),
+            Command::SetMemorySwitch {
+                switch: switch @ 1..=8,
+                mask,
+                value,
+            } => {
+                let msw = &mut self.memory_switches[usize::from(switch - 1)];
+                *msw = (*msw & !mask) | (value & mask);
+            }
+            Command::TransmitMemorySwitch(switch @ 1..=8) => {
+                let msw = self.memory_switches[usize::from(switch - 1)];
+                self.responses.extend([0x37, 0x21]);
+                self.responses
+                    .extend((0..8).rev().map(|n| b'0' + (msw >> n & 1)));
+                self.responses.push_back(0x00);
+            }
+            Command::WriteUserMemory { address, data } => {
+                let start = (address as usize).min(USER_MEMORY_SIZE);
+                let end = start.saturating_add(data.len()).min(USER_MEMORY_SIZE);
+                let data = &data[..end - start];
+                if self.user_memory.len() < end {
+                    self.user_memory.resize(end, 0);
+                }
+                self.user_memory[start..end].copy_from_slice(data);
+            }
+            Command::ReadUserMemory { address, len } => {
+                let start = (address as usize).min(USER_MEMORY_SIZE);
+                let end = (start + usize::from(len)).min(USER_MEMORY_SIZE);
+                if self.user_memory.len() < end {
+                    self.user_memory.resize(end, 0);
+                }
+                self.responses.push_back(0x5F);
+                self.responses.extend(&self.user_memory[start..end]);
+                self.responses.push_back(0x00);
+            }
+            Command::SetCustomValue { setting, value } => match setting {
+                CustomSetting::Density => self.density = value,
+                CustomSetting::PrintSpeed => self.print_speed = value,
+            },
+            Command::TransmitCustomValue(setting) => {
+                let value = match setting {
+                    CustomSetting::Density => self.density,
+                    CustomSetting::PrintSpeed => self.print_speed,
+                };
+                self.responses.extend([0x37, 0x27, setting.as_byte(), 0x1F]);
+                self.responses.extend(std::format!("{}", value).bytes());
+                self.responses.push_back(0x00);
+            }
+            Command::SetAutoStatusBack(flags) => {
+                self.asb = flags;
+                if !flags.is_empty() {
+                    self.responses.extend(self.status.to_asb());
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Write for SimulatedPrinter {
+    type Error = core::convert::Infallible;
+
+    fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        self.received.extend_from_slice(data);
+        self.pending.extend_from_slice(data);
+        let pending = core::mem::take(&mut self.pending);
+        let mut decoder = Decoder::new(&pending);
+        let mut consumed = 0;
+        while let Some(command) = decoder.next() {
+            // An unknown sequence at the very end may just be a command split
+            // across writes; keep it until more data arrives.
+            if matches!(command, Command::Unknown(_)) && decoder.remaining().is_empty() {
+                break;
+            }
+            self.handle(&command);
+            consumed = decoder.position();
+        }
+        self.pending = pending[consumed..].to_vec();
+        Ok(())
+    }
+}
+
+impl Read for SimulatedPrinter {
+    type Error = core::convert::Infallible;
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let n = buf.len().min(self.responses.len());
+        for (slot, byte) in buf.iter_mut().zip(self.responses.drain(..n)) {
+            *slot = byte;
+        }
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CutMode, Printer, StatusRequest};
+
+    #[test]
+    fn test_status_queries() {
+        let mut printer = Printer::new(SimulatedPrinter::new().with_printer_ids(0x20, 0x02, 0x11));
+        assert_eq!(printer.status().unwrap(), Status::default());
+        assert_eq!(
+            printer.transmit_status(StatusRequest::Printer).unwrap(),
+            0x12
+        );
+        assert_eq!(printer.printer_id(PrinterIdKind::Firmware).unwrap(), 0x11);
+    }
+
+    #[test]
+    fn test_paper_out_at_byte() {
+        let mut printer = Printer::new(SimulatedPrinter::new().paper_out_at(4));
+        printer.write("abc").unwrap();
+        assert!(!printer.status().unwrap().paper_end);
+        printer.write("def").unwrap();
+        let status = printer.status().unwrap();
+        assert!(status.paper_end && status.offline);
+        assert_eq!(printer.paper_status().unwrap(), 0x0F);
+        assert_eq!(printer.transport().printed(), 4);
+    }
+
+    #[test]
+    fn test_asb_on_cover_open_after_cut() {
+        let mut printer = Printer::new(SimulatedPrinter::new().cover_opens_after_cut());
+        printer.set_auto_status_back(AsbFlags::ONLINE).unwrap();
+        assert_eq!(printer.read_auto_status().unwrap(), Some(Status::default()));
+        assert_eq!(printer.read_auto_status().unwrap(), None);
+        printer.cut(CutMode::Full).unwrap();
+        let status = printer.read_auto_status().unwrap().unwrap();
+        assert!(status.cover_open && status.offline);
+    }
+
+    #[test]
+    fn test_memory_switches() {
+        let mut printer = Printer::new(SimulatedPrinter::new());
+        printer.enter_user_setting().unwrap();
+        printer.set_memory_switch(2, 0x81, 0x01).unwrap();
+        printer.set_memory_switch(2, 0x06, 0xFF).unwrap();
+        assert_eq!(printer.memory_switch(2).unwrap(), 0x07);
+        assert_eq!(printer.memory_switch(1).unwrap(), 0x00);
+        printer.exit_user_setting().unwrap();
+    }
+
+    #[test]
+    fn test_stored_density_and_speed() {
+        let mut printer = Printer::new(SimulatedPrinter::new());
+        printer.enter_user_setting().unwrap();
+        printer.store_density(-2).unwrap();
+        printer.store_print_speed(9).unwrap();
+        assert_eq!(printer.stored_density().unwrap(), -2);
+        assert_eq!(printer.stored_print_speed().unwrap(), 9);
+    }
+
+    #[test]
+    fn test_user_memory() {
+        let mut printer = Printer::new(SimulatedPrinter::new());
+        let data: Vec<u8> = (0..100).collect();
+        printer.write_user_memory(900, &data).unwrap();
+        let mut buf = [0u8; 100];
+        printer.read_user_memory(900, &mut buf).unwrap();
+        assert_eq!(buf.as_slice(), data);
+        assert!(printer.write_user_memory(1000, &data).is_err());
+    }
+
+    #[test]
+    fn test_command_split_across_writes() {
+        let mut sim = SimulatedPrinter::new();
+        sim.write(&[0x10]).unwrap();
+        sim.write(&[0x04, 0x01]).unwrap();
+        let mut buf = [0u8; 2];
+        assert_eq!(sim.read(&mut buf).unwrap(), 1);
+        assert_eq!(buf[0], 0x12);
+    }
+}
@@ -0,0 +1,182 @@
+use core::fmt;
+
+use super::{Command, CutMode, Delay, Printer, PrinterError, Read, Write};
+
+/// Prints a batch of receipts back-to-back.
+///
+/// Each receipt is any byte container, such as a [`Job`](crate::Job) or
+/// [`Recorder`](crate::Recorder). After every receipt the spool feeds and
+/// cuts as configured:
+///
+/// ```
+/// # use escpos_embedded::{CutMode, Job, Printer, PrinterError, Spool, Write};
+/// # fn end_of_day<T: Write>(printer: &mut Printer<T>, reports: &[Job<512>]) -> Result<(), PrinterError<T::Error>> {
+/// let spool = Spool::new().feed(3).cut(Some(CutMode::Full));
+/// spool.print(printer, reports).map_err(|e| e.error)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Spool {
+    feed: u8,
+    cut: Option<CutMode>,
+}
+
+/// Error from [`Spool`], recording how far the batch got.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpoolError<E> {
+    /// Number of receipts fully printed before the failure.
+    pub printed: usize,
+    /// What went wrong with the next receipt.
+    pub error: PrinterError<E>,
+}
+
+impl<E: fmt::Display> fmt::Display for SpoolError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "receipt {}: {}", self.printed + 1, self.error)
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> core::error::Error for SpoolError<E> {}
+
+impl Spool {
+    /// Partial cut after each receipt, without extra feed.
+    pub const fn new() -> Self {
+        Self {
+            feed: 0,
+            cut: Some(CutMode::Partial),
+        }
+    }
+
+    /// Feed `lines` after each receipt, before cutting.
+    pub const fn feed(mut self, lines: u8) -> Self {
+        self.feed = lines;
+        self
+    }
+
+    /// Cut after each receipt, or not at all with `None`.
+    ///
+    /// Cuts go through [`Printer::cut`], so on printers without a cutter
+    /// use `None` or configure a
+    /// [fallback](crate::PrinterBuilder::cut_fallback).
+    pub const fn cut(mut self, mode: Option<CutMode>) -> Self {
+        self.cut = mode;
+        self
+    }
+
+    /// Print every receipt, returning how many were printed.
+    ///
+    /// Receipts are sent with [`Printer::replay`], so errors name the
+    /// failing command and hooks and pacing apply within each receipt.
+    pub fn print<T, P: Delay, I>(
+        &self,
+        printer: &mut Printer<T, P>,
+        receipts: I,
+    ) -> Result<usize, SpoolError<T::Error>>
+    where
+        T: Write,
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        let mut printed = 0;
+        for receipt in receipts {
+            self.print_one(printer, receipt.as_ref())
+                .map_err(|error| SpoolError { printed, error })?;
+            printed += 1;
+        }
+        Ok(printed)
+    }
+
+    /// Like [`print`](Spool::print), but check the printer's status before
+    /// each receipt and stop with [`PrinterError::NotReady`] if it can't
+    /// print.
+    pub fn print_checked<T, P: Delay, I>(
+        &self,
+        printer: &mut Printer<T, P>,
+        receipts: I,
+    ) -> Result<usize, SpoolError<<T as Write>::Error>>
+    where
+        T: Write + Read<Error = <T as Write>::Error>,
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        let mut printed = 0;
+        for receipt in receipts {
+            let result = printer.status().and_then(|status| {
+                if !status.is_ready() {
+                    return Err(PrinterError::NotReady {
+                        command: "Spool",
+                        status,
+                    });
+                }
+                self.print_one(printer, receipt.as_ref())
+            });
+            result.map_err(|error| SpoolError { printed, error })?;
+            printed += 1;
+        }
+        Ok(printed)
+    }
+
+    fn print_one<T: Write, P: Delay>(
+        &self,
+        printer: &mut Printer<T, P>,
+        receipt: &[u8],
+    ) -> Result<(), PrinterError<T::Error>> {
+        printer.replay(receipt, false)?;
+        if self.feed > 0 {
+            printer.send(&Command::Feed(self.feed))?;
+        }
+        if let Some(mode) = self.cut {
+            printer.cut(mode)?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for Spool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{FaultyTransport, MockTransport};
+
+    #[test]
+    fn test_print_with_separators() {
+        let mut printer = Printer::new(MockTransport::new());
+        let printed = Spool::new()
+            .feed(2)
+            .cut(Some(CutMode::Full))
+            .print(&mut printer, [&b"A"[..], b"B"])
+            .unwrap();
+        assert_eq!(printed, 2);
+        let expected = b"A\x1B\x64\x02\x1D\x56\x00B\x1B\x64\x02\x1D\x56\x00";
+        assert_eq!(printer.transport().written(), expected);
+    }
+
+    #[test]
+    fn test_error_reports_progress() {
+        let mut printer = Printer::new(FaultyTransport::new(MockTransport::new()).fail_write(4));
+        let err = Spool::new()
+            .cut(None)
+            .print(&mut printer, [b"A\x1DV\x00", b"B\x1DV\x00"])
+            .unwrap_err();
+        assert_eq!(err.printed, 1);
+        assert_eq!(err.error.command(), "Cut");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_print_checked_stops_on_paper_out() {
+        let mut printer = Printer::new(crate::SimulatedPrinter::new().paper_out_at(4));
+        let err = Spool::new()
+            .cut(None)
+            .print_checked(&mut printer, ["abc", "def", "ghi"])
+            .unwrap_err();
+        assert_eq!(err.printed, 2);
+        assert!(matches!(err.error, PrinterError::NotReady { .. }));
+    }
+}
@@ -0,0 +1,215 @@
+use core::iter;
+use std::panic;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::{self, JoinHandle};
+use std::vec::Vec;
+
+use super::{Delay, Printer, PrinterError, Spool, Write};
+
+/// Identifies a job submitted to a [`Spooler`].
+///
+/// Ids are handed out in submission order, starting from 0.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct JobId(pub u64);
+
+/// Outcome of a job, reported by a [`Spooler`] once the job is done.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SpoolerEvent<E> {
+    /// The job was sent to the printer.
+    Printed(JobId),
+    /// Sending the job failed. Later jobs are still attempted.
+    Failed(JobId, PrinterError<E>),
+}
+
+impl<E> SpoolerEvent<E> {
+    /// The job this event is about.
+    pub fn job(&self) -> JobId {
+        match self {
+            SpoolerEvent::Printed(id) | SpoolerEvent::Failed(id, _) => *id,
+        }
+    }
+}
+
+/// Prints jobs on a background thread.
+///
+/// The spooler owns the printer and sends queued jobs one after another,
+/// so [`submit`](Spooler::submit) returns immediately even when the link is
+/// a slow serial line. This keeps the UI thread of a POS application
+/// responsive; it learns how each job went from [`SpoolerEvent`]s:
+///
+/// ```
+/// # use escpos_embedded::{Job, Printer, Spool, Spooler, SpoolerEvent, SimulatedPrinter};
+/// let mut spooler = Spooler::spawn(Printer::new(SimulatedPrinter::new()), Spool::new());
+///
+/// let mut receipt = Job::<256>::new();
+/// receipt.write_line("Thank you!").unwrap();
+/// let id = spooler.submit(&receipt);
+///
+/// // Poll with `try_event` from the UI event loop, or wait:
+/// assert_eq!(spooler.wait_event(), Some(SpoolerEvent::Printed(id)));
+/// let printer = spooler.shutdown();
+/// ```
+///
+/// Each job is printed with the [`Spool`] settings given to
+/// [`spawn`](Spooler::spawn), e.g. to cut after every receipt.
+pub struct Spooler<T: Write, P: Delay = ()> {
+    jobs: Option<Sender<(JobId, Vec<u8>)>>,
+    events: Receiver<SpoolerEvent<T::Error>>,
+    worker: Option<JoinHandle<Printer<T, P>>>,
+    next: u64,
+    reported: u64,
+}
+
+impl<T, P> Spooler<T, P>
+where
+    T: Write + Send + 'static,
+    T::Error: Send + 'static,
+    P: Delay + Send + 'static,
+{
+    /// Move `printer` to a new thread that prints jobs with `spool`.
+    pub fn spawn(mut printer: Printer<T, P>, spool: Spool) -> Self {
+        let (jobs, queue) = mpsc::channel::<(JobId, Vec<u8>)>();
+        let (notify, events) = mpsc::channel();
+        let worker = thread::spawn(move || {
+            for (id, job) in queue {
+                let event = match spool.print(&mut printer, [job]) {
+                    Ok(_) => SpoolerEvent::Printed(id),
+                    Err(err) => SpoolerEvent::Failed(id, err.error),
+                };
+                // Nobody listening is fine; keep printing.
+                let _ = notify.send(event);
+            }
+            printer
+        });
+        Self {
+            jobs: Some(jobs),
+            events,
+            worker: Some(worker),
+            next: 0,
+            reported: 0,
+        }
+    }
+
+    /// Queue a copy of `job` for printing and return its id.
+    ///
+    /// # Panics
+    ///
+    /// If the printing thread panicked, the panic is resumed here.
+    pub fn submit(&mut self, job: impl AsRef<[u8]>) -> JobId {
+        let id = JobId(self.next);
+        self.next += 1;
+        let sent = match &self.jobs {
+            Some(jobs) => jobs.send((id, job.as_ref().to_vec())).is_ok(),
+            None => false,
+        };
+        if !sent {
+            self.join();
+        }
+        id
+    }
+
+    /// Take the next event if one has arrived, without waiting.
+    pub fn try_event(&mut self) -> Option<SpoolerEvent<T::Error>> {
+        let event = self.events.try_recv().ok()?;
+        self.reported += 1;
+        Some(event)
+    }
+
+    /// Events that have arrived since the last call, without waiting.
+    pub fn events(&mut self) -> impl Iterator<Item = SpoolerEvent<T::Error>> + '_ {
+        iter::from_fn(|| self.try_event())
+    }
+
+    /// Wait for the next event. Returns `None` once every submitted job
+    /// has been reported.
+    pub fn wait_event(&mut self) -> Option<SpoolerEvent<T::Error>> {
+        if self.reported == self.next {
+            return None;
+        }
+        let event = self.events.recv().ok()?;
+        self.reported += 1;
+        Some(event)
+    }
+
+    /// Finish the queued jobs, stop the thread and return the printer.
+    ///
+    /// Events not yet taken are dropped.
+    ///
+    /// # Panics
+    ///
+    /// If the printing thread panicked, the panic is resumed here.
+    pub fn shutdown(mut self) -> Printer<T, P> {
+        self.join()
+    }
+
+    fn join(&mut self) -> Printer<T, P> {
+        drop(self.jobs.take());
+        let worker = self.worker.take().expect("spooler already stopped");
+        worker
+            .join()
+            .unwrap_or_else(|payload| panic::resume_unwind(payload))
+    }
+}
+
+impl<T: Write, P: Delay> Drop for Spooler<T, P> {
+    fn drop(&mut self) {
+        drop(self.jobs.take());
+        if let Some(worker) = self.worker.take() {
+            // Let queued jobs finish. A panic on the printing thread has
+            // already been reported there.
+            let _ = worker.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{FaultError, FaultyTransport, MockTransport};
+
+    #[test]
+    fn test_prints_in_order() {
+        let mut spooler =
+            Spooler::spawn(Printer::new(MockTransport::new()), Spool::new().cut(None));
+        let ids: Vec<_> = ["A", "B", "C"]
+            .iter()
+            .map(|job| spooler.submit(job))
+            .collect();
+        assert_eq!(ids, [JobId(0), JobId(1), JobId(2)]);
+        let events: Vec<_> = iter::from_fn(|| spooler.wait_event()).collect();
+        assert_eq!(
+            events,
+            ids.iter()
+                .map(|&id| SpoolerEvent::Printed(id))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(spooler.wait_event(), None);
+        assert_eq!(spooler.shutdown().transport().written(), b"ABC");
+    }
+
+    #[test]
+    fn test_failure_is_reported_and_printing_continues() {
+        let transport = FaultyTransport::new(MockTransport::new()).fail_write(1);
+        let mut spooler = Spooler::spawn(Printer::new(transport), Spool::new().cut(None));
+        let first = spooler.submit(b"A");
+        let second = spooler.submit(b"B");
+        let events = [spooler.wait_event().unwrap(), spooler.wait_event().unwrap()];
+        assert!(matches!(
+            &events[0],
+            SpoolerEvent::Failed(id, PrinterError::Transport { error: FaultError::Injected, .. })
+                if *id == first
+        ));
+        assert_eq!(events[1], SpoolerEvent::Printed(second));
+        assert_eq!(events[1].job(), second);
+    }
+
+    #[test]
+    fn test_shutdown_finishes_queue() {
+        let mut spooler = Spooler::spawn(Printer::new(MockTransport::new()), Spool::new());
+        spooler.submit(b"A");
+        spooler.submit(b"B");
+        let printer = spooler.shutdown();
+        assert_eq!(printer.transport().written(), b"A\x1D\x56\x01B\x1D\x56\x01");
+    }
+}
@@ -0,0 +1,153 @@
+use super::{Backend, BitImageScale, Command, CutMode, PrinterError, UnderlineMode, Write};
+
+/// Backend for Star Micronics printers in Star line mode.
+///
+/// Translates [`Command`]s into Star line mode sequences, so code written
+/// against [`Backend`] drives Star and ESC/POS fleets alike. Text, feeds,
+/// cuts, alignment, styles, density and raster images are supported;
+/// ESC/POS-only commands (status queries, macros, user settings, ...)
+/// return [`PrinterError::Unsupported`].
+///
+/// ```
+/// # use escpos_embedded::{Align, Backend, MockTransport, StarPrinter};
+/// let mut printer = StarPrinter::new(MockTransport::new());
+/// printer.set_align(Align::Center).unwrap();
+/// assert_eq!(printer.transport().written(), [0x1B, 0x1D, 0x61, 0x01]);
+/// ```
+pub struct StarPrinter<T> {
+    transport: T,
+}
+
+impl<T: Write> StarPrinter<T> {
+    /// Wrap a transport connected to a printer in Star line mode.
+    pub fn new(transport: T) -> Self {
+        Self { transport }
+    }
+
+    /// Borrow the underlying transport.
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// Mutably borrow the underlying transport.
+    pub fn transport_mut(&mut self) -> &mut T {
+        &mut self.transport
+    }
+
+    /// Consume the backend, returning the transport.
+    pub fn into_inner(self) -> T {
+        self.transport
+    }
+
+    fn raster(&mut self, width: u16, height: u16, data: &[u8]) -> Result<(), T::Error> {
+        let stride = width.div_ceil(8);
+        // ESC * r A enters raster mode; each row is `b n1 n2 data`.
+        self.transport.write(&[0x1B, 0x2A, 0x72, 0x41])?;
+        for row in data.chunks(usize::from(stride)).take(usize::from(height)) {
+            let n = (row.len() as u16).to_le_bytes();
+            self.transport.write(&[0x62, n[0], n[1]])?;
+            self.transport.write(row)?;
+        }
+        self.transport.write(&[0x1B, 0x2A, 0x72, 0x42])
+    }
+}
+
+impl<T: Write> Backend for StarPrinter<T> {
+    type Error = T::Error;
+
+    fn send(&mut self, command: &Command) -> Result<(), PrinterError<T::Error>> {
+        command.validate()?;
+        let out = &mut self.transport;
+        let result = match *command {
+            Command::Text(text) => out.write(text.as_bytes()),
+            Command::LineFeed => out.write(b"\n"),
+            Command::CarriageReturn => out.write(b"\r"),
+            Command::Initialize => out.write(&[0x1B, 0x40]),
+            Command::Feed(lines) => out.write(&[0x1B, 0x61, lines]),
+            // ESC d 2 / 3: feed to the cutter, then cut.
+            Command::Cut(CutMode::Full)
+            | Command::FeedAndCut {
+                mode: CutMode::Full,
+                ..
+            } => out.write(&[0x1B, 0x64, 0x02]),
+            Command::Cut(_) | Command::FeedAndCut { .. } => out.write(&[0x1B, 0x64, 0x03]),
+            Command::SetBold(on) => out.write(&[0x1B, if on { 0x45 } else { 0x46 }]),
+            Command::SetUnderline(mode) => {
+                out.write(&[0x1B, 0x2D, (mode != UnderlineMode::None) as u8])
+            }
+            Command::SetInvert(on) => out.write(&[0x1B, if on { 0x34 } else { 0x35 }]),
+            Command::SetAlign(align) => out.write(&[0x1B, 0x1D, 0x61, align.as_byte()]),
+            Command::SetJustification(mode) => out.write(&[0x1B, 0x1D, 0x61, mode.as_byte()]),
+            Command::SetFont(font) => out.write(&[0x1B, 0x1E, 0x46, font.as_byte()]),
+            // ESC i takes the height first; Star caps expansion at 6x.
+            Command::SetSize { width, height } => {
+                out.write(&[0x1B, 0x69, height.min(5), width.min(5)])
+            }
+            // Star density runs from 0 (darkest) to 6, with 3 as standard.
+            Command::SetDensity(density) => {
+                let n = 7u8.saturating_sub(density.as_byte()).min(6);
+                out.write(&[0x1B, 0x1E, 0x64, n])
+            }
+            Command::RasterImage {
+                width,
+                height,
+                scale: BitImageScale::Normal,
+                data,
+            } => self.raster(width, height, data),
+            Command::Raw(data) | Command::Vendor { data, .. } => out.write(data),
+            _ => {
+                return Err(PrinterError::Unsupported {
+                    command: command.name(),
+                })
+            }
+        };
+        result.map_err(|error| PrinterError::Transport {
+            command: command.name(),
+            error,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockTransport;
+    use crate::{Align, Density};
+
+    #[test]
+    fn test_receipt() {
+        let mut printer = StarPrinter::new(MockTransport::new());
+        printer.set_align(Align::Right).unwrap();
+        printer.send(&Command::SetBold(true)).unwrap();
+        printer.write_line("Hi").unwrap();
+        printer.feed(2).unwrap();
+        printer.cut(CutMode::Partial).unwrap();
+        let expected = [
+            0x1B, 0x1D, 0x61, 0x02, 0x1B, 0x45, b'H', b'i', b'\n', 0x1B, 0x61, 0x02, 0x1B, 0x64,
+            0x03,
+        ];
+        assert_eq!(printer.transport().written(), expected);
+    }
+
+    #[test]
+    fn test_raster() {
+        let mut printer = StarPrinter::new(MockTransport::new());
+        printer
+            .print_raster(9, 2, &[0xFF, 0x80, 0x01, 0x00])
+            .unwrap();
+        let expected = [
+            0x1B, 0x2A, 0x72, 0x41, 0x62, 0x02, 0x00, 0xFF, 0x80, 0x62, 0x02, 0x00, 0x01, 0x00,
+            0x1B, 0x2A, 0x72, 0x42,
+        ];
+        assert_eq!(printer.transport().written(), expected);
+    }
+
+    #[test]
+    fn test_density_and_unsupported() {
+        let mut printer = StarPrinter::new(MockTransport::new());
+        printer.set_density(Density::Level4).unwrap();
+        assert_eq!(printer.transport().written(), [0x1B, 0x1E, 0x64, 0x03]);
+        let err = printer.send(&Command::QueryPaperStatus).unwrap_err();
+        assert_eq!(err.command(), "QueryPaperStatus");
+    }
+}
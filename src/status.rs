@@ -0,0 +1,333 @@
+/// Real-time status requested with `DLE EOT n`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum StatusRequest {
+    /// Printer status (online, drawer, feed button).
+    Printer,
+    /// Offline cause (cover, paper end, error).
+    Offline,
+    /// Error cause (cutter, unrecoverable, auto-recoverable).
+    Error,
+    /// Roll paper sensor (near end, end).
+    PaperSensor,
+}
+
+impl StatusRequest {
+    pub(crate) fn as_byte(self) -> u8 {
+        match self {
+            StatusRequest::Printer => 0x01,
+            StatusRequest::Offline => 0x02,
+            StatusRequest::Error => 0x03,
+            StatusRequest::PaperSensor => 0x04,
+        }
+    }
+
+    pub(crate) fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0x01 => Some(StatusRequest::Printer),
+            0x02 => Some(StatusRequest::Offline),
+            0x03 => Some(StatusRequest::Error),
+            0x04 => Some(StatusRequest::PaperSensor),
+            _ => None,
+        }
+    }
+}
+
+/// Printer identification requested with `GS I n`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PrinterIdKind {
+    /// Printer model ID.
+    Model,
+    /// Type ID (capability bits).
+    Type,
+    /// Firmware version ID.
+    Firmware,
+}
+
+impl PrinterIdKind {
+    pub(crate) fn as_byte(self) -> u8 {
+        match self {
+            PrinterIdKind::Model => 0x01,
+            PrinterIdKind::Type => 0x02,
+            PrinterIdKind::Firmware => 0x03,
+        }
+    }
+
+    pub(crate) fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0x01 | 0x31 => Some(PrinterIdKind::Model),
+            0x02 | 0x32 => Some(PrinterIdKind::Type),
+            0x03 | 0x33 => Some(PrinterIdKind::Firmware),
+            _ => None,
+        }
+    }
+}
+
+/// Status changes reported by Automatic Status Back (`GS a n`).
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AsbFlags(pub u8);
+
+impl AsbFlags {
+    /// No automatic status reports.
+    pub const NONE: Self = Self(0x00);
+    /// Drawer kick-out connector pin 3.
+    pub const DRAWER: Self = Self(0x01);
+    /// Online/offline changes.
+    pub const ONLINE: Self = Self(0x02);
+    /// Error status changes.
+    pub const ERROR: Self = Self(0x04);
+    /// Roll paper sensor changes.
+    pub const PAPER: Self = Self(0x08);
+    /// Every supported status change.
+    pub const ALL: Self = Self(0x0F);
+
+    /// Whether all flags in `other` are set.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Whether no flags are set.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl core::ops::BitOr for AsbFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// Roll paper sensors selected with `ESC c 3` and `ESC c 4`.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PaperSensors(pub u8);
+
+impl PaperSensors {
+    /// No sensors.
+    pub const NONE: Self = Self(0x00);
+    /// Roll paper near-end sensor.
+    pub const NEAR_END: Self = Self(0x03);
+    /// Roll paper end sensor.
+    pub const END: Self = Self(0x0C);
+
+    /// Whether all sensors in `other` are selected.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl core::ops::BitOr for PaperSensors {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// What the printer does when the roll paper is nearly used up.
+///
+/// Applied with [`Printer::set_near_end_policy`](crate::Printer::set_near_end_policy)
+/// or [`PrinterBuilder::near_end_policy`](crate::PrinterBuilder::near_end_policy).
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NearEndPolicy {
+    /// Stop printing and report paper end at near-end. Receipts are never
+    /// cut short by the roll running out, at the cost of the paper left on
+    /// the stub.
+    Stop,
+    /// Keep printing until the roll runs out, reporting near-end only
+    /// through [`Status::paper_near_end`]. Uses the whole roll, but a
+    /// receipt may be truncated when it ends.
+    Continue,
+}
+
+/// Decoded printer status.
+///
+/// Built either from the four `DLE EOT` responses or from a 4-byte Automatic
+/// Status Back packet, which carry the same information.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Status {
+    /// Drawer kick-out connector pin 3 is high.
+    pub drawer_open: bool,
+    /// The printer is offline.
+    pub offline: bool,
+    /// The cover is open.
+    pub cover_open: bool,
+    /// Paper is being fed with the feed button.
+    pub paper_feeding: bool,
+    /// Printing stopped because the paper ran out.
+    pub paper_end_stop: bool,
+    /// An error occurred.
+    pub error: bool,
+    /// The autocutter failed.
+    pub cutter_error: bool,
+    /// An unrecoverable error occurred.
+    pub unrecoverable_error: bool,
+    /// An automatically recoverable error occurred (e.g. head overheating).
+    pub auto_recoverable_error: bool,
+    /// The roll paper is nearly used up.
+    pub paper_near_end: bool,
+    /// There is no roll paper.
+    pub paper_end: bool,
+}
+
+const fn bit(byte: u8, n: u8) -> bool {
+    byte & (1 << n) != 0
+}
+
+impl Status {
+    /// Decode the responses to `DLE EOT 1`, `2`, `3` and `4`.
+    pub const fn from_responses(printer: u8, offline: u8, error: u8, paper: u8) -> Self {
+        Self {
+            drawer_open: bit(printer, 2),
+            offline: bit(printer, 3),
+            cover_open: bit(offline, 2),
+            paper_feeding: bit(offline, 3),
+            paper_end_stop: bit(offline, 5),
+            error: bit(offline, 6),
+            cutter_error: bit(error, 3),
+            unrecoverable_error: bit(error, 5),
+            auto_recoverable_error: bit(error, 6),
+            paper_near_end: bit(paper, 2) || bit(paper, 3),
+            paper_end: bit(paper, 5) || bit(paper, 6),
+        }
+    }
+
+    /// Decode an Automatic Status Back packet.
+    ///
+    /// ASB has no dedicated bits for `paper_end_stop` and `error`, so they are
+    /// derived from the offline, paper end and error cause bits.
+    pub const fn from_asb(packet: [u8; 4]) -> Self {
+        Self {
+            drawer_open: bit(packet[0], 2),
+            offline: bit(packet[0], 3),
+            cover_open: bit(packet[0], 5),
+            paper_feeding: bit(packet[0], 6),
+            paper_end_stop: bit(packet[0], 3) && (bit(packet[2], 2) || bit(packet[2], 3)),
+            error: bit(packet[1], 3) || bit(packet[1], 5) || bit(packet[1], 6),
+            cutter_error: bit(packet[1], 3),
+            unrecoverable_error: bit(packet[1], 5),
+            auto_recoverable_error: bit(packet[1], 6),
+            paper_near_end: bit(packet[2], 0) || bit(packet[2], 1),
+            paper_end: bit(packet[2], 2) || bit(packet[2], 3),
+        }
+    }
+
+    /// Whether nothing prevents the printer from printing.
+    ///
+    /// `paper_near_end` and `drawer_open` don't count; the printer keeps
+    /// printing in both cases.
+    pub const fn is_ready(&self) -> bool {
+        !(self.offline
+            || self.cover_open
+            || self.paper_end
+            || self.paper_end_stop
+            || self.error
+            || self.cutter_error
+            || self.unrecoverable_error
+            || self.auto_recoverable_error)
+    }
+
+    /// Encode the `DLE EOT` response for `request`.
+    pub fn response(&self, request: StatusRequest) -> u8 {
+        let flags: &[(bool, u8)] = match request {
+            StatusRequest::Printer => &[(self.drawer_open, 0x04), (self.offline, 0x08)],
+            StatusRequest::Offline => &[
+                (self.cover_open, 0x04),
+                (self.paper_feeding, 0x08),
+                (self.paper_end_stop, 0x20),
+                (self.error, 0x40),
+            ],
+            StatusRequest::Error => &[
+                (self.cutter_error, 0x08),
+                (self.unrecoverable_error, 0x20),
+                (self.auto_recoverable_error, 0x40),
+            ],
+            StatusRequest::PaperSensor => &[(self.paper_near_end, 0x0C), (self.paper_end, 0x60)],
+        };
+        flags
+            .iter()
+            .filter(|(on, _)| *on)
+            .fold(0x12, |byte, (_, mask)| byte | mask)
+    }
+
+    /// Encode this status as an Automatic Status Back packet.
+    pub const fn to_asb(&self) -> [u8; 4] {
+        let mut packet = [0x10, 0x00, 0x00, 0x00];
+        if self.drawer_open {
+            packet[0] |= 0x04;
+        }
+        if self.offline {
+            packet[0] |= 0x08;
+        }
+        if self.cover_open {
+            packet[0] |= 0x20;
+        }
+        if self.paper_feeding {
+            packet[0] |= 0x40;
+        }
+        if self.cutter_error {
+            packet[1] |= 0x08;
+        }
+        if self.unrecoverable_error {
+            packet[1] |= 0x20;
+        }
+        if self.auto_recoverable_error {
+            packet[1] |= 0x40;
+        }
+        if self.paper_near_end {
+            packet[2] |= 0x03;
+        }
+        if self.paper_end {
+            packet[2] |= 0x0C;
+        }
+        packet
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dle_eot_round_trip() {
+        let status = Status {
+            offline: true,
+            cover_open: true,
+            cutter_error: true,
+            paper_near_end: true,
+            ..Status::default()
+        };
+        let decoded = Status::from_responses(
+            status.response(StatusRequest::Printer),
+            status.response(StatusRequest::Offline),
+            status.response(StatusRequest::Error),
+            status.response(StatusRequest::PaperSensor),
+        );
+        assert_eq!(decoded, status);
+        assert_eq!(status.response(StatusRequest::Printer), 0x1A);
+        assert_eq!(status.response(StatusRequest::PaperSensor), 0x1E);
+    }
+
+    #[test]
+    fn test_asb_round_trip() {
+        let status = Status {
+            offline: true,
+            paper_end_stop: true,
+            paper_end: true,
+            paper_near_end: true,
+            ..Status::default()
+        };
+        assert_eq!(status.to_asb(), [0x18, 0x00, 0x0F, 0x00]);
+        assert_eq!(Status::from_asb(status.to_asb()), status);
+    }
+}
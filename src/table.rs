@@ -0,0 +1,279 @@
+use std::string::String;
+use std::vec::Vec;
+
+use super::{BorderStyle, Command, Delay, Printer, PrinterError, Write};
+
+/// Split CSV text into records of fields.
+///
+/// Fields are separated by commas and records by line breaks. Fields may be
+/// quoted with `"` to contain commas, line breaks or doubled `""` quotes.
+/// Blank lines are skipped.
+pub fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => record.push(core::mem::take(&mut field)),
+            '\r' if !quoted => {}
+            '\n' if !quoted => {
+                if !record.is_empty() || !field.is_empty() {
+                    record.push(core::mem::take(&mut field));
+                    records.push(core::mem::take(&mut record));
+                }
+            }
+            c => field.push(c),
+        }
+    }
+    if !record.is_empty() || !field.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
+
+/// Column widths for `rows`, shrunk to fit `columns` characters with one
+/// space between columns.
+fn widths(rows: &[Vec<String>], columns: usize) -> Vec<usize> {
+    let mut widths: Vec<usize> = Vec::new();
+    for row in rows {
+        if widths.len() < row.len() {
+            widths.resize(row.len(), 0);
+        }
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let gaps = widths.len().saturating_sub(1);
+    while widths.iter().sum::<usize>() + gaps > columns {
+        let widest = widths.iter_mut().max().filter(|w| **w > 1);
+        match widest {
+            Some(width) => *width -= 1,
+            None => break,
+        }
+    }
+    widths
+}
+
+fn is_number(cell: &str) -> bool {
+    let cell = cell.trim();
+    !cell.is_empty()
+        && cell
+            .trim_start_matches(['-', '+'])
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == '.' || c == ',')
+}
+
+impl<T: Write, P: Delay> Printer<T, P> {
+    /// Print rows of cells as a table with automatically sized columns.
+    ///
+    /// Columns are as wide as their longest cell, then the widest are
+    /// narrowed until the table fits [`columns`](Printer::columns); cells
+    /// that don't fit are cut short, and line breaks in cells are printed as
+    /// spaces. Numbers are right-aligned.
+    pub fn print_table<I, R, S>(&mut self, rows: I) -> Result<(), PrinterError<<T as Write>::Error>>
+    where
+        I: IntoIterator<Item = R>,
+        R: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let rows: Vec<Vec<String>> = rows
+            .into_iter()
+            .map(|row| row.into_iter().map(|cell| cell.as_ref().into()).collect())
+            .collect();
+        let widths = widths(&rows, self.columns());
+        for row in &rows {
+            self.print_row(row, &widths)?;
+        }
+        Ok(())
+    }
+
+    /// Like [`print_table`](Printer::print_table), with a border around
+    /// the table and lines between the columns.
+    ///
+    /// The border is drawn with [`box_chars`](Printer::box_chars), so it
+    /// falls back to `+-|` unless a code page with box-drawing characters
+    /// was selected.
+    ///
+    /// ```
+    /// # use escpos_embedded::{BorderStyle, CodePage, Printer, Recorder};
+    /// let mut printer = Printer::new(Recorder::<256>::new());
+    /// printer.set_code_page(CodePage::Pc437).unwrap();
+    /// printer
+    ///     .print_table_bordered([["Latte", "3.20"], ["Scone", "2.10"]], BorderStyle::Single)
+    ///     .unwrap();
+    /// ```
+    pub fn print_table_bordered<I, R, S>(
+        &mut self,
+        rows: I,
+        style: BorderStyle,
+    ) -> Result<(), PrinterError<<T as Write>::Error>>
+    where
+        I: IntoIterator<Item = R>,
+        R: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let rows: Vec<Vec<String>> = rows
+            .into_iter()
+            .map(|row| row.into_iter().map(|cell| cell.as_ref().into()).collect())
+            .collect();
+        // The gaps between columns hold the inner lines; the outer ones
+        // take two more columns.
+        let widths = widths(&rows, self.columns().saturating_sub(2));
+        let chars = self.box_chars(style);
+        let top = [chars.top_left, chars.top_joint, chars.top_right];
+        self.print_border(&widths, top, chars.horizontal)?;
+        for row in &rows {
+            let mut line = Vec::new();
+            line.push(chars.vertical);
+            for (i, &width) in widths.iter().enumerate() {
+                let cell = row.get(i).map_or("", String::as_str);
+                line.extend_from_slice(cell_text(cell, width).as_bytes());
+                line.push(chars.vertical);
+            }
+            self.send(&Command::Raw(&line))?;
+            self.end_line()?;
+        }
+        let bottom = [chars.bottom_left, chars.bottom_joint, chars.bottom_right];
+        self.print_border(&widths, bottom, chars.horizontal)
+    }
+
+    /// Print a horizontal border line over columns of `widths`.
+    fn print_border(
+        &mut self,
+        widths: &[usize],
+        [left, joint, right]: [u8; 3],
+        horizontal: u8,
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        let mut line = Vec::from([left]);
+        for (i, &width) in widths.iter().enumerate() {
+            if i > 0 {
+                line.push(joint);
+            }
+            line.resize(line.len() + width, horizontal);
+        }
+        line.push(right);
+        self.send(&Command::Raw(&line))?;
+        self.end_line()
+    }
+
+    /// Print CSV text as a table, with the first record as a bold header
+    /// followed by a rule.
+    ///
+    /// See [`parse_csv`] for the accepted format and
+    /// [`print_table`](Printer::print_table) for the layout.
+    ///
+    /// ```
+    /// # use escpos_embedded::{Printer, Recorder};
+    /// let mut printer = Printer::new(Recorder::<256>::new());
+    /// printer.print_csv("item,qty\ncoffee,2\n\"tea, green\",1\n").unwrap();
+    /// ```
+    pub fn print_csv(&mut self, csv: &str) -> Result<(), PrinterError<<T as Write>::Error>> {
+        let rows = parse_csv(csv);
+        let widths = widths(&rows, self.columns());
+        let Some((header, body)) = rows.split_first() else {
+            return Ok(());
+        };
+        self.set_bold(true)?;
+        self.print_row(header, &widths)?;
+        self.set_bold(false)?;
+        let rule = widths.iter().sum::<usize>() + widths.len().saturating_sub(1);
+        self.write_line(&"-".repeat(rule))?;
+        for row in body {
+            self.print_row(row, &widths)?;
+        }
+        Ok(())
+    }
+
+    fn print_row(
+        &mut self,
+        row: &[String],
+        widths: &[usize],
+    ) -> Result<(), PrinterError<<T as Write>::Error>> {
+        let mut line = String::new();
+        for (i, &width) in widths.iter().enumerate() {
+            if i > 0 {
+                line.push(' ');
+            }
+            line.push_str(&cell_text(row.get(i).map_or("", String::as_str), width));
+        }
+        self.write_line(line.trim_end())
+    }
+}
+
+/// `cell` cut or padded to `width` characters, with numbers right-aligned.
+fn cell_text(cell: &str, width: usize) -> String {
+    let cell: String = cell
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .take(width)
+        .collect();
+    let pad = " ".repeat(width - cell.chars().count());
+    if is_number(&cell) {
+        pad + &cell
+    } else {
+        cell + &pad
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockTransport;
+    use crate::PaperWidth;
+    use std::vec;
+
+    #[test]
+    fn test_parse_csv() {
+        let records = parse_csv("a,\"b, \"\"c\"\"\"\r\n\n\"multi\nline\",2");
+        assert_eq!(records, [vec!["a", "b, \"c\""], vec!["multi\nline", "2"]]);
+    }
+
+    #[test]
+    fn test_print_table_fits_paper() {
+        let mut printer = Printer::builder(MockTransport::new())
+            .paper_width(PaperWidth::Dots(144))
+            .build()
+            .unwrap();
+        printer
+            .print_table([["Cappuccino", "3.50"], ["Tea", "12.00"]])
+            .unwrap();
+        assert_eq!(
+            printer.transport().written(),
+            b"Cappuc  3.50\nTea    12.00\n"
+        );
+    }
+
+    #[test]
+    fn test_print_table_bordered() {
+        let mut printer = Printer::builder(MockTransport::new())
+            .paper_width(PaperWidth::Dots(144))
+            .build()
+            .unwrap();
+        let rows = [["Cappuccino", "3.50"], ["Tea", "2.00"]];
+        printer
+            .print_table_bordered(rows, BorderStyle::Single)
+            .unwrap();
+        assert_eq!(
+            printer.transport_mut().take_written(),
+            b"+-----+----+\n|Cappu|3.50|\n|Tea  |2.00|\n+-----+----+\n"
+        );
+
+        printer.set_code_page(crate::CodePage::Pc437).unwrap();
+        printer.transport_mut().take_written();
+        printer
+            .print_table_bordered([["a", "b"]], BorderStyle::Double)
+            .unwrap();
+        assert_eq!(
+            printer.transport().written(),
+            b"\xC9\xCD\xCB\xCD\xBB\n\xBAa\xBAb\xBA\n\xC8\xCD\xCA\xCD\xBC\n"
+        );
+    }
+}
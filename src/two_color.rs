@@ -0,0 +1,80 @@
+use super::{command, Color, PrinterError};
+
+/// A black and red image for [`Printer::print_image_two_color`].
+///
+/// Each plane is a packed 1-bit bitmap like [`Image`](crate::Image)'s; a
+/// pixel set in both planes prints red.
+///
+/// [`Printer::print_image_two_color`]: crate::Printer::print_image_two_color
+pub struct TwoColorImage<D>
+where
+    D: AsRef<[u8]>,
+{
+    /// Image width in pixels.
+    pub width: u16,
+    /// Image height in pixels.
+    pub height: u16,
+    /// Pixels printed in the first color.
+    pub black: D,
+    /// Pixels printed in the second color.
+    pub red: D,
+}
+
+impl<D: AsRef<[u8]>> TwoColorImage<D> {
+    /// Return both planes if their lengths match the dimensions.
+    pub(crate) fn check_size<E>(&self) -> Result<(&[u8], &[u8]), PrinterError<E>> {
+        let expected = command::image_len("StoreGraphics", self.width, self.height, 8)?;
+        for plane in [self.black.as_ref(), self.red.as_ref()] {
+            if plane.len() != expected {
+                return Err(PrinterError::ImageSizeMismatch {
+                    command: "StoreGraphics",
+                    expected,
+                    actual: plane.len(),
+                });
+            }
+        }
+        Ok((self.black.as_ref(), self.red.as_ref()))
+    }
+}
+
+/// Split a palette image into the planes of a [`TwoColorImage`].
+///
+/// `pixels` holds one palette index per pixel, row by row, and `palette`
+/// maps each index to the color it prints in, or `None` for blank paper.
+/// `black` and `red` must each hold `width.div_ceil(8) * height` bytes;
+/// they are overwritten.
+///
+/// ```
+/// # use escpos_embedded::{split_planes, Color};
+/// let palette = [None, Some(Color::Black), Some(Color::Red)];
+/// let pixels = [0, 1, 2, 1];
+/// let (mut black, mut red) = ([0u8; 1], [0u8; 1]);
+/// split_planes(&pixels, 4, |i| palette[usize::from(i)], &mut black, &mut red);
+/// assert_eq!((black[0], red[0]), (0b0101_0000, 0b0010_0000));
+/// ```
+///
+/// # Panics
+///
+/// Panics if a plane is too small for the image.
+pub fn split_planes(
+    pixels: &[u8],
+    width: u16,
+    palette: impl Fn(u8) -> Option<Color>,
+    black: &mut [u8],
+    red: &mut [u8],
+) {
+    let width = usize::from(width);
+    let stride = width.div_ceil(8);
+    black.fill(0);
+    red.fill(0);
+    for (i, &index) in pixels.iter().enumerate() {
+        let (y, x) = (i / width.max(1), i % width.max(1));
+        let byte = y * stride + x / 8;
+        let bit = 0x80 >> (x % 8);
+        match palette(index) {
+            Some(Color::Black) => black[byte] |= bit,
+            Some(Color::Red) => red[byte] |= bit,
+            None => {}
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use super::{Delay, Printer, PrinterError, Read, Status, Write};
+
+/// A change in printer condition, reported by [`StatusWatcher`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum StatusEvent {
+    /// The cover was opened.
+    CoverOpened,
+    /// The cover was closed.
+    CoverClosed,
+    /// The roll paper is nearly used up.
+    PaperNearEnd,
+    /// The paper ran out.
+    PaperOut,
+    /// Paper is available again after running low or out.
+    PaperLoaded,
+    /// An error occurred: cutter, unrecoverable or automatically
+    /// recoverable.
+    ErrorSet,
+    /// All errors have cleared.
+    ErrorCleared,
+    /// The printer went offline.
+    Offline,
+    /// The printer came back online.
+    Online,
+}
+
+/// 0 with paper, 1 near end, 2 out.
+fn paper_level(status: &Status) -> u8 {
+    if status.paper_end || status.paper_end_stop {
+        2
+    } else if status.paper_near_end {
+        1
+    } else {
+        0
+    }
+}
+
+fn has_error(status: &Status) -> bool {
+    status.error
+        || status.cutter_error
+        || status.unrecoverable_error
+        || status.auto_recoverable_error
+}
+
+/// Turns successive [`Status`] reports into [`StatusEvent`]s.
+///
+/// Keeps the last status seen and calls a handler for each condition that
+/// changed, so applications don't have to diff status bytes themselves. The
+/// first status is compared against a printer with no problems, so
+/// conditions already present are reported too.
+///
+/// Usually fed from Automatic Status Back through
+/// [`Printer::poll_status_events`], but any status source works:
+///
+/// ```
+/// # use escpos_embedded::{Status, StatusEvent, StatusWatcher};
+/// let mut watcher = StatusWatcher::new();
+/// let mut events = Vec::new();
+/// let status = Status { cover_open: true, offline: true, ..Status::default() };
+/// watcher.update(status, |event| events.push(event));
+/// watcher.update(Status::default(), |event| events.push(event));
+/// assert_eq!(
+///     events,
+///     [
+///         StatusEvent::Offline,
+///         StatusEvent::CoverOpened,
+///         StatusEvent::CoverClosed,
+///         StatusEvent::Online,
+///     ]
+/// );
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct StatusWatcher {
+    last: Status,
+}
+
+impl StatusWatcher {
+    /// Create a watcher that hasn't seen a status yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The last status passed to [`update`](StatusWatcher::update).
+    pub fn last(&self) -> &Status {
+        &self.last
+    }
+
+    /// Record `status`, calling `on_event` for each change since the last
+    /// one.
+    ///
+    /// Events that take the printer out of service come first, in the order
+    /// offline, cover, paper, error; events that bring it back follow in the
+    /// reverse order, ending with [`StatusEvent::Online`].
+    pub fn update(&mut self, status: Status, mut on_event: impl FnMut(StatusEvent)) {
+        let last = core::mem::replace(&mut self.last, status);
+        if status.offline && !last.offline {
+            on_event(StatusEvent::Offline);
+        }
+        if status.cover_open && !last.cover_open {
+            on_event(StatusEvent::CoverOpened);
+        }
+        match (paper_level(&last), paper_level(&status)) {
+            (old, 1) if old != 1 => on_event(StatusEvent::PaperNearEnd),
+            (old, 2) if old != 2 => on_event(StatusEvent::PaperOut),
+            _ => {}
+        }
+        if has_error(&status) && !has_error(&last) {
+            on_event(StatusEvent::ErrorSet);
+        }
+        if !has_error(&status) && has_error(&last) {
+            on_event(StatusEvent::ErrorCleared);
+        }
+        if paper_level(&status) == 0 && paper_level(&last) != 0 {
+            on_event(StatusEvent::PaperLoaded);
+        }
+        if !status.cover_open && last.cover_open {
+            on_event(StatusEvent::CoverClosed);
+        }
+        if !status.offline && last.offline {
+            on_event(StatusEvent::Online);
+        }
+    }
+}
+
+impl<T, P: Delay> Printer<T, P>
+where
+    T: Write + Read<Error = <T as Write>::Error>,
+{
+    /// Read every pending Automatic Status Back report, passing each to
+    /// `watcher` and calling `on_event` for the changes it finds.
+    ///
+    /// Enable the reports first with
+    /// [`set_auto_status_back`](Printer::set_auto_status_back). Returns the
+    /// most recent report, or `None` if there was none.
+    pub fn poll_status_events(
+        &mut self,
+        watcher: &mut StatusWatcher,
+        mut on_event: impl FnMut(StatusEvent),
+    ) -> Result<Option<Status>, PrinterError<<T as Write>::Error>> {
+        let mut latest = None;
+        while let Some(status) = self.read_auto_status()? {
+            watcher.update(status, &mut on_event);
+            latest = Some(status);
+        }
+        Ok(latest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockTransport;
+    use std::vec::Vec;
+
+    #[test]
+    fn test_paper_transitions() {
+        let mut watcher = StatusWatcher::new();
+        let mut events = Vec::new();
+        let near_end = Status {
+            paper_near_end: true,
+            ..Status::default()
+        };
+        let out = Status {
+            paper_end: true,
+            offline: true,
+            ..near_end
+        };
+        for status in [near_end, near_end, out, Status::default()] {
+            watcher.update(status, |event| events.push(event));
+        }
+        assert_eq!(
+            events,
+            [
+                StatusEvent::PaperNearEnd,
+                StatusEvent::Offline,
+                StatusEvent::PaperOut,
+                StatusEvent::PaperLoaded,
+                StatusEvent::Online,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_poll_status_events() {
+        let error = Status {
+            cutter_error: true,
+            error: true,
+            offline: true,
+            ..Status::default()
+        };
+        let mut transport = MockTransport::new();
+        transport.push_response(&error.to_asb());
+        transport.push_response(&Status::default().to_asb());
+        let mut printer = Printer::new(transport);
+        let mut watcher = StatusWatcher::new();
+        let mut events = Vec::new();
+        let latest = printer
+            .poll_status_events(&mut watcher, |event| events.push(event))
+            .unwrap();
+        assert_eq!(latest, Some(Status::default()));
+        assert_eq!(
+            events,
+            [
+                StatusEvent::Offline,
+                StatusEvent::ErrorSet,
+                StatusEvent::ErrorCleared,
+                StatusEvent::Online,
+            ]
+        );
+        let none = printer.poll_status_events(&mut watcher, |_| unreachable!());
+        assert_eq!(none, Ok(None));
+    }
+}
@@ -0,0 +1,137 @@
+use js_sys::{Object, Promise, Reflect, Uint8Array};
+use std::vec::Vec;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+
+use super::AsyncWrite;
+
+// Web Serial is still marked unstable in `web-sys`, so bind the few parts
+// used here directly.
+#[wasm_bindgen]
+extern "C" {
+    type SerialPort;
+
+    #[wasm_bindgen(method)]
+    fn open(this: &SerialPort, options: &Object) -> Promise;
+
+    #[wasm_bindgen(method)]
+    fn close(this: &SerialPort) -> Promise;
+
+    #[wasm_bindgen(method, getter)]
+    fn writable(this: &SerialPort) -> Stream;
+
+    #[wasm_bindgen(method, getter)]
+    fn readable(this: &SerialPort) -> Stream;
+
+    type Stream;
+
+    #[wasm_bindgen(method, js_name = getWriter)]
+    fn get_writer(this: &Stream) -> StreamHandle;
+
+    #[wasm_bindgen(method, js_name = getReader)]
+    fn get_reader(this: &Stream) -> StreamHandle;
+
+    type StreamHandle;
+
+    #[wasm_bindgen(method)]
+    fn write(this: &StreamHandle, chunk: &Uint8Array) -> Promise;
+
+    #[wasm_bindgen(method)]
+    fn read(this: &StreamHandle) -> Promise;
+
+    #[wasm_bindgen(method, js_name = releaseLock)]
+    fn release_lock(this: &StreamHandle);
+}
+
+/// Transport over a Web Serial `SerialPort`, for printing from the browser.
+///
+/// The port comes from `navigator.serial.requestPort()`, which the browser
+/// only allows in response to a user gesture such as a click:
+///
+/// ```
+/// # use escpos_embedded::{AsyncPrinter, PrinterError, WebSerial};
+/// # use wasm_bindgen::JsValue;
+/// # async fn print(port: JsValue) -> Result<(), PrinterError<JsValue>> {
+/// let serial = WebSerial::open(port, 9600).await.map_err(|error| {
+///     PrinterError::Transport { command: "Open", error }
+/// })?;
+/// let mut printer = AsyncPrinter::new(serial);
+/// printer.write_line("Hello from the web").await?;
+/// printer.into_inner().close().await.ok();
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Errors are the `JsValue`s the browser rejects with, usually a
+/// `DOMException` such as `NetworkError` when the device is unplugged.
+pub struct WebSerial {
+    port: SerialPort,
+    writer: StreamHandle,
+    reader: Option<StreamHandle>,
+    pending: Vec<u8>,
+}
+
+impl WebSerial {
+    /// Open `port` at `baud_rate` (8N1, no flow control).
+    pub async fn open(port: JsValue, baud_rate: u32) -> Result<Self, JsValue> {
+        let options = Object::new();
+        Reflect::set(&options, &"baudRate".into(), &baud_rate.into())?;
+        let port: SerialPort = port.unchecked_into();
+        JsFuture::from(port.open(&options)).await?;
+        Ok(Self::from_open(port.into()))
+    }
+
+    /// Use a `SerialPort` already opened by the page.
+    pub fn from_open(port: JsValue) -> Self {
+        let port: SerialPort = port.unchecked_into();
+        let writer = port.writable().get_writer();
+        Self {
+            port,
+            writer,
+            reader: None,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Read received bytes into `buf`, waiting until at least one arrives.
+    ///
+    /// Returns `Ok(0)` if the port was closed. Web Serial has no read
+    /// timeout; race this against a timer to give up on a silent printer.
+    pub async fn read(&mut self, buf: &mut [u8]) -> Result<usize, JsValue> {
+        while self.pending.is_empty() {
+            let reader = self
+                .reader
+                .get_or_insert_with(|| self.port.readable().get_reader());
+            let result = JsFuture::from(reader.read()).await?;
+            if Reflect::get(&result, &"done".into())?.is_truthy() {
+                return Ok(0);
+            }
+            let value = Reflect::get(&result, &"value".into())?;
+            self.pending
+                .extend(value.unchecked_into::<Uint8Array>().to_vec());
+        }
+        let len = buf.len().min(self.pending.len());
+        for (slot, byte) in buf.iter_mut().zip(self.pending.drain(..len)) {
+            *slot = byte;
+        }
+        Ok(len)
+    }
+
+    /// Release the streams and close the port.
+    pub async fn close(self) -> Result<(), JsValue> {
+        self.writer.release_lock();
+        if let Some(reader) = &self.reader {
+            reader.release_lock();
+        }
+        JsFuture::from(self.port.close()).await.map(drop)
+    }
+}
+
+impl AsyncWrite for WebSerial {
+    type Error = JsValue;
+
+    async fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        let chunk = Uint8Array::from(data);
+        JsFuture::from(self.writer.write(&chunk)).await.map(drop)
+    }
+}
@@ -0,0 +1,159 @@
+use core::ffi::c_void;
+use core::ptr;
+use std::io;
+use std::vec::Vec;
+
+use super::Write;
+
+type Handle = *mut c_void;
+
+#[repr(C)]
+struct DocInfo1 {
+    doc_name: *const u16,
+    output_file: *const u16,
+    datatype: *const u16,
+}
+
+#[link(name = "winspool")]
+extern "system" {
+    fn OpenPrinterW(name: *const u16, handle: *mut Handle, defaults: *const c_void) -> i32;
+    fn ClosePrinter(handle: Handle) -> i32;
+    fn StartDocPrinterW(handle: Handle, level: u32, info: *const DocInfo1) -> u32;
+    fn EndDocPrinter(handle: Handle) -> i32;
+    fn StartPagePrinter(handle: Handle) -> i32;
+    fn EndPagePrinter(handle: Handle) -> i32;
+    fn WritePrinter(handle: Handle, buf: *const c_void, len: u32, written: *mut u32) -> i32;
+}
+
+fn wide(text: &str) -> Vec<u16> {
+    text.encode_utf16().chain([0]).collect()
+}
+
+fn check(ok: bool) -> io::Result<()> {
+    if ok {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+/// Transport to a Windows printer queue, sending raw bytes through the
+/// spooler.
+///
+/// For receipt printers installed as Windows printers (e.g. with a vendor
+/// USB driver) rather than exposed as a COM port. The bytes go to the
+/// queue as a `RAW` document, bypassing the driver's rendering, so the
+/// printer still receives plain ESC/POS.
+///
+/// The spooler prints a document once it is complete: output is collected
+/// until [`end_document`](WindowsPrinter::end_document) is called, so call
+/// it after each receipt.
+///
+/// ```no_run
+/// # use escpos_embedded::{Printer, WindowsPrinter};
+/// # fn main() -> std::io::Result<()> {
+/// let queue = WindowsPrinter::open("EPSON TM-T88V Receipt")?;
+/// let mut printer = Printer::new(queue);
+/// printer.write_line("Hello").unwrap();
+/// printer.transport_mut().end_document()?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Spooled queues don't pass data back, so this transport implements
+/// [`Write`] only.
+pub struct WindowsPrinter {
+    handle: Handle,
+    document: Vec<u16>,
+    in_document: bool,
+}
+
+// The handle is only used through `&mut self`.
+unsafe impl Send for WindowsPrinter {}
+
+impl WindowsPrinter {
+    /// Open the printer queue called `name`, as shown in Windows settings.
+    pub fn open(name: &str) -> io::Result<Self> {
+        let name = wide(name);
+        let mut handle = ptr::null_mut();
+        // SAFETY: `name` is NUL-terminated and `handle` is a valid out
+        // pointer; no defaults are passed.
+        check(unsafe { OpenPrinterW(name.as_ptr(), &mut handle, ptr::null()) } != 0)?;
+        Ok(Self {
+            handle,
+            document: wide("Receipt"),
+            in_document: false,
+        })
+    }
+
+    /// Name the documents shown in the print queue, "Receipt" by default.
+    pub fn document_name(mut self, name: &str) -> Self {
+        self.document = wide(name);
+        self
+    }
+
+    /// Finish the current document so the spooler prints it.
+    ///
+    /// Does nothing if nothing was written since the last call.
+    pub fn end_document(&mut self) -> io::Result<()> {
+        if !self.in_document {
+            return Ok(());
+        }
+        self.in_document = false;
+        // SAFETY: `handle` is open and a document and page were started.
+        let page = check(unsafe { EndPagePrinter(self.handle) } != 0);
+        check(unsafe { EndDocPrinter(self.handle) } != 0)?;
+        page
+    }
+
+    fn start_document(&mut self) -> io::Result<()> {
+        let datatype = wide("RAW");
+        let info = DocInfo1 {
+            doc_name: self.document.as_ptr(),
+            output_file: ptr::null(),
+            datatype: datatype.as_ptr(),
+        };
+        // SAFETY: `handle` is open and `info` points to NUL-terminated
+        // strings that outlive the call.
+        check(unsafe { StartDocPrinterW(self.handle, 1, &info) } != 0)?;
+        if let Err(err) = check(unsafe { StartPagePrinter(self.handle) } != 0) {
+            // SAFETY: the document was started above.
+            unsafe { EndDocPrinter(self.handle) };
+            return Err(err);
+        }
+        self.in_document = true;
+        Ok(())
+    }
+}
+
+impl Write for WindowsPrinter {
+    type Error = io::Error;
+
+    fn write(&mut self, mut data: &[u8]) -> Result<(), Self::Error> {
+        if !self.in_document {
+            self.start_document()?;
+        }
+        while !data.is_empty() {
+            let len = data.len().min(u32::MAX as usize) as u32;
+            let mut written = 0;
+            // SAFETY: `data` holds at least `len` bytes and `written` is a
+            // valid out pointer.
+            let ok = unsafe { WritePrinter(self.handle, data.as_ptr().cast(), len, &mut written) };
+            check(ok != 0)?;
+            if written == 0 {
+                return Err(io::ErrorKind::WriteZero.into());
+            }
+            data = &data[written as usize..];
+        }
+        Ok(())
+    }
+}
+
+impl Drop for WindowsPrinter {
+    fn drop(&mut self) {
+        // Print whatever was written rather than leave a stuck job behind.
+        let _ = self.end_document();
+        // SAFETY: `handle` was opened by `OpenPrinterW` and is closed once.
+        unsafe { ClosePrinter(self.handle) };
+    }
+}
@@ -0,0 +1,49 @@
+/// Split the first line of at most `columns` characters off `text`.
+///
+/// Breaks at the last space that fits, or mid-word if a single word is longer
+/// than the line. Spaces at the break are dropped. Returns the line and the
+/// rest of the text, or `None` once nothing is left.
+pub(crate) fn split_line(text: &str, columns: usize) -> (&str, Option<&str>) {
+    let Some((cut, _)) = text.char_indices().nth(columns) else {
+        return (text, None);
+    };
+    let (line, rest) = if text[cut..].starts_with(' ') {
+        (&text[..cut], &text[cut..])
+    } else {
+        match text[..cut].rfind(' ') {
+            Some(space) if !text[..space].trim_end().is_empty() => (&text[..space], &text[space..]),
+            _ => (&text[..cut], &text[cut..]),
+        }
+    };
+    let rest = rest.trim_start_matches(' ');
+    (
+        line.trim_end_matches(' '),
+        (!rest.is_empty()).then_some(rest),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec::Vec;
+
+    fn lines(text: &str, columns: usize) -> Vec<&str> {
+        let mut out = Vec::new();
+        let mut rest = Some(text);
+        while let Some(text) = rest {
+            let (line, next) = split_line(text, columns);
+            out.push(line);
+            rest = next;
+        }
+        out
+    }
+
+    #[test]
+    fn test_split_line() {
+        assert_eq!(lines("two words", 9), ["two words"]);
+        assert_eq!(lines("two words", 8), ["two", "words"]);
+        assert_eq!(lines("one  two three", 3), ["one", "two", "thr", "ee"]);
+        assert_eq!(lines("abcdefgh", 3), ["abc", "def", "gh"]);
+        assert_eq!(lines("£1.50 each", 5), ["£1.50", "each"]);
+    }
+}
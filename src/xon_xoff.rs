@@ -0,0 +1,256 @@
+use super::{Delay, Read, Write};
+
+/// Byte sent by the printer when it can accept data again.
+pub const XON: u8 = 0x11;
+/// Byte sent by the printer when its receive buffer is nearly full.
+pub const XOFF: u8 = 0x13;
+
+/// Size of the buffer holding other replies read while checking for XOFF.
+const STASH: usize = 32;
+
+/// Errors produced by an [`XonXoff`] transport.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum XonXoffError<E> {
+    /// The underlying transport failed.
+    Transport(E),
+    /// The printer sent XOFF and no XON arrived in time.
+    Timeout,
+}
+
+impl<E> From<E> for XonXoffError<E> {
+    fn from(err: E) -> Self {
+        XonXoffError::Transport(err)
+    }
+}
+
+/// Transport wrapper honouring XON/XOFF software flow control.
+///
+/// Many serial receipt printers default to XON/XOFF: they send XOFF when
+/// the receive buffer is nearly full and XON once it has drained. Before
+/// each chunk of data the wrapper reads whatever the printer has sent;
+/// after an XOFF it stops writing, polling every `poll_ms` until an XON
+/// arrives, and gives up with [`XonXoffError::Timeout`] after `max_polls`
+/// attempts.
+///
+/// ```
+/// # use escpos_embedded::{Delay, Printer, PrinterError, Read, Write, XonXoff, XonXoffError};
+/// # fn print<T, D>(uart: T, delay: D) -> Result<(), PrinterError<XonXoffError<<T as Write>::Error>>>
+/// # where
+/// #     T: Write + Read<Error = <T as Write>::Error>,
+/// #     D: Delay,
+/// # {
+/// let mut printer = Printer::new(XonXoff::new(uart, delay));
+/// printer.write_line("Sent as fast as the printer can take it")?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Bytes read meanwhile, such as status replies, are kept (up to 32) and
+/// returned by `read`. By default XON and XOFF are returned too, since
+/// binary replies like `GS I`, automatic status back and MICR data may
+/// contain 0x11 and 0x13; use
+/// [`strip_flow_control`](XonXoff::strip_flow_control) to remove them when
+/// only text or single-byte status replies are read. Either way a reply
+/// byte equal to XOFF pauses writing until an XON or the timeout. The
+/// inner transport's `read` must return `Ok(0)` when no data is
+/// available.
+pub struct XonXoff<T, D> {
+    inner: T,
+    delay: D,
+    chunk: usize,
+    poll_ms: u32,
+    max_polls: u32,
+    paused: bool,
+    strip: bool,
+    stash: [u8; STASH],
+    stashed: usize,
+}
+
+impl<T, D> XonXoff<T, D> {
+    /// Wrap `inner`, checking every 16 bytes and polling every 10ms for up
+    /// to 10 seconds.
+    pub fn new(inner: T, delay: D) -> Self {
+        Self {
+            inner,
+            delay,
+            chunk: 16,
+            poll_ms: 10,
+            max_polls: 1000,
+            paused: false,
+            strip: false,
+            stash: [0; STASH],
+            stashed: 0,
+        }
+    }
+
+    /// Check for XOFF before every `bytes` bytes.
+    ///
+    /// The printer keeps accepting data for a while after sending XOFF, so
+    /// this only needs to be small compared to that margin.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is zero.
+    pub fn chunk_size(mut self, bytes: usize) -> Self {
+        assert!(bytes > 0, "chunk size must be non-zero");
+        self.chunk = bytes;
+        self
+    }
+
+    /// Wait `poll_ms` between polls while paused, giving up after
+    /// `max_polls`.
+    pub fn polling(mut self, poll_ms: u32, max_polls: u32) -> Self {
+        self.poll_ms = poll_ms;
+        self.max_polls = max_polls;
+        self
+    }
+
+    /// Remove XON and XOFF from the bytes returned by `read`.
+    ///
+    /// Off by default, as it corrupts binary replies containing 0x11 or
+    /// 0x13.
+    pub fn strip_flow_control(mut self, strip: bool) -> Self {
+        self.strip = strip;
+        self
+    }
+
+    /// Whether the printer has sent XOFF and not yet XON.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Borrow the wrapped transport.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Mutably borrow the wrapped transport.
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consume the wrapper, returning the inner transport.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: Read, D> XonXoff<T, D> {
+    /// Read everything available, tracking flow control and stashing the
+    /// rest.
+    fn poll(&mut self) -> Result<(), T::Error> {
+        let mut buf = [0u8; 16];
+        loop {
+            let n = self.inner.read(&mut buf)?;
+            if n == 0 {
+                return Ok(());
+            }
+            for &byte in &buf[..n] {
+                match byte {
+                    XON => self.paused = false,
+                    XOFF => self.paused = true,
+                    _ => {}
+                }
+                let control = byte == XON || byte == XOFF;
+                if self.stashed < STASH && !(control && self.strip) {
+                    self.stash[self.stashed] = byte;
+                    self.stashed += 1;
+                }
+            }
+        }
+    }
+}
+
+impl<T, D> XonXoff<T, D>
+where
+    T: Write + Read<Error = <T as Write>::Error>,
+    D: Delay,
+{
+    fn wait_ready(&mut self) -> Result<(), XonXoffError<<T as Write>::Error>> {
+        self.poll()?;
+        let mut polls = 0;
+        while self.paused {
+            if polls >= self.max_polls {
+                return Err(XonXoffError::Timeout);
+            }
+            self.delay.delay_ms(self.poll_ms);
+            polls += 1;
+            self.poll()?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, D> Write for XonXoff<T, D>
+where
+    T: Write + Read<Error = <T as Write>::Error>,
+    D: Delay,
+{
+    type Error = XonXoffError<<T as Write>::Error>;
+
+    fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        for chunk in data.chunks(self.chunk) {
+            self.wait_ready()?;
+            self.inner.write(chunk)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Read, D> Read for XonXoff<T, D> {
+    type Error = XonXoffError<T::Error>;
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        self.poll()?;
+        let n = buf.len().min(self.stashed);
+        buf[..n].copy_from_slice(&self.stash[..n]);
+        self.stash.copy_within(n..self.stashed, 0);
+        self.stashed -= n;
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockTransport, RecordDelay};
+    use crate::Printer;
+
+    #[test]
+    fn test_pauses_on_xoff() {
+        let mock = MockTransport::with_responses(&[XOFF]);
+        let mut transport = XonXoff::new(mock, RecordDelay::default())
+            .chunk_size(2)
+            .polling(5, 3);
+        assert_eq!(transport.write(b"abc"), Err(XonXoffError::Timeout));
+        assert!(transport.is_paused());
+        assert!(transport.inner().written().is_empty());
+        transport.inner_mut().push_response(&[XON]);
+        transport.write(b"abc").unwrap();
+        assert!(!transport.is_paused());
+        assert_eq!(transport.inner().written(), b"abc");
+        assert_eq!(transport.delay.0, [5, 5, 5]);
+    }
+
+    #[test]
+    fn test_keeps_other_replies() {
+        let mock = MockTransport::with_responses(&[XOFF, 0x12, XON]);
+        let mut printer = Printer::new(XonXoff::new(mock, ()));
+        printer.write("x").unwrap();
+        let mut buf = [0u8; 4];
+        assert_eq!(printer.transport_mut().read(&mut buf).unwrap(), 3);
+        assert_eq!(buf[..3], [XOFF, 0x12, XON]);
+    }
+
+    #[test]
+    fn test_strip_flow_control() {
+        let mock = MockTransport::with_responses(&[XOFF, 0x12, XON]);
+        let transport = XonXoff::new(mock, ()).strip_flow_control(true);
+        let mut printer = Printer::new(transport);
+        printer.write("x").unwrap();
+        let mut buf = [0u8; 4];
+        assert_eq!(printer.transport_mut().read(&mut buf).unwrap(), 1);
+        assert_eq!(buf[0], 0x12);
+    }
+}